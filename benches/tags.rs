@@ -0,0 +1,69 @@
+//! Compares writing tags with `write!` against separate [`Write::write_str`] calls, and how `lignin-html` itself does.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::render_fragment;
+use std::fmt::Write;
+
+const PAIRS: usize = 10_000;
+
+fn tags(c: &mut Criterion) {
+	let mut group = c.benchmark_group("10k <div></div>");
+
+	group.bench_function("write!", |b| {
+		let mut html = String::with_capacity(PAIRS * 11);
+		b.iter(|| {
+			html.clear();
+			let name = black_box("div");
+			for _ in 0..PAIRS {
+				write!(html, "<{}", name).unwrap();
+				html.write_char('>').unwrap();
+				write!(html, "</{}>", name).unwrap();
+			}
+		})
+	});
+
+	group.bench_function("write_str", |b| {
+		let mut html = String::with_capacity(PAIRS * 11);
+		b.iter(|| {
+			html.clear();
+			let name = black_box("div");
+			for _ in 0..PAIRS {
+				html.write_char('<').unwrap();
+				html.write_str(name).unwrap();
+				html.write_char('>').unwrap();
+				html.write_str("</").unwrap();
+				html.write_str(name).unwrap();
+				html.write_char('>').unwrap();
+			}
+		})
+	});
+
+	let element = Element {
+		name: "div",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	};
+	let divs: Vec<Node<ThreadSafe>> = vec![
+		Node::HtmlElement {
+			element: &element,
+			dom_binding: None,
+		};
+		PAIRS
+	];
+	let vdom = Node::Multi(&divs);
+	group.bench_function("render_fragment", |b| {
+		let mut html = String::with_capacity(PAIRS * 11);
+		b.iter(|| {
+			html.clear();
+			render_fragment(black_box(&vdom), &mut html, 2).unwrap();
+		})
+	});
+
+	group.finish();
+}
+
+criterion_group!(benches, tags);
+criterion_main!(benches);
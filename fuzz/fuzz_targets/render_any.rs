@@ -0,0 +1,165 @@
+//! Renders arbitrary VDOMs with all entry points, which must never panic.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use bumpalo::Bump;
+use libfuzzer_sys::fuzz_target;
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ReorderableFragment, ThreadSafe};
+use lignin_html::{
+	render_document, render_fragment, render_fragment_with_options, render_svg_document,
+	CommentPolicy, PrettyOptions, RenderOptions,
+};
+
+#[derive(Debug, Arbitrary)]
+enum FuzzNode {
+	Comment(String),
+	HtmlElement(FuzzElement),
+	MathMlElement(FuzzElement),
+	SvgElement(FuzzElement),
+	Memoized(u64, Box<FuzzNode>),
+	Multi(Vec<FuzzNode>),
+	Keyed(Vec<(u32, FuzzNode)>),
+	Text(String),
+}
+
+#[derive(Debug, Arbitrary)]
+struct FuzzElement {
+	name: String,
+	is: Option<String>,
+	attributes: Vec<(String, String)>,
+	content: Box<FuzzNode>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct FuzzOptions {
+	transparent_depth_limit: u8,
+	xmlns: bool,
+	strict_namespaces: bool,
+	pretty: Option<u8>,
+	comment_policy: u8,
+	comment_guard_char: char,
+	strict_keys: bool,
+	sort_keyed: bool,
+	repeat_limit: Option<u8>,
+	harden_blank_targets: bool,
+	base_href: Option<String>,
+	ascii_only: bool,
+	reject_nul: bool,
+	named_entities: bool,
+	smart_ampersands: bool,
+	browser_canonical: bool,
+	strict_data_attributes: bool,
+	precise_escapable_raw_text: bool,
+	normalize_svg_case: bool,
+	roundtrip_safe: bool,
+	fragment_element_name: Option<String>,
+}
+
+impl FuzzOptions {
+	fn render_options(&self, depth_limit: usize) -> RenderOptions<'_> {
+		RenderOptions::new()
+			.with_depth_limit(depth_limit)
+			.with_transparent_depth_limit(self.transparent_depth_limit.into())
+			.with_xmlns(self.xmlns)
+			.with_strict_namespaces(self.strict_namespaces)
+			.with_pretty(self.pretty.map(|max_line_width| {
+				PrettyOptions::new().with_max_line_width(max_line_width.into())
+			}))
+			.with_comment_policy(match self.comment_policy % 3 {
+				0 => CommentPolicy::Legalize,
+				1 => CommentPolicy::Verbatim,
+				_ => CommentPolicy::ErrorIfLossy,
+			})
+			.with_comment_guard_char(self.comment_guard_char)
+			.with_strict_keys(self.strict_keys)
+			.with_sort_keyed(self.sort_keyed)
+			.with_repeat_limit(self.repeat_limit.map(Into::into))
+			.with_harden_blank_targets(self.harden_blank_targets)
+			.with_base_href(self.base_href.as_deref())
+			.with_ascii_only(self.ascii_only)
+			.with_reject_nul(self.reject_nul)
+			.with_named_entities(self.named_entities)
+			.with_smart_ampersands(self.smart_ampersands)
+			.with_browser_canonical(self.browser_canonical)
+			.with_strict_data_attributes(self.strict_data_attributes)
+			.with_precise_escapable_raw_text(self.precise_escapable_raw_text)
+			.with_normalize_svg_case(self.normalize_svg_case)
+			.with_roundtrip_safe(self.roundtrip_safe)
+			.with_fragment_element_name(self.fragment_element_name.as_deref())
+	}
+}
+
+fn vdom<'a>(bump: &'a Bump, node: &'a FuzzNode) -> Node<'a, ThreadSafe> {
+	match node {
+		FuzzNode::Comment(comment) => Node::Comment {
+			comment,
+			dom_binding: None,
+		},
+		FuzzNode::HtmlElement(element) => Node::HtmlElement {
+			element: self::element(bump, element),
+			dom_binding: None,
+		},
+		FuzzNode::MathMlElement(element) => Node::MathMlElement {
+			element: self::element(bump, element),
+			dom_binding: None,
+		},
+		FuzzNode::SvgElement(element) => Node::SvgElement {
+			element: self::element(bump, element),
+			dom_binding: None,
+		},
+		FuzzNode::Memoized(state_key, content) => Node::Memoized {
+			state_key: *state_key,
+			content: bump.alloc(vdom(bump, content)),
+		},
+		FuzzNode::Multi(nodes) => {
+			Node::Multi(bump.alloc_slice_fill_iter(nodes.iter().map(|node| vdom(bump, node))))
+		}
+		FuzzNode::Keyed(fragments) => Node::Keyed(
+			bump.alloc_slice_fill_iter(fragments.iter().map(|(dom_key, content)| {
+				ReorderableFragment {
+					dom_key: *dom_key,
+					content: vdom(bump, content),
+				}
+			})),
+		),
+		FuzzNode::Text(text) => Node::Text {
+			text,
+			dom_binding: None,
+		},
+	}
+}
+
+fn element<'a>(bump: &'a Bump, element: &'a FuzzElement) -> &'a Element<'a, ThreadSafe> {
+	bump.alloc(Element {
+		name: &element.name,
+		creation_options: ElementCreationOptions::new().with_is(element.is.as_deref()),
+		attributes: bump.alloc_slice_fill_iter(
+			element
+				.attributes
+				.iter()
+				.map(|(name, value)| Attribute { name, value }),
+		),
+		content: vdom(bump, &element.content),
+		event_bindings: &[],
+	})
+}
+
+fuzz_target!(|input: (FuzzNode, u8, FuzzOptions)| {
+	let (node, depth_limit, options) = input;
+	let bump = Bump::new();
+	let vdom = vdom(&bump, &node);
+
+	let mut output = String::new();
+	let _ = render_fragment(&vdom, &mut output, depth_limit.into());
+	output.clear();
+	let _ = render_document(&vdom, &mut output, depth_limit.into());
+	output.clear();
+	let _ = render_svg_document(&vdom, &mut output, depth_limit.into());
+	output.clear();
+	let _ = render_fragment_with_options(
+		&vdom,
+		&mut output,
+		&options.render_options(depth_limit.into()),
+	);
+});
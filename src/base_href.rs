@@ -0,0 +1,67 @@
+//! Conservative URL resolution for [`RenderOptions::base_href`](`crate::RenderOptions::base_href`).
+//!
+//! See <https://url.spec.whatwg.org/#concept-basic-url-parser>, of which only the unambiguous cases are handled here.
+
+use alloc::string::String;
+
+/// The length of the scheme at the start of `url`, if it has one (not counting the `:`).
+///
+/// See <https://url.spec.whatwg.org/#scheme-state>.
+fn scheme_len(url: &str) -> Option<usize> {
+	let bytes = url.as_bytes();
+	if !bytes.first()?.is_ascii_alphabetic() {
+		return None;
+	}
+	let len = bytes
+		.iter()
+		.position(|&byte| !(byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.')))?;
+	if bytes[len] == b':' {
+		Some(len)
+	} else {
+		None
+	}
+}
+
+/// Resolves `url` against `base`, or returns [`None`] if it should be left as is.
+///
+/// That's the case if `url` is already absolute, and also if either URL is unusual enough that resolving it here could
+/// differ from what a browser does: `base` must be hierarchical, like `https://example.com/`, and `url` must be
+/// scheme-relative (`//host/path`), path-absolute (`/path`) or path-relative (`path`), without whitespace or backslashes.
+///
+/// Dot segments are kept, since they're resolved the same way when the result is parsed.
+pub(crate) fn resolve(base: &str, url: &str) -> Option<String> {
+	let scheme = &base[..scheme_len(base)?];
+	let after_scheme = base[scheme.len() + 1..].strip_prefix("//")?;
+
+	if url.is_empty()
+		|| scheme_len(url).is_some()
+		|| url.starts_with(&['?', '#'][..])
+		|| url.contains(|c: char| c.is_ascii_whitespace() || c.is_ascii_control() || c == '\\')
+	{
+		return None;
+	}
+
+	let authority_len = after_scheme
+		.find(&['/', '?', '#'][..])
+		.unwrap_or(after_scheme.len());
+	let (authority, path) = after_scheme.split_at(authority_len);
+	if authority.is_empty() {
+		return None;
+	}
+
+	let mut resolved = String::from(scheme);
+	resolved.push(':');
+	if !url.starts_with("//") {
+		resolved.push_str("//");
+		resolved.push_str(authority);
+		if !url.starts_with('/') {
+			let path = &path[..path.find(&['?', '#'][..]).unwrap_or(path.len())];
+			resolved.push_str(match path.rfind('/') {
+				Some(i) => &path[..=i],
+				None => "/",
+			});
+		}
+	}
+	resolved.push_str(url);
+	Some(resolved)
+}
@@ -0,0 +1,223 @@
+//! A small builder for owned [`Node`] trees, as less verbose alternative to [`lignin`] literals.
+//!
+//! Only available with the `"alloc"` feature.
+//!
+//! ```
+//! use lignin_html::{
+//!     builder::{el, text, OwnedNode},
+//!     render_fragment, RenderOptions,
+//! };
+//!
+//! let paragraph: OwnedNode = el("p")
+//!     .attr("class", "greeting")
+//!     .child(text("Hello & welcome!"))
+//!     .into();
+//!
+//! let html = paragraph
+//!     .with_node(|node| {
+//!         let mut html = String::new();
+//!         render_fragment(node, &mut html, RenderOptions::DEFAULT_DEPTH_LIMIT)
+//!             .map(|()| html)
+//!             .map_err(|error| error.to_string())
+//!     })
+//!     .unwrap()
+//!     .unwrap();
+//! assert_eq!(html, "<p class=greeting>Hello &amp; welcome!</p>");
+//! ```
+
+use crate::{validate_attribute_name, ElementKind, Error, ErrorKind};
+use alloc::{string::String, vec::Vec};
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+
+/// An owned [`Node`], as created by [`el`], [`text`], [`comment`] and [`multi`].
+///
+/// Use [`OwnedNode::with_node`] to render it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnedNode {
+	/// See [`el`].
+	Element(OwnedElement),
+	/// See [`text`].
+	Text(String),
+	/// See [`comment`].
+	Comment(String),
+	/// See [`multi`].
+	Multi(Vec<OwnedNode>),
+}
+
+/// An owned HTML element, as created by [`el`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedElement {
+	name: String,
+	attributes: Vec<(String, String)>,
+	children: Vec<OwnedNode>,
+}
+
+/// Creates an HTML element named `name`, without attributes or content.
+#[must_use]
+pub fn el(name: impl Into<String>) -> OwnedElement {
+	OwnedElement {
+		name: name.into(),
+		attributes: Vec::new(),
+		children: Vec::new(),
+	}
+}
+
+/// Creates a text node. Its text is escaped when rendered.
+#[must_use]
+pub fn text(text: impl Into<String>) -> OwnedNode {
+	OwnedNode::Text(text.into())
+}
+
+/// Creates a comment.
+#[must_use]
+pub fn comment(comment: impl Into<String>) -> OwnedNode {
+	OwnedNode::Comment(comment.into())
+}
+
+/// Groups `nodes` without a surrounding element.
+#[must_use]
+pub fn multi(nodes: impl IntoIterator<Item = OwnedNode>) -> OwnedNode {
+	OwnedNode::Multi(nodes.into_iter().collect())
+}
+
+impl OwnedElement {
+	/// Appends an attribute.
+	#[must_use]
+	pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.attributes.push((name.into(), value.into()));
+		self
+	}
+
+	/// Appends a child node.
+	#[must_use]
+	pub fn child(mut self, child: impl Into<OwnedNode>) -> Self {
+		self.children.push(child.into());
+		self
+	}
+}
+
+impl From<OwnedElement> for OwnedNode {
+	fn from(element: OwnedElement) -> Self {
+		Self::Element(element)
+	}
+}
+
+impl OwnedNode {
+	/// Calls `f` with a [`Node`] borrowing from `self`, which can be passed to e.g. [`render_fragment`](`crate::render_fragment`).
+	///
+	/// Element and attribute names are validated before `f` is called.
+	///
+	/// Each element's content is a [`Node::Multi`], which counts towards the [`RenderOptions::transparent_depth_limit`](`crate::RenderOptions::transparent_depth_limit`)
+	/// rather than the depth limit when rendering.
+	///
+	/// # Errors
+	///
+	/// Iff any element or attribute name is invalid, in which case `f` isn't called.
+	pub fn with_node<R>(
+		&self,
+		f: impl FnOnce(&Node<'_, ThreadSafe>) -> R,
+	) -> Result<R, Error<'_, ThreadSafe>> {
+		self.validate()?;
+
+		let mut f = Some(f);
+		let mut result = None;
+		materialize(&[self], &mut |nodes| {
+			if let (Some(f), [node]) = (f.take(), nodes) {
+				result = Some(f(node))
+			}
+		});
+		result.ok_or(Error(ErrorKind::Internal))
+	}
+
+	fn validate(&self) -> Result<(), Error<'_, ThreadSafe>> {
+		if let OwnedNode::Element(element) = self {
+			ElementKind::detect(&element.name)
+				.map_err(|reason| Error(ErrorKind::InvalidElementName(&element.name, reason)))?;
+			for (name, _) in &element.attributes {
+				validate_attribute_name(name, false)?;
+			}
+		}
+		self.children().iter().try_for_each(OwnedNode::validate)
+	}
+
+	fn children(&self) -> &[OwnedNode] {
+		match self {
+			OwnedNode::Element(element) => &element.children,
+			OwnedNode::Multi(nodes) => nodes,
+			OwnedNode::Text(_) | OwnedNode::Comment(_) => &[],
+		}
+	}
+}
+
+/// Converts `nodes` into [`Node`]s and calls `f` with them.
+///
+/// [`Node`]s can only reference their children, so this recurses into all children on the next level first.
+/// The recursion depth is the depth of the tree.
+fn materialize(nodes: &[&OwnedNode], f: &mut dyn FnMut(&[Node<'_, ThreadSafe>])) {
+	let children: Vec<&OwnedNode> = nodes.iter().flat_map(|node| node.children()).collect();
+	let attributes: Vec<Vec<Attribute>> = nodes
+		.iter()
+		.map(|node| match node {
+			OwnedNode::Element(element) => element
+				.attributes
+				.iter()
+				.map(|(name, value)| Attribute { name, value })
+				.collect(),
+			OwnedNode::Text(_) | OwnedNode::Comment(_) | OwnedNode::Multi(_) => Vec::new(),
+		})
+		.collect();
+	let mut ranges = Vec::with_capacity(nodes.len());
+	let mut start = 0;
+	for node in nodes {
+		let end = start + node.children().len();
+		ranges.push(start..end);
+		start = end;
+	}
+
+	let mut build = |children: &[Node<'_, ThreadSafe>]| {
+		let elements: Vec<Option<Element<ThreadSafe>>> = nodes
+			.iter()
+			.zip(&attributes)
+			.zip(&ranges)
+			.map(|((node, attributes), range)| match node {
+				OwnedNode::Element(element) => Some(Element {
+					name: &element.name,
+					creation_options: ElementCreationOptions::new(),
+					attributes,
+					content: Node::Multi(&children[range.clone()]),
+					event_bindings: &[],
+				}),
+				OwnedNode::Text(_) | OwnedNode::Comment(_) | OwnedNode::Multi(_) => None,
+			})
+			.collect();
+		let nodes: Vec<Node<ThreadSafe>> = nodes
+			.iter()
+			.zip(&elements)
+			.zip(&ranges)
+			.map(|((node, element), range)| match (node, element) {
+				(OwnedNode::Element(_), Some(element)) => Node::HtmlElement {
+					element,
+					dom_binding: None,
+				},
+				(OwnedNode::Text(text), _) => Node::Text {
+					text,
+					dom_binding: None,
+				},
+				(OwnedNode::Comment(comment), _) => Node::Comment {
+					comment,
+					dom_binding: None,
+				},
+				(OwnedNode::Multi(_), _) | (OwnedNode::Element(_), None) => {
+					Node::Multi(&children[range.clone()])
+				}
+			})
+			.collect();
+		f(&nodes)
+	};
+
+	if children.is_empty() {
+		build(&[])
+	} else {
+		materialize(&children, &mut build)
+	}
+}
@@ -0,0 +1,78 @@
+//! HTTP/1.1 chunked transfer coding for streamed output.
+
+use core::fmt::{self, Write};
+use std::io;
+
+/// A [`Write`] that frames everything written to it as [chunked transfer coding](https://www.rfc-editor.org/rfc/rfc9112#name-chunked-transfer-coding)
+/// into a [`std::io::Write`], e.g. an HTTP response body.
+///
+/// Each non-empty [`write_str`](`Write::write_str`) becomes one `<hex length>\r\n<data>\r\n` chunk.
+/// Renderers write many small pieces, so `inner` should usually be buffered.
+///
+/// Call [`ChunkedWriter::finish`] to write the terminating `0\r\n\r\n` chunk.
+///
+/// ```
+/// use lignin_html::{lignin::Node, render_fragment, ChunkedWriter};
+///
+/// let mut writer = ChunkedWriter::new(Vec::new());
+/// render_fragment(
+///     &Node::Text { text: "Hello!", dom_binding: None }.prefer_thread_safe(),
+///     &mut writer,
+///     1,
+/// ).unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"6\r\nHello!\r\n0\r\n\r\n");
+/// ```
+#[derive(Debug)]
+pub struct ChunkedWriter<W: io::Write> {
+	inner: W,
+	error: Option<io::Error>,
+}
+
+impl<W: io::Write> ChunkedWriter<W> {
+	/// Creates a new [`ChunkedWriter`] that writes chunks into `inner`.
+	#[must_use]
+	pub const fn new(inner: W) -> Self {
+		Self { inner, error: None }
+	}
+
+	/// Returns a reference to the wrapped [`std::io::Write`].
+	#[must_use]
+	pub const fn inner(&self) -> &W {
+		&self.inner
+	}
+
+	/// Writes the terminating chunk and unwraps the wrapped [`std::io::Write`].
+	///
+	/// # Errors
+	///
+	/// With the first [`std::io::Error`] that made a write fail (which then failed with [`fmt::Error`]),
+	/// or iff writing the terminating chunk fails.
+	/// In the former case, the terminating chunk isn't written, so the body is recognizably incomplete.
+	pub fn finish(mut self) -> io::Result<W> {
+		if let Some(error) = self.error {
+			return Err(error);
+		}
+		self.inner.write_all(b"0\r\n\r\n")?;
+		Ok(self.inner)
+	}
+}
+
+impl<W: io::Write> Write for ChunkedWriter<W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if self.error.is_some() {
+			return Err(fmt::Error);
+		}
+		// An empty chunk would end the body.
+		if s.is_empty() {
+			return Ok(());
+		}
+		let inner = &mut self.inner;
+		write!(inner, "{:x}\r\n", s.len())
+			.and_then(|()| inner.write_all(s.as_bytes()))
+			.and_then(|()| inner.write_all(b"\r\n"))
+			.map_err(|error| {
+				self.error = Some(error);
+				fmt::Error
+			})
+	}
+}
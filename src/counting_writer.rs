@@ -0,0 +1,63 @@
+//! Counting how much is written.
+
+use core::fmt::{self, Write};
+
+/// A [`Write`] that forwards everything to `inner` while counting the UTF-8 bytes written successfully.
+///
+/// ```
+/// use lignin_html::{lignin::Node, render_fragment, CountingWriter};
+///
+/// let mut html = String::new();
+/// let mut writer = CountingWriter::new(&mut html);
+/// render_fragment(
+///     &Node::Text { text: "Grüße", dom_binding: None }.prefer_thread_safe(),
+///     &mut writer,
+///     1,
+/// ).unwrap();
+/// assert_eq!(writer.count(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CountingWriter<W: Write> {
+	inner: W,
+	count: usize,
+}
+
+impl<W: Write> CountingWriter<W> {
+	/// Creates a new [`CountingWriter`] with a count of `0`.
+	#[must_use]
+	pub const fn new(inner: W) -> Self {
+		Self { inner, count: 0 }
+	}
+
+	/// How many bytes (not characters) were written to `inner` so far.
+	#[must_use]
+	pub const fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Returns a reference to the wrapped [`Write`].
+	#[must_use]
+	pub const fn inner(&self) -> &W {
+		&self.inner
+	}
+
+	/// Unwraps the wrapped [`Write`].
+	#[must_use]
+	pub fn into_inner(self) -> W {
+		self.inner
+	}
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.inner.write_str(s)?;
+		self.count += s.len();
+		Ok(())
+	}
+
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		self.inner.write_char(c)?;
+		self.count += c.len_utf8();
+		Ok(())
+	}
+}
@@ -0,0 +1,183 @@
+//! Introspection of how [`Node`]s are rendered, for debugging.
+
+use crate::{AttributeValueMode, ElementKind};
+use alloc::{string::String, vec::Vec};
+use lignin::{Element, Node, ThreadSafety};
+
+/// How a [`Node`] would be rendered by [`render_fragment`](`crate::render_fragment`), as returned by [`describe_node`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct NodeDescription {
+	/// Which kind of [`Node`] this is.
+	pub kind: NodeKind,
+	/// Details about the element, iff [`NodeDescription::kind`] is [`NodeKind::HtmlElement`], [`NodeKind::MathMlElement`] or [`NodeKind::SvgElement`].
+	pub element: Option<ElementDescription>,
+}
+
+/// The variant of a [`Node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NodeKind {
+	/// [`Node::Comment`]
+	Comment,
+	/// [`Node::HtmlElement`]
+	HtmlElement,
+	/// [`Node::MathMlElement`]
+	MathMlElement,
+	/// [`Node::SvgElement`]
+	SvgElement,
+	/// [`Node::Memoized`]
+	Memoized,
+	/// [`Node::Multi`]
+	Multi,
+	/// [`Node::Keyed`]
+	Keyed,
+	/// [`Node::Text`]
+	Text,
+	/// [`Node::RemnantSite`]
+	RemnantSite,
+}
+
+/// How an element would be rendered, as part of a [`NodeDescription`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ElementDescription {
+	/// The element's name.
+	pub name: String,
+	/// How the element's tags and content are written, or [`None`] if its name is invalid so that it can't be rendered.
+	pub content_model: Option<ContentModel>,
+	/// Whether a newline is written directly after the start tag, since parsers drop one there (as for `pre` and `textarea`).
+	pub newline_after_start_tag: bool,
+	/// Each attribute's name, and how its value is quoted, in order.
+	pub attributes: Vec<(String, AttributeQuoting)>,
+}
+
+/// How an element's tags and content are written. See <https://html.spec.whatwg.org/multipage/syntax.html#elements-2>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentModel {
+	/// A [***void element***](https://html.spec.whatwg.org/multipage/syntax.html#void-elements) like `br`, which has no end tag and must be empty.
+	Void,
+	/// The `template` element.
+	Template,
+	/// A [***raw text element***](https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements) (`script` or `style`), whose text isn't escaped.
+	RawText,
+	/// An [***escapable raw text element***](https://html.spec.whatwg.org/multipage/syntax.html#escapable-raw-text-elements) (`title` or `textarea`),
+	/// which can only contain text.
+	EscapableRawText,
+	/// An empty foreign (SVG or `MathML`) element, which is written self-closing.
+	ForeignSelfClosing,
+	/// A non-empty foreign (SVG or `MathML`) element.
+	Foreign,
+	/// Any other element.
+	Normal,
+}
+
+/// How an attribute value is written. See <https://html.spec.whatwg.org/multipage/syntax.html#attributes-2>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeQuoting {
+	/// The value is empty, so only the attribute's name is written.
+	Empty,
+	/// The value is written without quotes, as in `class=a`.
+	Unquoted,
+	/// The value is written in single quotes, as in `title='"a"'`.
+	SingleQuoted,
+	/// The value is written in double quotes, as in `class="a b"`.
+	DoubleQuoted,
+}
+
+/// Describes how `vdom` would be rendered by [`render_fragment`](`crate::render_fragment`) (with default [`RenderOptions`](`crate::RenderOptions`)),
+/// e.g. to find out why an element got a leading newline or an attribute value is quoted.
+///
+/// Only `vdom` itself is described, not its content, and nothing is validated beyond the element name.
+///
+/// ```
+/// use lignin_html::{
+///     describe_node,
+///     lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe},
+///     AttributeQuoting, ContentModel,
+/// };
+///
+/// let vdom: Node<ThreadSafe> = Node::HtmlElement {
+///     element: &Element {
+///         name: "pre",
+///         creation_options: ElementCreationOptions::new(),
+///         attributes: &[Attribute { name: "class", value: "a b" }],
+///         content: Node::Multi(&[]),
+///         event_bindings: &[],
+///     },
+///     dom_binding: None,
+/// };
+///
+/// let element = describe_node(&vdom).element.unwrap();
+/// assert_eq!(element.content_model, Some(ContentModel::Normal));
+/// assert!(element.newline_after_start_tag);
+/// assert_eq!(element.attributes, [("class".to_string(), AttributeQuoting::DoubleQuoted)]);
+/// ```
+#[must_use]
+pub fn describe_node<S: ThreadSafety>(vdom: &Node<S>) -> NodeDescription {
+	let (kind, element) = match vdom {
+		Node::Comment { .. } => (NodeKind::Comment, None),
+		Node::HtmlElement { element, .. } => (
+			NodeKind::HtmlElement,
+			Some(describe_element(element, false)),
+		),
+		Node::MathMlElement { element, .. } => (
+			NodeKind::MathMlElement,
+			Some(describe_element(element, true)),
+		),
+		Node::SvgElement { element, .. } => {
+			(NodeKind::SvgElement, Some(describe_element(element, true)))
+		}
+		Node::Memoized { .. } => (NodeKind::Memoized, None),
+		Node::Multi(_) => (NodeKind::Multi, None),
+		Node::Keyed(_) => (NodeKind::Keyed, None),
+		Node::Text { .. } => (NodeKind::Text, None),
+		Node::RemnantSite(_) => (NodeKind::RemnantSite, None),
+	};
+	NodeDescription { kind, element }
+}
+
+/// Like [`start_element`](`crate::start_element`) and [`write_attribute`](`crate::write_attribute`) decide it.
+fn describe_element<S: ThreadSafety>(element: &Element<S>, foreign: bool) -> ElementDescription {
+	let kind = ElementKind::detect(element.name).ok();
+	let content_model = kind.map(|kind| match kind {
+		_ if foreign && element.content.dom_empty() => ContentModel::ForeignSelfClosing,
+		_ if foreign => ContentModel::Foreign,
+		ElementKind::Void => ContentModel::Void,
+		ElementKind::Template => ContentModel::Template,
+		ElementKind::RawText => ContentModel::RawText,
+		ElementKind::EscapableRawText | ElementKind::EscapableRawTextTextarea => {
+			ContentModel::EscapableRawText
+		}
+		ElementKind::ForeignSelfClosing => ContentModel::ForeignSelfClosing,
+		ElementKind::ForeignNotSelfClosing => ContentModel::Foreign,
+		ElementKind::NormalPre
+		| ElementKind::Normal
+		| ElementKind::PotentialCustomElementNameCharacter
+		| ElementKind::Dash
+		| ElementKind::Invalid => ContentModel::Normal,
+	});
+	ElementDescription {
+		name: element.name.into(),
+		content_model,
+		newline_after_start_tag: !foreign
+			&& matches!(
+				kind,
+				Some(ElementKind::NormalPre | ElementKind::EscapableRawTextTextarea)
+			),
+		attributes: element
+			.attributes
+			.iter()
+			.map(|attribute| {
+				let quoting = match AttributeValueMode::detect(attribute.value) {
+					AttributeValueMode::Empty => AttributeQuoting::Empty,
+					AttributeValueMode::Unquoted => AttributeQuoting::Unquoted,
+					AttributeValueMode::SingleQuoted => AttributeQuoting::SingleQuoted,
+					AttributeValueMode::DoubleQuoted => AttributeQuoting::DoubleQuoted,
+				};
+				(attribute.name.into(), quoting)
+			})
+			.collect(),
+	}
+}
@@ -0,0 +1,226 @@
+//! Escaping helpers that can be used independently of the [`Node`](`lignin::Node`) renderers.
+
+use crate::RenderOptions;
+use core::fmt::{self, Write};
+use logos::Logos;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+//FIXME: I haven't found the actual reference on this yet.
+#[derive(Logos)]
+enum PlainTextToken<'a> {
+	/// This could close this element or start a new one.
+	#[token("<")]
+	Lt,
+	/// See <https://html.spec.whatwg.org/multipage/syntax.html#character-references>.
+	///
+	/// This could be an ambiguous ampersand or part something that would be parsed as character reference, so it's escaped unless it starts a known character reference
+	/// and [`RenderOptions::smart_ampersands`] is enabled.
+	#[token("&")]
+	Ampersand,
+	/// This is only escaped in XML, where it may not appear as part of `]]>`, and with [`RenderOptions::browser_canonical`].
+	///
+	/// See <https://www.w3.org/TR/xml/#syntax>.
+	#[token(">")]
+	Gt,
+	#[regex("[^<&>]+")]
+	SafeVerbatim(&'a str),
+	#[error]
+	Error,
+}
+
+/// Writes non-ASCII characters into the wrapped [`Write`] as named character references if [`RenderOptions::named_entities`] is set
+/// and as hexadecimal numeric character references if [`RenderOptions::ascii_only`] is set, and everything else as-is.
+///
+/// U+00A0 is also written as `&nbsp;` with [`RenderOptions::browser_canonical`].
+///
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#character-references>.
+pub(crate) struct CharacterReferences<'a, W: Write> {
+	target: &'a mut W,
+	ascii_only: bool,
+	named_entities: bool,
+	nbsp: bool,
+}
+
+impl<'a, W: Write> CharacterReferences<'a, W> {
+	pub(crate) fn new(target: &'a mut W, options: &RenderOptions) -> Self {
+		Self {
+			target,
+			ascii_only: options.ascii_only(),
+			named_entities: options.named_entities() && !options.xml,
+			nbsp: options.browser_canonical() && !options.xml,
+		}
+	}
+}
+
+impl<W: Write> Write for CharacterReferences<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if !(self.ascii_only || self.named_entities || self.nbsp) || s.is_ascii() {
+			return self.target.write_str(s);
+		}
+		for c in s.chars() {
+			self.write_char(c)?
+		}
+		Ok(())
+	}
+
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		if c.is_ascii() {
+			return self.target.write_char(c);
+		}
+		match named_character_reference(c) {
+			Some(name) if self.named_entities || self.nbsp && c == '\u{A0}' => {
+				write!(self.target, "&{};", name)
+			}
+			_ if self.ascii_only => write!(self.target, "&#x{:x};", c as u32),
+			_ => self.target.write_char(c),
+		}
+	}
+}
+
+/// The curated subset of named character references used for [`RenderOptions::named_entities`].
+///
+/// These are always written with their terminating semicolon, so they can't be misparsed.
+/// See <https://html.spec.whatwg.org/multipage/named-characters.html#named-character-references>.
+fn named_character_reference(c: char) -> Option<&'static str> {
+	Some(match c {
+		'\u{A0}' => "nbsp",
+		'\u{A9}' => "copy",
+		'\u{AD}' => "shy",
+		'\u{AE}' => "reg",
+		'\u{2013}' => "ndash",
+		'\u{2014}' => "mdash",
+		'\u{2026}' => "hellip",
+		'\u{2122}' => "trade",
+		_ => return None,
+	})
+}
+
+/// Character references that are left as-is with [`RenderOptions::smart_ampersands`].
+///
+/// Only ones that are terminated by a semicolon are recognized, since those are unambiguous in any context.  
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#character-references>.
+#[derive(Logos)]
+enum CharacterReferenceToken {
+	#[regex("&#[0-9]+;")]
+	#[regex("&#[xX][0-9a-fA-F]+;")]
+	Numeric,
+	/// A small subset of <https://html.spec.whatwg.org/multipage/named-characters.html#named-character-references>.
+	#[regex("&(amp|lt|gt|quot|apos|nbsp|shy|copy|reg|ndash|mdash|hellip|trade);")]
+	Named,
+	#[error]
+	Error,
+}
+
+/// Whether `text` starts with a character reference recognized by [`RenderOptions::smart_ampersands`].
+pub(crate) fn starts_with_character_reference(text: &str) -> bool {
+	let mut lexer = CharacterReferenceToken::lexer(text);
+	matches!(
+		lexer.next(),
+		Some(CharacterReferenceToken::Numeric | CharacterReferenceToken::Named)
+	) && lexer.span().start == 0
+}
+
+pub(crate) fn write_text(
+	text: &str,
+	target: &mut impl Write,
+	escape_gt: bool,
+	smart_ampersands: bool,
+) -> fmt::Result {
+	let mut lexer = PlainTextToken::lexer(text);
+	while let Some(token) = lexer.next() {
+		match token {
+			PlainTextToken::Lt => target.write_str("&lt;"),
+			PlainTextToken::Ampersand
+				if smart_ampersands
+					&& starts_with_character_reference(&text[lexer.span().start..]) =>
+			{
+				target.write_char('&')
+			}
+			PlainTextToken::Ampersand => target.write_str("&amp;"),
+			PlainTextToken::Gt if escape_gt => target.write_str("&gt;"),
+			PlainTextToken::Gt => target.write_char('>'),
+			PlainTextToken::SafeVerbatim(str) => target.write_str(str),
+			// Any text matches one of the other tokens, but this fallback is cheap.
+			PlainTextToken::Error => {
+				for c in lexer.slice().chars() {
+					match c {
+						'<' => target.write_str("&lt;"),
+						'&' => target.write_str("&amp;"),
+						'>' if escape_gt => target.write_str("&gt;"),
+						c => target.write_char(c),
+					}?
+				}
+				Ok(())
+			}
+		}?
+	}
+	Ok(())
+}
+
+/// Writes `text` into `target`, escaped exactly like the text of a [`Node::Text`](`lignin::Node::Text`) in normal element content.
+///
+/// # Errors
+///
+/// Iff writing to `target` fails.
+pub fn write_escaped_text(text: &str, target: &mut impl Write) -> fmt::Result {
+	write_text(text, target, false, false)
+}
+
+/// Writes `value` into `target` escaped for use inside a quoted JavaScript string literal that is itself inside a quoted attribute value,
+/// like `VALUE` in `onclick="greet('VALUE')"`.
+///
+/// > **Warning:** This is a best-effort helper for a discouraged pattern!
+/// >
+/// > The output is only safe *inside* a `'`, `"` or `` ` ``-quoted string literal, in a `'` or `"`-quoted attribute value.
+/// > It does **not** make `value` safe to use as code, outside of a string literal, in an unquoted attribute value
+/// > or in `javascript:` URLs, and the script must not pass the resulting string to e.g. `eval` or `innerHTML` either.
+/// >
+/// > Prefer attaching event listeners from a script and passing data through `data-*` attributes,
+/// > which [`Node`](`lignin::Node`)s can represent without any special escaping.
+///
+/// Quotes, `\`, `` ` ``, `$`, `&`, `<`, `>`, control characters and line terminators are written as JavaScript escape sequences,
+/// so that the output contains nothing an HTML parser decodes or that ends the string literal or attribute value.
+///
+/// ```
+/// use lignin_html::write_escaped_js_attribute;
+///
+/// let mut onclick = String::new();
+/// write_escaped_js_attribute(r#"'); alert("pwned"); ('"#, &mut onclick).unwrap();
+/// assert_eq!(onclick, r"\x27); alert(\x22pwned\x22); (\x27");
+/// ```
+///
+/// # Errors
+///
+/// Iff writing to `target` fails.
+pub fn write_escaped_js_attribute(value: &str, target: &mut impl Write) -> fmt::Result {
+	for c in value.chars() {
+		match c {
+			'\n' => target.write_str("\\n"),
+			'\r' => target.write_str("\\r"),
+			'\t' => target.write_str("\\t"),
+			'\u{2028}' => target.write_str("\\u2028"),
+			'\u{2029}' => target.write_str("\\u2029"),
+			'"' | '\'' | '`' | '\\' | '$' | '&' | '<' | '>' | '\0'..='\u{1F}' | '\u{7F}' => {
+				write!(target, "\\x{:02X}", c as u32)
+			}
+			c => target.write_char(c),
+		}?
+	}
+	Ok(())
+}
+
+/// Escapes `text` like [`write_escaped_text`], but only allocates if anything had to be escaped.
+#[cfg(feature = "alloc")]
+#[allow(clippy::missing_panics_doc)] // Writing to a `String` can't fail.
+#[must_use]
+pub fn escape_text_cow(text: &str) -> Cow<'_, str> {
+	if text.contains(&['<', '&'][..]) {
+		let mut escaped = String::with_capacity(text.len() + 8);
+		write_escaped_text(text, &mut escaped).unwrap();
+		Cow::Owned(escaped)
+	} else {
+		Cow::Borrowed(text)
+	}
+}
@@ -0,0 +1,90 @@
+//! Cheap upper bounds for the length of rendered output.
+
+use crate::{is_transparent, Depth, RenderOptions};
+use lignin::{Element, Node, ThreadSafety};
+
+/// How many bytes each input byte becomes at most when escaped, as in `"` → `&quot;`.
+const ESCAPE_FACTOR: usize = 6;
+
+/// How many bytes each input byte of a comment becomes at most, as in NUL → U+FFFD.
+const COMMENT_FACTOR: usize = 3;
+
+/// Estimates how many bytes [`render_fragment`](`crate::render_fragment`) writes for `vdom`, e.g. to allocate a buffer up front.
+///
+/// The estimate is an upper bound for successful renders, but may be much larger than the actual output.
+/// It's calculated by walking `vdom` once, without checking whether it can be rendered.
+///
+/// ```
+/// use lignin_html::{estimate_output_len, lignin::Node, render_fragment};
+///
+/// let vdom = Node::Text { text: "a & b", dom_binding: None }.prefer_thread_safe();
+///
+/// let mut html = String::with_capacity(estimate_output_len(&vdom, 1));
+/// render_fragment(&vdom, &mut html, 1).unwrap();
+/// assert!(html.len() <= html.capacity());
+/// ```
+#[must_use]
+pub fn estimate_output_len<S: ThreadSafety>(vdom: &Node<S>, depth_limit: usize) -> usize {
+	estimate_node_len(vdom, Depth::top_level(depth_limit))
+}
+
+fn estimate_node_len<S: ThreadSafety>(vdom: &Node<S>, depth: Depth) -> usize {
+	let exceeded = if is_transparent(vdom) {
+		depth.transparent >= RenderOptions::DEFAULT_TRANSPARENT_DEPTH_LIMIT
+	} else {
+		depth.remaining == 0
+	};
+	if exceeded {
+		// Rendering fails here.
+		return 0;
+	}
+	match vdom {
+		Node::Comment { comment, .. } => comment
+			.len()
+			.saturating_mul(COMMENT_FACTOR)
+			// `<!--`, `-->` and up to two guard characters.
+			.saturating_add(4 + 3 + 2 * '|'.len_utf8()),
+		Node::HtmlElement { element, .. }
+		| Node::MathMlElement { element, .. }
+		| Node::SvgElement { element, .. } => estimate_element_len(element)
+			.saturating_add(estimate_node_len(&element.content, depth.element_content())),
+		Node::Memoized { content, .. } => estimate_node_len(content, depth.transparent_content()),
+		Node::Multi(nodes) => nodes.iter().fold(0, |len, node| {
+			len.saturating_add(estimate_node_len(node, depth.transparent_content()))
+		}),
+		Node::Keyed(reorderable_fragments) => {
+			reorderable_fragments.iter().fold(0, |len, fragment| {
+				len.saturating_add(estimate_node_len(
+					&fragment.content,
+					depth.transparent_content(),
+				))
+			})
+		}
+		Node::Text { text, .. } => text.len().saturating_mul(ESCAPE_FACTOR),
+		Node::RemnantSite(_) => 0,
+	}
+}
+
+/// The length of `element`'s tags at most, without its content.
+fn estimate_element_len<S: ThreadSafety>(element: &Element<S>) -> usize {
+	let attribute_len = |name: &str, value: &str| {
+		// ` name="value"`
+		value
+			.len()
+			.saturating_mul(ESCAPE_FACTOR)
+			.saturating_add(name.len() + 4)
+	};
+	let is_len = element
+		.creation_options
+		.is()
+		.map_or(0, |is| attribute_len("is", is));
+	element
+		.attributes
+		.iter()
+		.fold(is_len, |len, attribute| {
+			len.saturating_add(attribute_len(attribute.name, attribute.value))
+		})
+		// `<name`, `>`, a newline after `pre` or `textarea`, and `</name>`.
+		.saturating_add(element.name.len().saturating_mul(2))
+		.saturating_add(1 + 1 + 1 + 3)
+}
@@ -0,0 +1,228 @@
+//! Resumable rendering in bounded steps.
+
+use crate::{
+	check_keys, keyed_in_order, render_element_content, render_node, start_element, write_end_tag,
+	Context, Depth, ElementKind, ElementStart, Error, RenderOptions, Scope,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt::Write;
+use lignin::{Node, ThreadSafety};
+
+/// Renders a [`Node`] as HTML fragment in bounded steps, e.g. to yield to an async executor in between.
+///
+/// The output is the same as that of [`render_fragment_with_options`](`crate::render_fragment_with_options`),
+/// no matter how it's split up.
+///
+/// ```
+/// use lignin_html::{lignin::Node, FragmentRenderer, RenderOptions, StepResult};
+///
+/// let options = RenderOptions::new();
+/// let vdom = Node::Multi(&[
+///     Node::Text { text: "a", dom_binding: None },
+///     Node::Comment { comment: "b", dom_binding: None },
+/// ])
+/// .prefer_thread_safe();
+///
+/// let mut renderer = FragmentRenderer::new(&vdom, &options).with_nodes_per_step(1);
+/// let mut html = String::new();
+/// while renderer.step(&mut html).unwrap() == StepResult::Yielded {
+///     // Yield to the executor here.
+/// }
+/// assert_eq!(html, "a<!--b-->");
+/// ```
+pub struct FragmentRenderer<'a, S: ThreadSafety> {
+	context: Context<'a, S>,
+	stack: Vec<Frame<'a, S>>,
+	nodes_per_step: usize,
+}
+
+/// Pending work of a [`FragmentRenderer`].
+#[derive(Clone, Copy)]
+enum Frame<'a, S: ThreadSafety> {
+	Node {
+		vdom: &'a Node<'a, S>,
+		depth: Depth,
+		scope: Scope,
+	},
+	EndTag {
+		name: &'a str,
+		kind: ElementKind,
+	},
+}
+
+/// The outcome of a successful [`FragmentRenderer::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepResult {
+	/// Everything was rendered.
+	Done,
+	/// There's more to render. Call [`FragmentRenderer::step`] again to continue.
+	Yielded,
+}
+
+impl<'a, S: ThreadSafety> FragmentRenderer<'a, S> {
+	/// The default [`FragmentRenderer::nodes_per_step`].
+	pub const DEFAULT_NODES_PER_STEP: usize = 64;
+
+	/// Creates a new [`FragmentRenderer`] that renders `vdom` with `options`.
+	#[must_use]
+	pub fn new(vdom: &'a Node<'a, S>, options: &'a RenderOptions<'a>) -> Self {
+		Self {
+			context: Context::new(options),
+			stack: vec![Frame::Node {
+				vdom,
+				depth: Depth::top_level(options.depth_limit()),
+				scope: Scope::TOP_LEVEL,
+			}],
+			nodes_per_step: Self::DEFAULT_NODES_PER_STEP,
+		}
+	}
+
+	/// How many [`Node`]s are rendered at most per [`FragmentRenderer::step`], roughly.
+	///
+	/// [`Node`]s whose content isn't rendered as separate [`Node`]s, like raw text elements or comments,
+	/// are each rendered in one go along with their content.
+	///
+	/// Defaults to [`FragmentRenderer::DEFAULT_NODES_PER_STEP`] (64).
+	#[must_use]
+	pub const fn nodes_per_step(&self) -> usize {
+		self.nodes_per_step
+	}
+
+	/// Sets how many [`Node`]s are rendered at most per [`FragmentRenderer::step`]. At least one always is.
+	///
+	/// See [`FragmentRenderer::nodes_per_step`].
+	#[must_use]
+	pub fn with_nodes_per_step(self, nodes_per_step: usize) -> Self {
+		Self {
+			nodes_per_step,
+			..self
+		}
+	}
+
+	/// Renders the next few [`Node`]s into `target`.
+	///
+	/// Once this returns [`StepResult::Done`] or an error, further calls do nothing and return [`StepResult::Done`].
+	///
+	/// # Errors
+	///
+	/// Iff the [`Node`] is found to represent invalid HTML.
+	///
+	/// > **Warning:** Rendering succeeding does not guarantee that the produced HTML is fully valid!
+	pub fn step(&mut self, target: &mut impl Write) -> Result<StepResult, Error<'a, S>> {
+		for _ in 0..self.nodes_per_step.max(1) {
+			let frame = match self.stack.pop() {
+				Some(frame) => frame,
+				None => return Ok(StepResult::Done),
+			};
+			if let Err(error) = self.render_frame(frame, target) {
+				self.stack.clear();
+				return Err(error);
+			}
+		}
+		Ok(if self.stack.is_empty() {
+			StepResult::Done
+		} else {
+			StepResult::Yielded
+		})
+	}
+
+	/// Renders `frame`, pushing any [`Node`]s it contains onto the stack instead of rendering them.
+	fn render_frame(
+		&mut self,
+		frame: Frame<'a, S>,
+		target: &mut impl Write,
+	) -> Result<(), Error<'a, S>> {
+		let (vdom, depth, scope) = match frame {
+			Frame::Node { vdom, depth, scope } => (vdom, depth, scope),
+			Frame::EndTag { name, kind } => {
+				return write_end_tag(name, kind, &self.context, target)
+			}
+		};
+		let context = &self.context;
+		match vdom {
+			Node::HtmlElement { element, .. }
+			| Node::MathMlElement { element, .. }
+			| Node::SvgElement { element, .. } => {
+				context.enter(vdom, depth)?;
+				match start_element(vdom, element, target, context, scope)? {
+					ElementStart::Skipped => (),
+					ElementStart::ContentOnly => {
+						context.check_transparent_depth(vdom, depth)?;
+						self.stack.push(Frame::Node {
+							vdom: &element.content,
+							depth: depth.transparent_content(),
+							scope,
+						})
+					}
+					ElementStart::Tag {
+						name,
+						kind,
+						content_scope,
+					} => match kind {
+						ElementKind::Template
+						| ElementKind::Normal
+						| ElementKind::NormalPre
+						| ElementKind::ForeignNotSelfClosing => {
+							self.stack.push(Frame::EndTag { name, kind });
+							self.stack.push(Frame::Node {
+								vdom: &element.content,
+								depth: depth.element_content(),
+								scope: content_scope,
+							});
+						}
+						_ => {
+							render_element_content(
+								element,
+								name,
+								kind,
+								content_scope,
+								target,
+								depth,
+								context,
+							)?;
+							write_end_tag(name, kind, context, target)?
+						}
+					},
+				}
+			}
+
+			Node::Memoized { content, .. } => {
+				context.enter(vdom, depth)?;
+				self.stack.push(Frame::Node {
+					vdom: content,
+					depth: depth.transparent_content(),
+					scope,
+				})
+			}
+
+			Node::Multi(nodes) => {
+				context.enter(vdom, depth)?;
+				self.stack
+					.extend(nodes.iter().rev().map(|node| Frame::Node {
+						vdom: node,
+						depth: depth.transparent_content(),
+						scope,
+					}))
+			}
+
+			Node::Keyed(reorderable_fragments) => {
+				context.enter(vdom, depth)?;
+				check_keys(vdom, reorderable_fragments, context)?;
+				self.stack.extend(
+					keyed_in_order(reorderable_fragments, context.options)
+						.rev()
+						.map(|fragment| Frame::Node {
+							vdom: &fragment.content,
+							depth: depth.transparent_content(),
+							scope,
+						}),
+				)
+			}
+
+			Node::Comment { .. } | Node::Text { .. } | Node::RemnantSite(_) => {
+				render_node(vdom, target, depth, context, scope)?
+			}
+		}
+		Ok(())
+	}
+}
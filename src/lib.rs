@@ -13,17 +13,22 @@
 //!
 //! # Caveats
 //!
-//! In HTML comments, if illegal comment text is encountered, certain dashes (`-`) are **silently** replaced with equal signs (`=`) and pipe characters (`|`) are **silently** inserted around the comment text as necessary.
+//! In HTML comments, if illegal comment text is encountered, certain dashes (`-`) are **silently** replaced with equal signs (`=`) and pipe characters (`|`, see [`RenderOptions::comment_guard_char`]) are **silently** inserted around the comment text as necessary.
 //! See [***Comments***](https://html.spec.whatwg.org/multipage/syntax.html#comments).
 //!
 //! > Originally I was going to use [zero width non-joiner](https://graphemica.com/200C) and [zero width joiner](https://graphemica.com/200D) characters for this,
 //! > to make the comment resemble the original better, but this could be a very bad idea if any transport in-between strips Unicode.
+//!
+//! NUL characters in comments are replaced with `U+FFFD REPLACEMENT CHARACTER`, as an HTML parser would do anyway.
 
 #![doc(html_root_url = "https://docs.rs/lignin-html/0.0.5")]
 #![forbid(unsafe_code)]
 #![no_std]
 #![warn(clippy::pedantic, missing_docs)]
-#![allow(clippy::semicolon_if_nothing_returned)]
+#![allow(
+	clippy::semicolon_if_nothing_returned,
+	clippy::doc_comment_double_space_linebreaks, // Hard line breaks in documentation are written as two trailing spaces.
+)]
 
 //TODO: Much more thorough tests, ideally with coverage.
 
@@ -31,105 +36,2822 @@
 #[doc = include_str!("../README.md")]
 mod readme {}
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod base_href;
+#[cfg(feature = "alloc")]
+pub mod builder;
+#[cfg(feature = "std")]
+mod chunked;
+mod counting_writer;
+#[cfg(feature = "alloc")]
+mod describe;
+mod escaping;
+mod estimate;
+#[cfg(feature = "alloc")]
+mod fragment_renderer;
+#[cfg(feature = "alloc")]
+mod mapping;
+#[cfg(feature = "alloc")]
+mod memo_cache;
+mod namespaces;
+mod raw_text;
+mod render_html;
+#[cfg(feature = "alloc")]
+mod roundtrip;
+mod sink;
+mod svg_case;
+mod tee;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "alloc")]
+mod warnings;
+mod whitespace;
+
+#[cfg(feature = "std")]
+pub use chunked::ChunkedWriter;
+pub use counting_writer::CountingWriter;
+#[cfg(feature = "alloc")]
+pub use describe::{
+	describe_node, AttributeQuoting, ContentModel, ElementDescription, NodeDescription, NodeKind,
+};
+#[cfg(feature = "alloc")]
+pub use escaping::escape_text_cow;
+pub use escaping::{write_escaped_js_attribute, write_escaped_text};
+pub use estimate::estimate_output_len;
+#[cfg(feature = "alloc")]
+pub use fragment_renderer::{FragmentRenderer, StepResult};
+#[cfg(feature = "alloc")]
+pub use mapping::{render_fragment_with_mapping, NodePath};
+#[cfg(feature = "alloc")]
+pub use memo_cache::MemoCache;
+pub use render_html::RenderHtml;
+#[cfg(feature = "alloc")]
+pub use roundtrip::{render_fragment_roundtrip_safe, RoundtripManifest};
+#[cfg(feature = "std")]
+pub use sink::IoSink;
+pub use sink::{render_fragment_to_sink, HtmlSink, SinkError};
+pub use tee::Tee;
+#[cfg(feature = "alloc")]
+pub use warnings::{render_fragment_with_warnings, Warning};
+pub use whitespace::{whitespace_sensitivity, WhitespaceSensitivity};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
 use core::{
+	cell::{Cell, RefCell},
+	cmp::Ordering,
 	fmt::{self, Display, Write},
-	ops::Range,
+	iter, ptr,
 };
+use escaping::CharacterReferences;
 use fmt::Debug;
 pub use lignin;
-use lignin::{Attribute, Element, Node, ThreadSafety};
+use lignin::{
+	auto_safety::Align, Attribute, Element, Node, ReorderableFragment, ThreadBound, ThreadSafety,
+};
 use logos::{Lexer, Logos};
+use raw_text::RawTextTokenizer;
 
-//TODO: Benchmark and text-size-check using `core::fmt` macros vs. calling `Write` methods.
+//TODO: Benchmark and text-size-check using `core::fmt` macros vs. calling `Write` methods outside of tags, like `benches/tags.rs` does for those.
 
 /// Renders `vdom` into `target` as HTML document *with* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype).
 ///
-/// `depth_limit` is measured in [`Node`]s and must be at least `1` to not error on it.
+/// `depth_limit` is measured in element nesting levels and must be at least `1` to not error on it.
+/// See [`RenderOptions::depth_limit`].
+///
+/// # Caveats
+///
+/// See [`render_fragment`#caveats].
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_document<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<(), Error<'a, S>> {
+	render_document_with_options(
+		vdom,
+		target,
+		&RenderOptions::new().with_depth_limit(depth_limit),
+	)
+}
+
+/// Renders `vdom` into `target` as HTML document *with* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype),
+/// adjusted by `options`.
 ///
 /// # Caveats
 ///
 /// See [`render_fragment`#caveats].
 ///
-/// # Errors
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML or is nested deeper than [`RenderOptions::depth_limit`].
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_document_with_options<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	options: &RenderOptions,
+) -> Result<(), Error<'a, S>> {
+	if options.depth_limit == 0 {
+		return Err(Error(ErrorKind::DepthLimitExceeded {
+			node: ByAddress(vdom),
+			depth_limit: options.depth_limit,
+		}));
+	}
+	if options.require_html_root {
+		match vdom {
+			Node::HtmlElement { element, .. } if element.name.eq_ignore_ascii_case("html") => (),
+			_ => return Err(Error(ErrorKind::DocumentRootNotHtml(ByAddress(vdom)))),
+		}
+	}
+	if options.emit_bom {
+		target.write_char('\u{FEFF}')?;
+	}
+	target.write_str(options.doctype_style.doctype())?;
+	render_fragment_with_options(vdom, target, options)?;
+	if options.trailing_newline {
+		target.write_char('\n')?;
+	}
+	Ok(())
+}
+
+/// Renders `vdom` into `target` as standalone SVG document *with* [XML declaration](https://www.w3.org/TR/xml/#NT-XMLDecl).
+///
+/// `vdom` must be a [`Node::SvgElement`], which is given an `xmlns` attribute if it doesn't have one yet.
+///
+/// Unlike with the HTML renderers, the output follows XML rules:
+/// Attribute values are always quoted, empty elements are self-closing and `--` is removed from comments.
+///
+/// HTML content (e.g. inside `foreignObject`) is given an XHTML `xmlns` attribute where it starts.
+/// Its void elements are self-closing and all of its text is escaped, including that of `script` and `style` elements.
+///
+/// `depth_limit` is measured in element nesting levels and must be at least `1` to not error on it.
+/// See [`RenderOptions::depth_limit`].
+///
+/// # Errors
+///
+/// Iff `vdom` is not a [`Node::SvgElement`] or is found to represent invalid SVG.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced SVG is fully valid!
+pub fn render_svg_document<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<(), Error<'a, S>> {
+	render_svg_document_with_prolog(vdom, target, depth_limit, &[])
+}
+
+/// Renders `vdom` into `target` like [`render_svg_document`],
+/// but with the processing instructions in `prolog` between the XML declaration and the root element.
+///
+/// Each entry of `prolog` is a processing instruction's target and data, as for [`write_processing_instruction`].
+///
+/// ```
+/// use lignin_html::{lignin::{Element, ElementCreationOptions, Node}, render_svg_document_with_prolog};
+///
+/// let mut svg = String::new();
+/// render_svg_document_with_prolog(
+///     &Node::SvgElement {
+///         element: &Element {
+///             name: "svg",
+///             creation_options: ElementCreationOptions::new(),
+///             attributes: &[],
+///             content: Node::Multi(&[]),
+///             event_bindings: &[],
+///         },
+///         dom_binding: None,
+///     }
+///     .prefer_thread_safe(),
+///     &mut svg,
+///     1,
+///     &[("xml-stylesheet", r#"href="style.css""#)],
+/// ).unwrap();
+/// assert_eq!(
+///     svg,
+///     r#"<?xml version="1.0" encoding="UTF-8"?><?xml-stylesheet href="style.css"?><svg xmlns="http://www.w3.org/2000/svg"/>"#,
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Iff any processing instruction in `prolog` is invalid, in which case nothing is written,
+/// or `vdom` is not a [`Node::SvgElement`] or is found to represent invalid SVG.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced SVG is fully valid!
+pub fn render_svg_document_with_prolog<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+	prolog: &[(&'a str, &'a str)],
+) -> Result<(), Error<'a, S>> {
+	for &(target_name, data) in prolog {
+		validate_processing_instruction(target_name, data)?;
+	}
+	if depth_limit == 0 {
+		return Err(Error(ErrorKind::DepthLimitExceeded {
+			node: ByAddress(vdom),
+			depth_limit,
+		}));
+	}
+	if !matches!(vdom, Node::SvgElement { .. }) {
+		return Err(Error(ErrorKind::NonSvgDocumentRoot(ByAddress(vdom))));
+	}
+	target.write_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+	for &(target_name, data) in prolog {
+		write_processing_instruction(target, target_name, data)?;
+	}
+	render_node(
+		vdom,
+		target,
+		Depth::top_level(depth_limit),
+		&Context::new(&RenderOptions {
+			xml: true,
+			..RenderOptions::new()
+				.with_depth_limit(depth_limit)
+				.with_xmlns(true)
+		}),
+		Scope::TOP_LEVEL,
+	)
+}
+
+/// Writes an XML [processing instruction](https://www.w3.org/TR/xml/#sec-pi) like `<?xml-stylesheet href="style.css"?>` into `target`.
+///
+/// > **Warning:** Processing instructions only exist in XML, e.g. in documents rendered with [`render_svg_document`].
+/// > HTML parsers treat them as bogus comments.
+///
+/// `data` is written as-is after a space, unless it's empty.
+/// Use [`render_svg_document_with_prolog`] for processing instructions before the root element.
+///
+/// # Errors
+///
+/// Iff `target_name` is not a valid [***PITarget***](https://www.w3.org/TR/xml/#NT-PITarget) without colons,
+/// which excludes `xml` in any case, or `data` contains `?>` or characters not allowed in XML,
+/// or writing to `target` fails.
+pub fn write_processing_instruction<'a, S: ThreadSafety>(
+	target: &mut impl Write,
+	target_name: &'a str,
+	data: &'a str,
+) -> Result<(), Error<'a, S>> {
+	validate_processing_instruction(target_name, data)?;
+	target.write_str("<?")?;
+	target.write_str(target_name)?;
+	if !data.is_empty() {
+		target.write_char(' ')?;
+		target.write_str(data)?;
+	}
+	target.write_str("?>")?;
+	Ok(())
+}
+
+/// See [`write_processing_instruction`].
+fn validate_processing_instruction<'a, S: ThreadSafety>(
+	target_name: &'a str,
+	data: &'a str,
+) -> Result<(), Error<'a, S>> {
+	// See <https://www.w3.org/TR/xml/#NT-Name>, without `:` as per <https://www.w3.org/TR/xml-names/#Conformance>.
+	let is_name_start_char = |c| {
+		matches!(c,
+			'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}'
+			| '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}'
+			| '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+			| '\u{10000}'..='\u{EFFFF}')
+	};
+	let is_name_char = |c| {
+		is_name_start_char(c)
+			|| matches!(c, '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+	};
+	let mut chars = target_name.chars();
+	if !matches!(chars.next(), Some(c) if is_name_start_char(c))
+		|| !chars.all(is_name_char)
+		|| target_name.eq_ignore_ascii_case("xml")
+	{
+		return Err(Error(ErrorKind::InvalidProcessingInstructionTarget(
+			target_name,
+		)));
+	}
+
+	// See <https://www.w3.org/TR/xml/#NT-Char>.
+	if data.contains("?>")
+		|| data.chars().any(|c| {
+			matches!(c, '\0'..='\u{8}' | '\u{B}' | '\u{C}' | '\u{E}'..='\u{1F}' | '\u{FFFE}' | '\u{FFFF}')
+		}) {
+		return Err(Error(ErrorKind::InvalidProcessingInstructionData(data)));
+	}
+	Ok(())
+}
+
+/// Renders `attributes` into `target` as they would appear in an HTML element's start tag, i.e. as ` name=value` pairs.
+///
+/// Names are validated and values quoted and escaped exactly like [`render_fragment`] does for [`Node::HtmlElement`]s.
+///
+/// # Errors
+///
+/// Iff an attribute name is invalid or reserved (`is`, which is set through [`Element::creation_options`]),
+/// or writing to `target` fails.
+///
+/// Attributes before the invalid one may already have been written.
+pub fn render_attributes<'a, S: ThreadSafety>(
+	attributes: &'a [Attribute<'a>],
+	target: &mut impl Write,
+) -> Result<(), Error<'a, S>> {
+	for &Attribute { name, value } in attributes {
+		write_attribute(
+			target,
+			" ",
+			validate_attribute_name(name, false)?,
+			false,
+			value,
+			&RenderOptions::new(),
+		)?;
+	}
+	Ok(())
+}
+
+/// Renders an attribute whose value is a space-separated list of `tokens` (like `class` or `rel`) into `target`,
+/// as ` name="token1 token2 …"`.
+///
+/// The name is validated and the tokens are escaped exactly like [`render_attributes`] does, but the value is always double-quoted.  
+/// Empty tokens are skipped, and if `deduplicate` is `true`, so are tokens that are equal to an earlier one.
+/// If no tokens remain, nothing is written, which omits the attribute.
+///
+/// Tokens are written as-is otherwise, so whitespace in them separates them into several tokens.
+///
+/// ```
+/// use lignin_html::{lignin::ThreadSafe, write_token_list_attribute};
+///
+/// let mut html = String::new();
+/// write_token_list_attribute::<ThreadSafe, _>(
+///     "class",
+///     ["btn", "btn-primary", "btn"].iter().copied(),
+///     true,
+///     &mut html,
+/// ).unwrap();
+/// assert_eq!(html, r#" class="btn btn-primary""#);
+/// ```
+///
+/// # Errors
+///
+/// Iff `name` is invalid or reserved (`is`, which is set through [`Element::creation_options`]),
+/// or writing to `target` fails.
+pub fn write_token_list_attribute<'a, S: ThreadSafety, I>(
+	name: &'a str,
+	tokens: I,
+	deduplicate: bool,
+	target: &mut impl Write,
+) -> Result<(), Error<'a, S>>
+where
+	I: IntoIterator<Item = &'a str>,
+	I::IntoIter: Clone,
+{
+	let name = validate_attribute_name(name, false)?;
+	let tokens = tokens.into_iter();
+	let options = RenderOptions::new();
+	let mut first = true;
+	for (i, token) in tokens.clone().enumerate() {
+		if token.is_empty() || deduplicate && tokens.clone().take(i).any(|earlier| earlier == token)
+		{
+			continue;
+		}
+		if first {
+			target.write_char(' ')?;
+			target.write_str(name)?;
+			target.write_str("=\"")?;
+			first = false;
+		} else {
+			target.write_char(' ')?;
+		}
+		write_attribute_value(target, token, AttributeValueMode::DoubleQuoted, &options)?;
+	}
+	if !first {
+		target.write_char('"')?;
+	}
+	Ok(())
+}
+
+/// Renders a `<script type="application/json">` element containing `json` into `target`,
+/// with an `id` attribute if one is given.
+///
+/// `json` is not validated, but `<`, `>` and `&` in it are replaced with the JSON escape sequences `\u003C`, `\u003E` and `\u0026`.
+/// This is lossless inside JSON strings and neutralises any markup that would otherwise end the element early.
+///
+/// > **Warning:** This is not sufficient if `json` is not valid JSON, as `<`, `>` and `&` may only be escaped like this inside JSON strings!
+///
+/// # Errors
+///
+/// Iff `id` is not a valid [***ID***](https://html.spec.whatwg.org/multipage/dom.html#the-id-attribute),
+/// i.e. empty or containing [***ASCII whitespace***](https://infra.spec.whatwg.org/#ascii-whitespace),
+/// or writing to `target` fails.
+pub fn render_json_script<'a, S: ThreadSafety>(
+	id: Option<&'a str>,
+	json: &str,
+	target: &mut impl Write,
+) -> Result<(), Error<'a, S>> {
+	if let Some(id) = id {
+		if id.is_empty() || id.contains(&['\t', '\n', '\u{C}', '\r', ' '][..]) {
+			return Err(Error(ErrorKind::InvalidId(id)));
+		}
+	}
+
+	target.write_str("<script")?;
+	write_attribute(
+		target,
+		" ",
+		"type",
+		false,
+		"application/json",
+		&RenderOptions::new(),
+	)?;
+	if let Some(id) = id {
+		write_attribute(target, " ", "id", false, id, &RenderOptions::new())?;
+	}
+	target.write_char('>')?;
+	for c in json.chars() {
+		match c {
+			'<' => target.write_str("\\u003C"),
+			'>' => target.write_str("\\u003E"),
+			'&' => target.write_str("\\u0026"),
+			c => target.write_char(c),
+		}?
+	}
+	target.write_str("</script>")?;
+	Ok(())
+}
+
+/// Renders `vdom` into `target` as HTML fragment *without* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype).
+///
+/// `depth_limit` is measured in element nesting levels and must be at least `1` to not error on it.
+/// See [`RenderOptions::depth_limit`].
+///
+/// # Caveats
+///
+/// Output is written to `target` as rendering progresses, so on error, `target` is left with whatever was rendered up to that point.
+/// This includes errors from `target` itself, e.g. when a fixed-capacity string like `heapless::String` runs out of space,
+/// which are returned (as [`fmt::Error`]) rather than causing a panic.
+///
+/// If partial output is a problem, truncate `target` back to its previous length on error,
+/// or measure the output with a [`CountingWriter`] around a discarding [`Write`] first.
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<(), Error<'a, S>> {
+	render_fragment_with_options(
+		vdom,
+		target,
+		&RenderOptions::new().with_depth_limit(depth_limit),
+	)
+}
+
+/// Like [`render_fragment`], but for use in [`Display`] implementations, which can only return [`fmt::Error`].
+///
+/// Iff rendering fails, the actual [`Error`] is stored in `error` (replacing any previous value) and [`fmt::Error`] is returned.
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML or writing to `f` fails.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+///
+/// # Example
+///
+/// ```
+/// use core::{cell::RefCell, fmt::{self, Write as _}};
+/// use lignin::{Node, ThreadSafe};
+/// use lignin_html::{render_fragment_into_formatter, Error};
+///
+/// struct Html<'a> {
+///     vdom: &'a Node<'a, ThreadSafe>,
+///     depth_limit: usize,
+///     error: RefCell<Option<Error<'a, ThreadSafe>>>,
+/// }
+///
+/// impl fmt::Display for Html<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         render_fragment_into_formatter(self.vdom, f, self.depth_limit, &mut self.error.borrow_mut())
+///     }
+/// }
+///
+/// let vdom = Node::Text { text: "<3", dom_binding: None };
+///
+/// let html = Html { vdom: &vdom, depth_limit: 1, error: RefCell::new(None) };
+/// let mut rendered = String::new();
+/// write!(rendered, "{}", html).unwrap();
+/// assert_eq!(rendered, "&lt;3");
+///
+/// let html = Html { vdom: &vdom, depth_limit: 0, error: RefCell::new(None) };
+/// assert!(write!(String::new(), "{}", html).is_err());
+/// assert_eq!(
+///     html.error.into_inner().unwrap().to_string(),
+///     "Depth limit of 0 exceeded at a text node",
+/// );
+/// ```
+pub fn render_fragment_into_formatter<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	f: &mut fmt::Formatter<'_>,
+	depth_limit: usize,
+	error: &mut Option<Error<'a, S>>,
+) -> fmt::Result {
+	render_fragment(vdom, f, depth_limit).map_err(|render_error| {
+		*error = Some(render_error);
+		fmt::Error
+	})
+}
+
+/// Renders `vdom` into a new [`String`](`alloc::string::String`) as HTML fragment *without* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype).
+///
+/// `depth_limit` is measured in element nesting levels and must be at least `1` to not error on it.
+/// See [`RenderOptions::depth_limit`].
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+#[cfg(feature = "alloc")]
+pub fn render_fragment_to_string<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	depth_limit: usize,
+) -> Result<alloc::string::String, Error<'a, S>> {
+	let mut string = alloc::string::String::new();
+	render_fragment(vdom, &mut string, depth_limit)?;
+	Ok(string)
+}
+
+/// Renders `vdom` into a new [`Vec<u8>`](`alloc::vec::Vec`) as HTML fragment *without* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype).
+///
+/// The returned bytes are always valid UTF-8, since they are written through [`Write`].
+///
+/// `depth_limit` is measured in element nesting levels and must be at least `1` to not error on it.
+/// See [`RenderOptions::depth_limit`].
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+#[cfg(feature = "alloc")]
+pub fn render_fragment_to_vec<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	depth_limit: usize,
+) -> Result<alloc::vec::Vec<u8>, Error<'a, S>> {
+	/// Appends UTF-8 to a [`Vec<u8>`](`alloc::vec::Vec`).
+	struct VecWrite(alloc::vec::Vec<u8>);
+	impl Write for VecWrite {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			self.0.extend_from_slice(s.as_bytes());
+			Ok(())
+		}
+	}
+
+	let mut vec = VecWrite(alloc::vec::Vec::new());
+	render_fragment(vdom, &mut vec, depth_limit)?;
+	Ok(vec.0)
+}
+
+/// Renders `old` and `new` with [`render_fragment_to_string`] and finds the first byte offset at which the results differ.
+///
+/// The offset is [`None`] iff both renderings are identical.
+/// If one rendering is a prefix of the other, it's the length of the shorter one.
+///
+/// # Errors
+///
+/// Iff either `old` or `new` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+#[cfg(feature = "alloc")]
+#[allow(clippy::type_complexity)]
+pub fn render_pair<'a, S: ThreadSafety>(
+	old: &'a Node<'a, S>,
+	new: &'a Node<'a, S>,
+	depth_limit: usize,
+) -> Result<(alloc::string::String, alloc::string::String, Option<usize>), Error<'a, S>> {
+	let old = render_fragment_to_string(old, depth_limit)?;
+	let new = render_fragment_to_string(new, depth_limit)?;
+	let difference = old
+		.bytes()
+		.zip(new.bytes())
+		.position(|(old, new)| old != new)
+		.or_else(|| (old.len() != new.len()).then(|| old.len().min(new.len())));
+	Ok((old, new, difference))
+}
+
+/// Renders only the content of the first fragment in `keyed` whose [`ReorderableFragment::dom_key`] is `dom_key`
+/// into `target`, like [`render_fragment`] would, e.g. to send an updated part of a [`Node::Keyed`] to a client.
+///
+/// Returns whether such a fragment was found. Nothing is written otherwise.
+///
+/// Since [`Node::Keyed`] doesn't count towards it, `depth_limit` applies to the content as if the whole [`Node::Keyed`] was rendered.
+///
+/// ```
+/// use lignin_html::{lignin::{Node, ReorderableFragment, ThreadSafe}, render_keyed_by_key};
+///
+/// let keyed: &[ReorderableFragment<ThreadSafe>] = &[
+///     ReorderableFragment { dom_key: 1, content: Node::Text { text: "a", dom_binding: None } },
+///     ReorderableFragment { dom_key: 2, content: Node::Text { text: "b", dom_binding: None } },
+/// ];
+///
+/// let mut html = String::new();
+/// assert!(render_keyed_by_key(keyed, 2, &mut html, 1).unwrap());
+/// assert_eq!(html, "b");
+/// ```
+///
+/// # Errors
+///
+/// Iff the found fragment's content is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_keyed_by_key<'a, S: ThreadSafety>(
+	keyed: &'a [ReorderableFragment<'a, S>],
+	dom_key: u32,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<bool, Error<'a, S>> {
+	match keyed.iter().find(|fragment| fragment.dom_key == dom_key) {
+		Some(fragment) => {
+			render_fragment(&fragment.content, target, depth_limit)?;
+			Ok(true)
+		}
+		None => Ok(false),
+	}
+}
+
+/// Renders `vdom` into `target` as HTML fragment *without* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype),
+/// adjusted by `options`.
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML or is nested deeper than [`RenderOptions::depth_limit`].
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_with_options<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	options: &RenderOptions,
+) -> Result<(), Error<'a, S>> {
+	render_node(
+		vdom,
+		target,
+		Depth::top_level(options.depth_limit),
+		&Context::new(options),
+		Scope::TOP_LEVEL,
+	)
+}
+
+/// Renders `vdom` into `target` as content of an HTML element named `parent`.
+///
+/// This is useful for partials that are later inserted into a known element:
+/// For example, text rendered in the context of `style` is checked and written as raw text,
+/// while in the context of `title` only text is allowed and it's escaped as escapable raw text.  
+/// Only the content is rendered, not `parent`'s tags.
+///
+/// # Errors
+///
+/// Iff `parent` is not a valid element name, or `vdom` is found to represent invalid HTML in its context or is nested deeper than `depth_limit`.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_in_context<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+	parent: &'a str,
+) -> Result<(), Error<'a, S>> {
+	let kind = ElementKind::detect(parent)
+		.map_err(|reason| Error(ErrorKind::InvalidElementName(parent, reason)))?;
+	let options = RenderOptions::new().with_depth_limit(depth_limit);
+	let context = Context::new(&options);
+	match kind {
+		ElementKind::Void => {
+			if !renders_empty(vdom) {
+				return Err(Error(ErrorKind::NonEmptyVoidElementContent(ByAddress(
+					vdom,
+				))));
+			}
+			Ok(())
+		}
+		ElementKind::Template
+		| ElementKind::Normal
+		| ElementKind::NormalPre
+		| ElementKind::ForeignSelfClosing
+		| ElementKind::ForeignNotSelfClosing => render_node(
+			vdom,
+			target,
+			Depth::top_level(depth_limit),
+			&context,
+			Scope::TOP_LEVEL.enter(Namespace::Html, parent, &[]),
+		),
+		ElementKind::RawText => render_raw_text(
+			vdom,
+			target,
+			parent,
+			Depth::top_level(depth_limit),
+			&context,
+		),
+		ElementKind::EscapableRawText | ElementKind::EscapableRawTextTextarea => {
+			render_escapable_raw_text(
+				vdom,
+				target,
+				parent,
+				Depth::top_level(depth_limit),
+				&context,
+			)
+		}
+		// Never returned by `ElementKind::detect`.
+		ElementKind::PotentialCustomElementNameCharacter
+		| ElementKind::Dash
+		| ElementKind::Invalid => Err(Error(ErrorKind::Internal)),
+	}
+}
+
+/// Writes the text content of `vdom` into `target` as plain text, e.g. for previews or search indexing.
+///
+/// Only [`Node::Text`]s contribute, including those inside raw text elements like `script`.
+/// Their text is written as-is, without escaping or whitespace collapsing.  
+/// Elements, attributes and [`Node::Comment`]s are skipped, and no markup is written at all.
+///
+/// See [`render_text_only_with_options`] to collapse whitespace or separate blocks.
+///
+/// # Errors
+///
+/// Iff `vdom` contains unsupported [`Node`]s or is nested deeper than `depth_limit`.
+pub fn render_text_only<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<(), Error<'a, S>> {
+	render_text_only_with_options(vdom, target, depth_limit, TextOnlyOptions::new())
+}
+
+/// Like [`render_text_only`], but can collapse whitespace and separate block elements as configured by `options`.
+///
+/// Collapsed whitespace and block boundaries are dropped at the start and end of the output, so that it can be used as is.
+///
+/// # Errors
+///
+/// Iff `vdom` contains unsupported [`Node`]s or is nested deeper than `depth_limit`.
+pub fn render_text_only_with_options<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+	options: TextOnlyOptions,
+) -> Result<(), Error<'a, S>> {
+	write_text_only(
+		vdom,
+		target,
+		Depth::top_level(depth_limit),
+		&Context::new(&RenderOptions::new().with_depth_limit(depth_limit)),
+		options,
+		false,
+		&mut TextOnlyState {
+			at_start: true,
+			separator: TextSeparator::None,
+		},
+	)
+}
+
+/// What to write before the next text, if any. Stronger separators replace weaker ones.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TextSeparator {
+	None,
+	Space,
+	Newline,
+}
+
+/// Whether anything was written by [`write_text_only`] yet, and what to separate the next text with.
+struct TextOnlyState {
+	at_start: bool,
+	separator: TextSeparator,
+}
+
+impl TextOnlyState {
+	fn separate(&mut self, separator: TextSeparator) {
+		self.separator = self.separator.max(separator);
+	}
+
+	fn write(&mut self, target: &mut impl Write, text: &str) -> fmt::Result {
+		if text.is_empty() {
+			return Ok(());
+		}
+		if !self.at_start {
+			match self.separator {
+				TextSeparator::None => (),
+				TextSeparator::Space => target.write_char(' ')?,
+				TextSeparator::Newline => target.write_char('\n')?,
+			}
+		}
+		self.at_start = false;
+		self.separator = TextSeparator::None;
+		target.write_str(text)
+	}
+}
+
+/// The recursive part of [`render_text_only_with_options`].
+///
+/// `pre` is whether `vdom` is inside an element classified as [`WhitespaceSensitivity::Pre`].
+fn write_text_only<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth: Depth,
+	context: &Context<S>,
+	options: TextOnlyOptions,
+	pre: bool,
+	state: &mut TextOnlyState,
+) -> Result<(), Error<'a, S>> {
+	context.enter(vdom, depth)?;
+	match vdom {
+		Node::Comment { .. } => (),
+		Node::HtmlElement { element, .. } => {
+			let sensitivity = whitespace_sensitivity(element.name);
+			let block = options.block_boundaries && sensitivity == WhitespaceSensitivity::Block;
+			if block {
+				state.separate(TextSeparator::Newline);
+			}
+			write_text_only(
+				&element.content,
+				target,
+				depth.element_content(),
+				context,
+				options,
+				pre || sensitivity == WhitespaceSensitivity::Pre,
+				state,
+			)?;
+			if block {
+				state.separate(TextSeparator::Newline);
+			}
+		}
+		Node::MathMlElement { element, .. } | Node::SvgElement { element, .. } => write_text_only(
+			&element.content,
+			target,
+			depth.element_content(),
+			context,
+			options,
+			pre,
+			state,
+		)?,
+		Node::Memoized { content, .. } => write_text_only(
+			content,
+			target,
+			depth.transparent_content(),
+			context,
+			options,
+			pre,
+			state,
+		)?,
+		Node::Multi(nodes) => {
+			for node in *nodes {
+				write_text_only(
+					node,
+					target,
+					depth.transparent_content(),
+					context,
+					options,
+					pre,
+					state,
+				)?
+			}
+		}
+		Node::Keyed(pairs) => {
+			for pair in *pairs {
+				write_text_only(
+					&pair.content,
+					target,
+					depth.transparent_content(),
+					context,
+					options,
+					pre,
+					state,
+				)?
+			}
+		}
+		Node::Text { text, .. } if pre || !options.collapse_whitespace => {
+			state.write(target, text)?
+		}
+		Node::Text { text, .. } => {
+			let mut rest = *text;
+			while !rest.is_empty() {
+				let whitespace_len = rest
+					.find(|c: char| !c.is_ascii_whitespace())
+					.unwrap_or(rest.len());
+				if whitespace_len > 0 {
+					state.separate(TextSeparator::Space);
+					rest = &rest[whitespace_len..];
+					continue;
+				}
+				let word_len = rest
+					.find(|c: char| c.is_ascii_whitespace())
+					.unwrap_or(rest.len());
+				state.write(target, &rest[..word_len])?;
+				rest = &rest[word_len..];
+			}
+		}
+		Node::RemnantSite(_) => return Err(Error(ErrorKind::UnsupportedNode(ByAddress(vdom)))),
+	}
+	Ok(())
+}
+
+/// Like [`render_fragment`], but also collects [`RenderStats`] about the rendered tree and output.
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_with_stats<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<RenderStats, Error<'a, S>> {
+	let stats = Cell::new(RenderStats::default());
+	let mut target = CountingWriter::new(target);
+	render_node(
+		vdom,
+		&mut target,
+		Depth::top_level(depth_limit),
+		&Context {
+			stats: Some(&stats),
+			..Context::new(&RenderOptions::new().with_depth_limit(depth_limit))
+		},
+		Scope::TOP_LEVEL,
+	)?;
+	Ok(RenderStats {
+		bytes_written: target.count(),
+		..stats.get()
+	})
+}
+
+/// Like [`render_fragment`], but reuses and fills `cache` when rendering [`Node::Memoized`]s.
+///
+/// See [`MemoCache`#invalidation] for when cached content is reused.
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+#[cfg(feature = "alloc")]
+pub fn render_fragment_cached<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+	cache: &mut MemoCache,
+) -> Result<(), Error<'a, S>> {
+	let cache = RefCell::new(cache);
+	render_node(
+		vdom,
+		target,
+		Depth::top_level(depth_limit),
+		&Context {
+			memo_cache: Some(&cache),
+			..Context::new(&RenderOptions::new().with_depth_limit(depth_limit))
+		},
+		Scope::TOP_LEVEL,
+	)
+}
+
+/// Like [`render_fragment`], but calls `visitor` with each [`Node`] before it's rendered.
+///
+/// Rendering stops with the first error returned by `visitor`, e.g. one created with [`Error::rejected`].
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML or `visitor` returns an error.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+///
+/// # Example
+///
+/// ```rust
+/// use lignin::{Element, ElementCreationOptions, Node};
+/// use lignin_html::{render_fragment_with_visitor, Error};
+///
+/// let mut elements = 0;
+/// let mut html = String::new();
+/// render_fragment_with_visitor(
+///     &Node::HtmlElement {
+///         element: &Element {
+///             name: "p",
+///             creation_options: ElementCreationOptions::new(),
+///             attributes: &[],
+///             content: Node::Text {
+///                 text: "Hello!",
+///                 dom_binding: None,
+///             },
+///             event_bindings: &[],
+///         },
+///         dom_binding: None,
+///     }
+///     .prefer_thread_safe(),
+///     &mut html,
+///     2,
+///     &mut |node| match node {
+///         Node::HtmlElement { element, .. } if element.name == "iframe" => {
+///             Err(Error::rejected(node))
+///         }
+///         Node::HtmlElement { .. } => {
+///             elements += 1;
+///             Ok(())
+///         }
+///         _ => Ok(()),
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!(elements, 1);
+/// ```
+pub fn render_fragment_with_visitor<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+	visitor: &mut impl for<'n> FnMut(&'n Node<'n, S>) -> Result<(), Error<'n, S>>,
+) -> Result<(), Error<'a, S>> {
+	let visitor: RefCell<&mut Visitor<S>> = RefCell::new(visitor);
+	render_node(
+		vdom,
+		target,
+		Depth::top_level(depth_limit),
+		&Context {
+			visitor: Some(&visitor),
+			..Context::new(&RenderOptions::new().with_depth_limit(depth_limit))
+		},
+		Scope::TOP_LEVEL,
+	)
+}
+
+/// Statistics about a successful render, as returned by [`render_fragment_with_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RenderStats {
+	/// The greatest [`Node`] depth reached, counting the root as `1`, as measured for [`RenderOptions::depth_limit`].
+	///
+	/// This is the smallest `depth_limit` the same `vdom` can be rendered with.
+	pub max_depth: usize,
+	/// How many [`Node`]s were rendered in total.
+	pub node_count: usize,
+	/// How many [`Node::HtmlElement`]s were rendered.
+	pub html_elements: usize,
+	/// How many [`Node::MathMlElement`]s were rendered.
+	pub math_ml_elements: usize,
+	/// How many [`Node::SvgElement`]s were rendered.
+	pub svg_elements: usize,
+	/// How many [`Node::Comment`]s were rendered.
+	pub comments: usize,
+	/// How many [`Node::Text`]s were rendered.
+	pub text_nodes: usize,
+	/// How many [`Node::Memoized`]s were rendered.
+	pub memoized: usize,
+	/// How many [`Node::Multi`]s were rendered.
+	pub multi: usize,
+	/// How many [`Node::Keyed`]s were rendered.
+	pub keyed: usize,
+	/// How many bytes (not characters) were written to `target`.
+	pub bytes_written: usize,
+}
+
+/// Options for [`render_fragment_with_options`].
+///
+/// Apart from [`RenderOptions::depth_limit`], the defaults match [`render_fragment`]'s behaviour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // These are independent switches.
+pub struct RenderOptions<'a> {
+	depth_limit: usize,
+	transparent_depth_limit: usize,
+	xmlns: bool,
+	strict_namespaces: bool,
+	emit_bom: bool,
+	trailing_newline: bool,
+	doctype_style: DoctypeStyle,
+	pretty: Option<PrettyOptions>,
+	comment_policy: CommentPolicy,
+	comment_guard_char: char,
+	#[cfg(feature = "alloc")]
+	strict_keys: bool,
+	#[cfg(feature = "alloc")]
+	sort_keyed: bool,
+	#[cfg(feature = "alloc")]
+	repeat_limit: Option<usize>,
+	extra_void_elements: &'a [&'a str],
+	extra_raw_text_elements: &'a [&'a str],
+	require_html_lang: bool,
+	require_html_root: bool,
+	require_img_alt: bool,
+	strict_document_structure: bool,
+	image_perf_defaults: bool,
+	harden_blank_targets: bool,
+	csp_nonce: Option<&'a str>,
+	#[cfg(feature = "alloc")]
+	base_href: Option<&'a str>,
+	ascii_only: bool,
+	reject_nul: bool,
+	named_entities: bool,
+	smart_ampersands: bool,
+	browser_canonical: bool,
+	strict_data_attributes: bool,
+	precise_escapable_raw_text: bool,
+	normalize_svg_case: bool,
+	roundtrip_safe: bool,
+	redact_attributes: &'a [&'a str],
+	quote_empty_attributes: &'a [&'a str],
+	/// [`None`] means identity.
+	map_element_name: Option<ElementNameMapper>,
+	/// [`None`] means always `true`.
+	should_render: Option<ElementFilter>,
+	fragment_element_name: Option<&'a str>,
+	/// [`None`] means identity.
+	#[cfg(feature = "alloc")]
+	map_attribute: Option<AttributeMapper>,
+	/// Whether to serialize as XML rather than HTML. Only used by [`render_svg_document`].
+	xml: bool,
+}
+
+impl Default for RenderOptions<'_> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<'a> RenderOptions<'a> {
+	/// The default [`RenderOptions::depth_limit`].
+	///
+	/// This is generous for handwritten HTML, but may have to be raised for deeply nested generated content.
+	pub const DEFAULT_DEPTH_LIMIT: usize = 256;
+
+	/// The default [`RenderOptions::transparent_depth_limit`].
+	pub const DEFAULT_TRANSPARENT_DEPTH_LIMIT: usize = 256;
+
+	/// What the values of [`RenderOptions::redact_attributes`] are replaced with.
+	pub const REDACTED: &'static str = "[redacted]";
+
+	/// The default [`RenderOptions::fragment_element_name`].
+	pub const DEFAULT_FRAGMENT_ELEMENT_NAME: &'static str = "lignin:fragment";
+
+	/// Creates a new [`RenderOptions`] instance with default settings.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			depth_limit: Self::DEFAULT_DEPTH_LIMIT,
+			transparent_depth_limit: Self::DEFAULT_TRANSPARENT_DEPTH_LIMIT,
+			xmlns: false,
+			strict_namespaces: false,
+			emit_bom: false,
+			trailing_newline: false,
+			doctype_style: DoctypeStyle::Uppercase,
+			pretty: None,
+			comment_policy: CommentPolicy::Legalize,
+			comment_guard_char: '|',
+			#[cfg(feature = "alloc")]
+			strict_keys: false,
+			#[cfg(feature = "alloc")]
+			sort_keyed: false,
+			#[cfg(feature = "alloc")]
+			repeat_limit: None,
+			extra_void_elements: &[],
+			extra_raw_text_elements: &[],
+			require_html_lang: false,
+			require_html_root: false,
+			require_img_alt: false,
+			strict_document_structure: false,
+			image_perf_defaults: false,
+			harden_blank_targets: false,
+			csp_nonce: None,
+			#[cfg(feature = "alloc")]
+			base_href: None,
+			ascii_only: false,
+			reject_nul: false,
+			named_entities: false,
+			smart_ampersands: false,
+			browser_canonical: false,
+			strict_data_attributes: false,
+			precise_escapable_raw_text: false,
+			normalize_svg_case: false,
+			roundtrip_safe: false,
+			redact_attributes: &[],
+			quote_empty_attributes: &[],
+			map_element_name: None,
+			should_render: None,
+			fragment_element_name: Some(Self::DEFAULT_FRAGMENT_ELEMENT_NAME),
+			#[cfg(feature = "alloc")]
+			map_attribute: None,
+			xml: false,
+		}
+	}
+
+	/// How deeply the rendered [`Node`]s may be nested, measured in element nesting levels.
+	///
+	/// Each [`Node::Text`], [`Node::Comment`] and element is at depth `1` plus the number of elements around it,
+	/// and rendering errors at the first one that is deeper than this limit.
+	///
+	/// [`Node::Multi`], [`Node::Keyed`] and [`Node::Memoized`] are transparent:
+	/// They don't have a depth of their own and their content is at the same depth as they are.
+	/// Elements rendered as content only (see [`RenderOptions::fragment_element_name`] and [`RenderOptions::map_element_name`])
+	/// count normally, but don't nest their content any deeper.
+	///
+	/// For example, an empty `<p></p>` (with an empty [`Node::Multi`] as content) can be rendered with a depth limit of `1`,
+	/// but `<p>text</p>` needs at least `2`.
+	///
+	/// This must be at least `1` to render any text, comments or elements.
+	///
+	/// [`usize::MAX`] effectively disables the limit, but note that rendering is recursive and may then overflow the stack.  
+	/// (This never causes arithmetic overflow, as the remaining depth is only ever decremented after checking it's not `0`.)
+	///
+	/// Transparent [`Node`]s and elements rendered as content only are limited separately by [`RenderOptions::transparent_depth_limit`].
+	#[must_use]
+	pub const fn depth_limit(&self) -> usize {
+		self.depth_limit
+	}
+
+	/// Sets how deeply the rendered [`Node`]s may be nested.
+	///
+	/// See [`RenderOptions::depth_limit`].
+	#[must_use]
+	pub const fn with_depth_limit(self, depth_limit: usize) -> Self {
+		Self {
+			depth_limit,
+			..self
+		}
+	}
+
+	/// How many transparent [`Node`]s ([`Node::Multi`], [`Node::Keyed`] and [`Node::Memoized`]) and elements rendered as content only
+	/// may be around any [`Node`], counting only those and regardless of any other elements in between.
+	///
+	/// These don't count against [`RenderOptions::depth_limit`], so without this limit, a long enough chain of them
+	/// (like a [`Node::Multi`] in a [`Node::Multi`] in a …) would overflow the stack instead of causing an error.
+	///
+	/// For example, with a limit of `1`, `Multi[Multi[]]` can be rendered, but `Multi[Multi[Multi[]]]` can't.
+	///
+	/// Defaults to [`RenderOptions::DEFAULT_TRANSPARENT_DEPTH_LIMIT`] (256),
+	/// which also applies to rendering functions that only take a `depth_limit`.
+	#[must_use]
+	pub const fn transparent_depth_limit(&self) -> usize {
+		self.transparent_depth_limit
+	}
+
+	/// Sets how many transparent [`Node`]s and elements rendered as content only may be around any [`Node`].
+	///
+	/// See [`RenderOptions::transparent_depth_limit`].
+	#[must_use]
+	pub const fn with_transparent_depth_limit(self, transparent_depth_limit: usize) -> Self {
+		Self {
+			transparent_depth_limit,
+			..self
+		}
+	}
+
+	/// Whether to add an `xmlns` attribute to the outermost element of each [`Node::SvgElement`] or [`Node::MathMlElement`] subtree.
+	///
+	/// This is necessary when that subtree is used as standalone XML document, like an `.svg` file.  
+	/// Elements that already have an `xmlns` attribute are left as-is.
+	#[must_use]
+	pub const fn xmlns(&self) -> bool {
+		self.xmlns
+	}
+
+	/// Sets whether to add `xmlns` attributes to the outermost elements of foreign subtrees.
+	///
+	/// See [`RenderOptions::xmlns`].
+	#[must_use]
+	pub const fn with_xmlns(self, xmlns: bool) -> Self {
+		Self { xmlns, ..self }
+	}
+
+	/// Whether element names that clearly belong to another namespace are rejected.
+	///
+	/// For example, with this enabled, a [`Node::HtmlElement`] named `circle` or a [`Node::SvgElement`] named `div` is an error.  
+	/// Names that exist in more than one namespace, like `a`, `title`, `script` and `style`, as well as unknown names are always accepted.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn strict_namespaces(&self) -> bool {
+		self.strict_namespaces
+	}
+
+	/// Sets whether element names that clearly belong to another namespace are rejected.
+	///
+	/// See [`RenderOptions::strict_namespaces`].
+	#[must_use]
+	pub const fn with_strict_namespaces(self, strict_namespaces: bool) -> Self {
+		Self {
+			strict_namespaces,
+			..self
+		}
+	}
+
+	/// Whether [`render_document_with_options`] starts its output with a UTF-8 [byte order mark](https://en.wikipedia.org/wiki/Byte_order_mark) (`U+FEFF`).
+	///
+	/// The BOM is written like any other character, so it counts as three bytes of output.  
+	/// Fragments are never prefixed with one.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn emit_bom(&self) -> bool {
+		self.emit_bom
+	}
+
+	/// Sets whether [`render_document_with_options`] starts its output with a UTF-8 byte order mark.
+	///
+	/// See [`RenderOptions::emit_bom`].
+	#[must_use]
+	pub const fn with_emit_bom(self, emit_bom: bool) -> Self {
+		Self { emit_bom, ..self }
+	}
+
+	/// Whether [`render_document_with_options`] ends its output with a line feed (`\n`), as many POSIX tools expect of text files.
+	///
+	/// Fragments never end with one.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn trailing_newline(&self) -> bool {
+		self.trailing_newline
+	}
+
+	/// Sets whether [`render_document_with_options`] ends its output with a line feed.
+	///
+	/// See [`RenderOptions::trailing_newline`].
+	#[must_use]
+	pub const fn with_trailing_newline(self, trailing_newline: bool) -> Self {
+		Self {
+			trailing_newline,
+			..self
+		}
+	}
+
+	/// How [`render_document_with_options`] writes the DOCTYPE.
+	///
+	/// Defaults to [`DoctypeStyle::Uppercase`] (`<!DOCTYPE html>`).
+	#[must_use]
+	pub const fn doctype_style(&self) -> DoctypeStyle {
+		self.doctype_style
+	}
+
+	/// Sets how [`render_document_with_options`] writes the DOCTYPE.
+	///
+	/// See [`RenderOptions::doctype_style`].
+	#[must_use]
+	pub const fn with_doctype_style(self, doctype_style: DoctypeStyle) -> Self {
+		Self {
+			doctype_style,
+			..self
+		}
+	}
+
+	/// How to format the output for readability, if at all.
+	///
+	/// Any formatting only adds whitespace where it's not significant.
+	///
+	/// Defaults to [`None`].
+	#[must_use]
+	pub const fn pretty(&self) -> Option<PrettyOptions> {
+		self.pretty
+	}
+
+	/// Sets how to format the output for readability.
+	///
+	/// See [`RenderOptions::pretty`].
+	#[must_use]
+	pub const fn with_pretty(self, pretty: Option<PrettyOptions>) -> Self {
+		Self { pretty, ..self }
+	}
+
+	/// How [`Node::Comment`]s are made safe to render.
+	///
+	/// Defaults to [`CommentPolicy::Legalize`].
+	#[must_use]
+	pub const fn comment_policy(&self) -> CommentPolicy {
+		self.comment_policy
+	}
+
+	/// Sets how [`Node::Comment`]s are made safe to render.
+	///
+	/// See [`CommentPolicy`].
+	#[must_use]
+	pub const fn with_comment_policy(self, comment_policy: CommentPolicy) -> Self {
+		Self {
+			comment_policy,
+			..self
+		}
+	}
+
+	/// The character that [`CommentPolicy::Legalize`] inserts at the start or end of comment text
+	/// that would otherwise close the comment early or run into its delimiters, like a leading `>` or trailing `<!-`.
+	///
+	/// This can't be `-`, `<`, `>` or a control character, which could themselves end up forming a delimiter,
+	/// and must be ASCII with [`RenderOptions::ascii_only`].
+	/// Rendering a [`Node::Comment`] with a policy other than [`CommentPolicy::Verbatim`] is an error otherwise.
+	///
+	/// Defaults to `'|'`.
+	#[must_use]
+	pub const fn comment_guard_char(&self) -> char {
+		self.comment_guard_char
+	}
+
+	/// Sets the character that [`CommentPolicy::Legalize`] inserts around problematic comment text.
+	///
+	/// See [`RenderOptions::comment_guard_char`].
+	#[must_use]
+	pub const fn with_comment_guard_char(self, comment_guard_char: char) -> Self {
+		Self {
+			comment_guard_char,
+			..self
+		}
+	}
+
+	/// Whether duplicate [`ReorderableFragment::dom_key`]s within a single [`Node::Keyed`] are rejected.
+	///
+	/// Duplicate keys don't affect the rendered HTML, but can break reconciliation when the page is later hydrated.
+	///
+	/// Defaults to `false`.
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn strict_keys(&self) -> bool {
+		self.strict_keys
+	}
+
+	/// Sets whether duplicate [`ReorderableFragment::dom_key`]s within a single [`Node::Keyed`] are rejected.
+	///
+	/// See [`RenderOptions::strict_keys`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn with_strict_keys(self, strict_keys: bool) -> Self {
+		Self {
+			strict_keys,
+			..self
+		}
+	}
+
+	/// Whether the content of each [`Node::Keyed`] is rendered in order of [`ReorderableFragment::dom_key`]s instead of as given.
+	///
+	/// This produces the same output for the same set of fragments regardless of their order, e.g. for caching.  
+	/// Note that this also changes the order in which they appear on the page! Fragments with equal keys keep their relative order.
+	///
+	/// Defaults to `false`, which renders fragments in their given (DOM) order.
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn sort_keyed(&self) -> bool {
+		self.sort_keyed
+	}
+
+	/// Sets whether the content of each [`Node::Keyed`] is rendered in order of [`ReorderableFragment::dom_key`]s.
+	///
+	/// See [`RenderOptions::sort_keyed`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn with_sort_keyed(self, sort_keyed: bool) -> Self {
+		Self { sort_keyed, ..self }
+	}
+
+	/// How often the same [`Node`] may be rendered during one call, if limited.
+	///
+	/// Since [`Node`]s are borrowed, a large subtree can be included many times over with little effort,
+	/// which multiplies the output size accordingly. This helps catch templates that do so by accident.
+	///
+	/// [`Node`]s are [`Copy`], so they're identified by (the address of) what they borrow rather than by value or their own address:
+	/// elements by their [`Element`], [`Node::Multi`] and [`Node::Keyed`] by their (non-empty) slice and [`Node::Memoized`] by its content.
+	/// Only [`Node::Text`]s and [`Node::Comment`]s aren't counted, and [`Node::Memoized`] only if its content is one of them,
+	/// since other content is counted already.
+	///
+	/// Defaults to [`None`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn repeat_limit(&self) -> Option<usize> {
+		self.repeat_limit
+	}
+
+	/// Sets how often the same [`Node`] may be rendered during one call.
+	///
+	/// See [`RenderOptions::repeat_limit`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn with_repeat_limit(self, repeat_limit: Option<usize>) -> Self {
+		Self {
+			repeat_limit,
+			..self
+		}
+	}
+
+	/// HTML element names that are treated as [***void elements***](https://html.spec.whatwg.org/multipage/syntax.html#void-elements)
+	/// in addition to the standard ones, i.e. written without end tag and required to have empty content.
+	///
+	/// Names are compared ASCII-case-insensitively.
+	///
+	/// > **Warning:** Standard HTML parsers don't treat these elements as void, so anything following one would end up inside it.  
+	/// > Only use this for output that is processed by something that knows about these elements, as it is otherwise invalid HTML!
+	///
+	/// Defaults to none.
+	#[must_use]
+	pub const fn extra_void_elements(&self) -> &'a [&'a str] {
+		self.extra_void_elements
+	}
+
+	/// Sets additional HTML element names that are treated as void elements.
+	///
+	/// See [`RenderOptions::extra_void_elements`].
+	#[must_use]
+	pub const fn with_extra_void_elements(self, extra_void_elements: &'a [&'a str]) -> Self {
+		Self {
+			extra_void_elements,
+			..self
+		}
+	}
+
+	/// HTML element names that are treated as [***raw text elements***](https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements)
+	/// in addition to `script` and `style`, i.e. whose text content is written without escaping.
+	///
+	/// As with the standard raw text elements, only [`Node::Text`]s are allowed as content
+	/// and text that would close the element early is an error.
+	///
+	/// Names are compared ASCII-case-insensitively.
+	/// [`RenderOptions::extra_void_elements`] take precedence.
+	///
+	/// > **Warning:** Text inside these elements isn't escaped, so **any markup in it is rendered as-is**.
+	/// > Never put untrusted text into them.
+	/// >
+	/// > Standard HTML parsers also don't treat these elements as raw text, so the output is only valid for something that does.
+	///
+	/// Defaults to none.
+	#[must_use]
+	pub const fn extra_raw_text_elements(&self) -> &'a [&'a str] {
+		self.extra_raw_text_elements
+	}
+
+	/// Sets additional HTML element names that are treated as raw text elements.
+	///
+	/// See [`RenderOptions::extra_raw_text_elements`].
+	#[must_use]
+	pub const fn with_extra_raw_text_elements(
+		self,
+		extra_raw_text_elements: &'a [&'a str],
+	) -> Self {
+		Self {
+			extra_raw_text_elements,
+			..self
+		}
+	}
+
+	/// Whether a top-level HTML `html` element without `lang` attribute is an error.
+	///
+	/// The document language is needed by e.g. screen readers to pick the right pronunciation.  
+	/// `html` elements nested inside other elements aren't checked.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn require_html_lang(&self) -> bool {
+		self.require_html_lang
+	}
+
+	/// Sets whether a top-level HTML `html` element without `lang` attribute is an error.
+	///
+	/// See [`RenderOptions::require_html_lang`].
+	#[must_use]
+	pub const fn with_require_html_lang(self, require_html_lang: bool) -> Self {
+		Self {
+			require_html_lang,
+			..self
+		}
+	}
+
+	/// Whether [`render_document_with_options`] rejects any `vdom` that isn't a single HTML `html` element,
+	/// instead of writing the DOCTYPE before arbitrary content.
+	///
+	/// Fragments aren't affected by this.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn require_html_root(&self) -> bool {
+		self.require_html_root
+	}
+
+	/// Sets whether [`render_document_with_options`] requires the document root to be an HTML `html` element.
+	///
+	/// See [`RenderOptions::require_html_root`].
+	#[must_use]
+	pub const fn with_require_html_root(self, require_html_root: bool) -> Self {
+		Self {
+			require_html_root,
+			..self
+		}
+	}
+
+	/// Whether an HTML `img` element without `alt` attribute is an error.
+	///
+	/// An empty `alt` attribute is fine, as it marks the image as decorative.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn require_img_alt(&self) -> bool {
+		self.require_img_alt
+	}
+
+	/// Sets whether an HTML `img` element without `alt` attribute is an error.
+	///
+	/// See [`RenderOptions::require_img_alt`].
+	#[must_use]
+	pub const fn with_require_img_alt(self, require_img_alt: bool) -> Self {
+		Self {
+			require_img_alt,
+			..self
+		}
+	}
+
+	/// Whether to reject HTML elements that are out of place in a document's `head` or `body`.
+	///
+	/// Only a few clear-cut cases are checked:
+	///
+	/// - Elements directly inside `head` must be `base`, `link`, `meta`, `noscript`, `script`, `style`, `template` or `title`.
+	/// - `base`, `head` and `title` elements, as well as `meta` elements without `itemprop` attribute, must not appear anywhere inside `body`.
+	///
+	/// Foreign elements (like SVG `title`) and text aren't checked.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn strict_document_structure(&self) -> bool {
+		self.strict_document_structure
+	}
+
+	/// Sets whether to reject HTML elements that are out of place in a document's `head` or `body`.
+	///
+	/// See [`RenderOptions::strict_document_structure`].
+	#[must_use]
+	pub const fn with_strict_document_structure(self, strict_document_structure: bool) -> Self {
+		Self {
+			strict_document_structure,
+			..self
+		}
+	}
+
+	/// Whether to add `loading="lazy"` and `decoding="async"` to HTML `img` elements that don't specify them already.
+	///
+	/// These attributes are added after the element's own attributes. Existing values are never overridden.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn image_perf_defaults(&self) -> bool {
+		self.image_perf_defaults
+	}
+
+	/// Sets whether to add `loading="lazy"` and `decoding="async"` to HTML `img` elements that don't specify them already.
+	///
+	/// See [`RenderOptions::image_perf_defaults`].
+	#[must_use]
+	pub const fn with_image_perf_defaults(self, image_perf_defaults: bool) -> Self {
+		Self {
+			image_perf_defaults,
+			..self
+		}
+	}
+
+	/// Whether to make sure that the `rel` attribute of HTML `a` elements with `target="_blank"` contains `noopener` and `noreferrer`.
+	///
+	/// This prevents the opened page from navigating the opening one ("tab-nabbing").  
+	/// Missing tokens are appended to an existing `rel` attribute. Otherwise, `rel="noopener noreferrer"` is added after the element's own attributes.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn harden_blank_targets(&self) -> bool {
+		self.harden_blank_targets
+	}
+
+	/// Sets whether to add `noopener` and `noreferrer` to the `rel` attribute of HTML `a` elements with `target="_blank"`.
+	///
+	/// See [`RenderOptions::harden_blank_targets`].
+	#[must_use]
+	pub const fn with_harden_blank_targets(self, harden_blank_targets: bool) -> Self {
+		Self {
+			harden_blank_targets,
+			..self
+		}
+	}
+
+	/// A [Content Security Policy nonce](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/nonce)
+	/// that is added as `nonce` attribute to each HTML `script` and `style` element that doesn't have one already.
+	///
+	/// The nonce is escaped like any other attribute value.
+	///
+	/// Defaults to [`None`].
+	#[must_use]
+	pub const fn csp_nonce(&self) -> Option<&'a str> {
+		self.csp_nonce
+	}
+
+	/// Sets a Content Security Policy nonce to add to `script` and `style` elements.
+	///
+	/// See [`RenderOptions::csp_nonce`].
+	#[must_use]
+	pub const fn with_csp_nonce(self, csp_nonce: Option<&'a str>) -> Self {
+		Self { csp_nonce, ..self }
+	}
+
+	/// An absolute URL, like that of a `<base href>`, to resolve relative `href` and `src` attribute values against.
+	///
+	/// Only clear cases are resolved: The base must be hierarchical (e.g. `https://example.com/dir/`)
+	/// and each value scheme-relative (`//host/path`), path-absolute (`/path`) or path-relative (`path`).
+	/// Everything else, including absolute URLs and values starting with `?` or `#`, is written as is.
+	///
+	/// This applies after [`RenderOptions::map_attribute`], but not to the `href` of `base` elements themselves.
+	///
+	/// Defaults to [`None`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn base_href(&self) -> Option<&'a str> {
+		self.base_href
+	}
+
+	/// Sets an absolute URL to resolve relative `href` and `src` attribute values against.
+	///
+	/// See [`RenderOptions::base_href`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub const fn with_base_href(self, base_href: Option<&'a str>) -> Self {
+		Self { base_href, ..self }
+	}
+
+	/// Whether to write only ASCII, e.g. for transports that can't reliably handle anything else.
+	///
+	/// Non-ASCII characters in text and attribute values are then written as hexadecimal numeric character references, like `&#xe9;`.
+	/// Where that's not possible, i.e. in raw text, comments and element or attribute names, non-ASCII characters are an error.
+	///
+	/// > The byte order mark written by [`RenderOptions::emit_bom`] is not affected by this.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn ascii_only(&self) -> bool {
+		self.ascii_only
+	}
+
+	/// Sets whether to write only ASCII.
+	///
+	/// See [`RenderOptions::ascii_only`].
+	#[must_use]
+	pub const fn with_ascii_only(self, ascii_only: bool) -> Self {
+		Self { ascii_only, ..self }
+	}
+
+	/// Whether text, comments and attribute values containing NUL (U+0000) are an error, so that the output never contains a NUL byte.
+	///
+	/// This is a hard check for output that is passed on to e.g. C APIs with NUL-terminated strings.
+	/// Only text from the rendered [`Node`]s is checked (including [`RenderOptions::map_attribute`] results), not options like [`RenderOptions::csp_nonce`].
+	///
+	/// Legalized comments (see [`CommentPolicy`]) are unaffected, since NUL is already replaced with U+FFFD in them.
+	///
+	/// Defaults to `false`, which writes NUL as-is.
+	#[must_use]
+	pub const fn reject_nul(&self) -> bool {
+		self.reject_nul
+	}
+
+	/// Sets whether text, comments and attribute values containing NUL are an error.
+	///
+	/// See [`RenderOptions::reject_nul`].
+	#[must_use]
+	pub const fn with_reject_nul(self, reject_nul: bool) -> Self {
+		Self { reject_nul, ..self }
+	}
+
+	/// Whether to write some easily confused characters in text and attribute values as named character references, for readability.
+	///
+	/// This only covers a small, unambiguous set:
+	/// `&nbsp;` (U+00A0), `&shy;` (U+00AD), `&copy;` (U+00A9), `&reg;` (U+00AE),
+	/// `&ndash;` (U+2013), `&mdash;` (U+2014), `&hellip;` (U+2026) and `&trade;` (U+2122).  
+	/// Named character references take precedence over [`RenderOptions::ascii_only`]'s numeric ones.
+	///
+	/// This has no effect in XML, which only knows a few named character references.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn named_entities(&self) -> bool {
+		self.named_entities
+	}
+
+	/// Sets whether to write some characters as named character references.
+	///
+	/// See [`RenderOptions::named_entities`].
+	#[must_use]
+	pub const fn with_named_entities(self, named_entities: bool) -> Self {
+		Self {
+			named_entities,
+			..self
+		}
+	}
+
+	/// Whether to leave `&` unescaped in text and attribute values where it already starts a character reference, like `&amp;` or `&#x2F;`.
+	///
+	/// This is useful for text that is already (partially) escaped, which would otherwise be escaped twice.  
+	/// Note that such text then renders as its *unescaped* meaning, i.e. `a&amp;b` is parsed as `a&b`.
+	///
+	/// Raw text (e.g. in `script`) is never escaped, so it's not affected by this.
+	///
+	/// Only numeric character references and a few common named ones (including `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`) are recognized,
+	/// and only if terminated by a semicolon. Any other `&` is still escaped.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn smart_ampersands(&self) -> bool {
+		self.smart_ampersands
+	}
+
+	/// Sets whether to leave `&` unescaped where it already starts a character reference.
+	///
+	/// See [`RenderOptions::smart_ampersands`].
+	#[must_use]
+	pub const fn with_smart_ampersands(self, smart_ampersands: bool) -> Self {
+		Self {
+			smart_ampersands,
+			..self
+		}
+	}
+
+	/// Whether to write HTML the way browsers serialize the DOM (e.g. as [`outerHTML`](https://developer.mozilla.org/en-US/docs/Web/API/Element/outerHTML)),
+	/// to reduce noise when comparing server-side rendered output to what a browser produces.
+	///
+	/// This applies the following rules of the [fragment serializing algorithm](https://html.spec.whatwg.org/multipage/parsing.html#serialising-html-fragments):
+	///
+	/// - HTML element names and attribute names on HTML elements are written in ASCII lowercase.
+	///   Names on foreign (SVG and `MathML`) elements keep their case.
+	/// - Non-empty attribute values are always double-quoted.
+	///   `&`, `"`, `<`, `>` and U+00A0 in them are written as `&amp;`, `&quot;`, `&lt;`, `&gt;` and `&nbsp;`.
+	/// - `&`, `<`, `>` and U+00A0 in text, including in `title` and `textarea`, are written as `&amp;`, `&lt;`, `&gt;` and `&nbsp;`.
+	/// - Empty foreign elements get an end tag instead of being self-closing.
+	/// - A newline is added after the start tags of `pre`, `listing` and `textarea` only if their text starts with one,
+	///   which the parser would drop otherwise.
+	///
+	/// On top of that, two rules make the output independent of incidental differences between equivalent VDOMs:
+	///
+	/// - [`Element::attributes`] are sorted by name, ASCII-case-insensitively.
+	///   Attributes with the same name keep their order, and attributes added by the renderer (like `xmlns` or [`RenderOptions::csp_nonce`]) keep their position.
+	/// - Empty attribute values are left out, so boolean attributes are written bare, like `hidden`.
+	///
+	/// > Browsers instead keep the attribute order and write `hidden=""`,
+	/// > so to compare with their output directly, sort its attributes and drop `=""` first.
+	///
+	/// Void elements never have a `/` either way.
+	///
+	/// Other options still apply on top of this, so e.g. [`RenderOptions::smart_ampersands`] or [`RenderOptions::named_entities`] can cause differences.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn browser_canonical(&self) -> bool {
+		self.browser_canonical
+	}
+
+	/// Sets whether to write HTML the way browsers serialize the DOM.
+	///
+	/// See [`RenderOptions::browser_canonical`].
+	#[must_use]
+	pub const fn with_browser_canonical(self, browser_canonical: bool) -> Self {
+		Self {
+			browser_canonical,
+			..self
+		}
+	}
+
+	/// Whether `data-*` attribute names with ASCII uppercase letters (like `data-fooBar`) are an error.
+	///
+	/// Parsers lowercase attribute names, so such an attribute ends up in `dataset.foobar` rather than the likely intended `dataset.fooBar`,
+	/// which is written `data-foo-bar`. The `data-` prefix itself is matched ASCII-case-insensitively.
+	///
+	/// Only [`Element::attributes`] are checked, on elements in all namespaces.
+	///
+	/// Defaults to `false`, which accepts them like any other attribute name.
+	#[must_use]
+	pub const fn strict_data_attributes(&self) -> bool {
+		self.strict_data_attributes
+	}
+
+	/// Sets whether `data-*` attribute names with ASCII uppercase letters are an error.
+	///
+	/// See [`RenderOptions::strict_data_attributes`].
+	#[must_use]
+	pub const fn with_strict_data_attributes(self, strict_data_attributes: bool) -> Self {
+		Self {
+			strict_data_attributes,
+			..self
+		}
+	}
+
+	/// Whether text in [***escapable raw text elements***](https://html.spec.whatwg.org/multipage/syntax.html#escapable-raw-text-elements)
+	/// (`title` and `textarea`) only has `</` escaped where it could start the element's own end tag, like `</title>`.
+	///
+	/// Otherwise, every `</` is written as `&lt;/`, which is safe but often unnecessary (e.g. in `a</b>c`).  
+	/// [`Node::Text`]s are checked one by one, so a `<` or `</` at the end of one, or followed by only the start of the element name,
+	/// is still escaped in case the next one continues it.
+	///
+	/// `&` in these elements is then also left as-is where it starts a character reference, exactly like with [`RenderOptions::smart_ampersands`],
+	/// so that e.g. `&copy;` is kept rather than turned into `&amp;copy;`.  
+	/// > **Warning:** This means such text is rendered as its *unescaped* meaning.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn precise_escapable_raw_text(&self) -> bool {
+		self.precise_escapable_raw_text
+	}
+
+	/// Sets whether text in escapable raw text elements only has `</` escaped where it could start the element's end tag.
+	///
+	/// See [`RenderOptions::precise_escapable_raw_text`].
+	#[must_use]
+	pub const fn with_precise_escapable_raw_text(self, precise_escapable_raw_text: bool) -> Self {
+		Self {
+			precise_escapable_raw_text,
+			..self
+		}
+	}
+
+	/// Whether SVG element and attribute names that HTML parsers know in camelCase are written that way
+	/// regardless of their case in the VDOM, e.g. `viewbox` as `viewBox` or `LINEARGRADIENT` as `linearGradient`.
+	///
+	/// This uses the tables the HTML parser [adjusts names in foreign content](https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inforeign) with,
+	/// so that VDOMs from case-insensitive sources still work when the DOM is created from them directly. Other names are written unchanged.
+	///
+	/// Only [`Node::SvgElement`]s are affected, after [`RenderOptions::map_element_name`].
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn normalize_svg_case(&self) -> bool {
+		self.normalize_svg_case
+	}
+
+	/// Sets whether known SVG element and attribute names are written in their canonical camelCase.
+	///
+	/// See [`RenderOptions::normalize_svg_case`].
+	#[must_use]
+	pub const fn with_normalize_svg_case(self, normalize_svg_case: bool) -> Self {
+		Self {
+			normalize_svg_case,
+			..self
+		}
+	}
+
+	/// Whether an empty comment (`<!---->`) is written between adjacent non-empty [`Node::Text`]s,
+	/// so that parsing the output creates the same text nodes instead of merging them.
+	///
+	/// Only text nodes that would otherwise be merged are separated, also across [`Node::Multi`], [`Node::Keyed`] and [`Node::Memoized`] boundaries
+	/// and elements rendered as content only (see [`RenderOptions::fragment_element_name`]).  
+	/// The inserted comments are part of the DOM when parsed, but not visible in the rendered page.
+	/// [`render_fragment_roundtrip_safe`] lists where they were inserted, so that they can be removed again.
+	///
+	/// This doesn't make rendering fully round-trip-safe:
+	///
+	/// - Empty [`Node::Text`]s are still not parsed as text nodes.
+	/// - The content of raw text elements like `script` and escapable raw text elements like `title` is always parsed as a single text node.
+	/// - Comments may still be changed by [`RenderOptions::comment_policy`].
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn roundtrip_safe(&self) -> bool {
+		self.roundtrip_safe
+	}
+
+	/// Sets whether empty comments are written between adjacent text nodes.
+	///
+	/// See [`RenderOptions::roundtrip_safe`].
+	#[must_use]
+	pub const fn with_roundtrip_safe(self, roundtrip_safe: bool) -> Self {
+		Self {
+			roundtrip_safe,
+			..self
+		}
+	}
+
+	/// Names of attributes whose values are written as [`RenderOptions::REDACTED`] (`"[redacted]"`) instead,
+	/// e.g. to share dumps of rendered HTML without leaking secrets or personal data.
+	///
+	/// Names are compared ASCII-case-insensitively. A name ending in `*` matches any attribute name that starts with the rest,
+	/// so `"data-secret-*"` redacts `data-secret-token`.
+	///
+	/// This applies to all attributes on all elements, including ones added by the renderer (like a [`RenderOptions::csp_nonce`]),
+	/// after [`RenderOptions::map_attribute`].
+	///
+	/// > **Warning:** Only attribute values are redacted. Secrets in text or other attributes are still written as-is.
+	///
+	/// Defaults to none.
+	#[must_use]
+	pub const fn redact_attributes(&self) -> &'a [&'a str] {
+		self.redact_attributes
+	}
+
+	/// Sets names of attributes whose values are redacted.
+	///
+	/// See [`RenderOptions::redact_attributes`].
+	#[must_use]
+	pub const fn with_redact_attributes(self, redact_attributes: &'a [&'a str]) -> Self {
+		Self {
+			redact_attributes,
+			..self
+		}
+	}
+
+	/// Names of attributes that are written as `name=""` rather than just `name` when their value is empty.
+	///
+	/// Both forms parse to the same empty value, but the explicit one may be clearer where an empty value is meaningful,
+	/// like `value` of an `input`, rather than one meaning "present", like `required`.
+	/// This also applies with [`RenderOptions::browser_canonical`].
+	///
+	/// Names are matched like those in [`RenderOptions::redact_attributes`].
+	///
+	/// Defaults to none.
+	#[must_use]
+	pub const fn quote_empty_attributes(&self) -> &'a [&'a str] {
+		self.quote_empty_attributes
+	}
+
+	/// Sets names of attributes whose empty values are written as `""`.
+	///
+	/// See [`RenderOptions::quote_empty_attributes`].
+	#[must_use]
+	pub const fn with_quote_empty_attributes(self, quote_empty_attributes: &'a [&'a str]) -> Self {
+		Self {
+			quote_empty_attributes,
+			..self
+		}
+	}
+
+	/// Errors iff [`RenderOptions::reject_nul`] is set and `text` contains NUL.
+	fn check_nul<'t, S: ThreadSafety>(&self, text: &'t str) -> Result<(), Error<'t, S>> {
+		if self.reject_nul && text.contains('\0') {
+			Err(Error(ErrorKind::NulByteInOutput(text)))
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Whether the value of the attribute named `attribute_name` should be redacted. See [`RenderOptions::redact_attributes`].
+	fn redacts(&self, attribute_name: &str) -> bool {
+		matches_attribute_name(self.redact_attributes, attribute_name)
+	}
+
+	/// Maps each element name before it's validated and rendered, e.g. to translate framework-specific names to HTML ones.
+	///
+	/// If this returns [`None`], the element's content is rendered in its place *without* wrapping element,
+	/// like a [`Node::Multi`]. The element's attributes are then ignored.
+	///
+	/// Defaults to the identity function.
+	#[must_use]
+	pub fn map_element_name(&self) -> fn(&str) -> Option<&str> {
+		#[allow(clippy::unnecessary_wraps)] // Must match the mapper signature.
+		fn identity(name: &str) -> Option<&str> {
+			Some(name)
+		}
+		self.map_element_name.map_or(identity, |mapper| mapper.0)
+	}
+
+	/// Sets a function that maps element names before rendering.
+	///
+	/// See [`RenderOptions::map_element_name`].
+	#[must_use]
+	pub fn with_map_element_name(self, map_element_name: fn(&str) -> Option<&str>) -> Self {
+		Self {
+			map_element_name: Some(ElementNameMapper(map_element_name)),
+			..self
+		}
+	}
+
+	/// Decides for each [`Element`] whether it's rendered at all, e.g. to leave out sections behind server-side feature flags.
+	///
+	/// If this returns `false`, the element is skipped along with its content, so nothing is written in its place.
+	/// (Compare [`RenderOptions::map_element_name`], which can drop just the element's tag.)
+	///
+	/// This is checked first, with the element as it appears in the VDOM (so before its name is mapped).  
+	/// The element is passed as [`ThreadBound`](`lignin::ThreadBound`), which any [`Element`] can be viewed as,
+	/// so that one function works regardless of the rendered VDOM's [`ThreadSafety`].
+	///
+	/// Defaults to always `true`.
+	#[must_use]
+	pub fn should_render(&self) -> fn(&Element<ThreadBound>) -> bool {
+		fn always(_: &Element<ThreadBound>) -> bool {
+			true
+		}
+		self.should_render.map_or(always, |filter| filter.0)
+	}
+
+	/// Sets a function that decides whether each element is rendered.
+	///
+	/// See [`RenderOptions::should_render`].
+	#[must_use]
+	pub fn with_should_render(self, should_render: fn(&Element<ThreadBound>) -> bool) -> Self {
+		Self {
+			should_render: Some(ElementFilter(should_render)),
+			..self
+		}
+	}
+
+	/// The element name (compared exactly) of pseudo-elements that render only their content, like a [`Node::Multi`].
+	///
+	/// This is useful where a template requires each node to be an element.  
+	/// Since there is no tag to put them on, such a fragment can't have attributes (or an [`is`](`lignin::ElementCreationOptions::is`) value);
+	/// rendering one that does is an error.
+	///
+	/// The name is checked before [`RenderOptions::map_element_name`] is applied.
+	/// The default isn't a valid element name, so it can't clash with an actual element.
+	///
+	/// Defaults to [`Some`]\([`RenderOptions::DEFAULT_FRAGMENT_ELEMENT_NAME`]\) (`"lignin:fragment"`).
+	#[must_use]
+	pub const fn fragment_element_name(&self) -> Option<&'a str> {
+		self.fragment_element_name
+	}
+
+	/// Sets the element name of pseudo-elements that render only their content, or disables them with [`None`].
+	///
+	/// See [`RenderOptions::fragment_element_name`].
+	#[must_use]
+	pub const fn with_fragment_element_name(self, fragment_element_name: Option<&'a str>) -> Self {
+		Self {
+			fragment_element_name,
+			..self
+		}
+	}
+
+	/// Maps each attribute value, given the (mapped) element name, the attribute name and the value.
+	///
+	/// If this returns [`None`], the attribute is omitted. Otherwise, the returned value is written instead and escaped as usual.  
+	/// This is useful for e.g. URL rewriting or cache-busting query strings.
+	///
+	/// Only [`Element::attributes`] are mapped, not ones added by the renderer (like `xmlns` or `loading`).
+	///
+	/// Defaults to the identity function.
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn map_attribute(&self) -> MapAttribute {
+		#[allow(clippy::unnecessary_wraps)] // Must match the mapper signature.
+		fn identity<'v>(_: &str, _: &str, value: &'v str) -> Option<Cow<'v, str>> {
+			Some(Cow::Borrowed(value))
+		}
+		self.map_attribute.map_or(identity, |mapper| mapper.0)
+	}
+
+	/// Sets a function that maps attribute values before rendering.
+	///
+	/// See [`RenderOptions::map_attribute`].
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn with_map_attribute(self, map_attribute: MapAttribute) -> Self {
+		Self {
+			map_attribute: Some(AttributeMapper(map_attribute)),
+			..self
+		}
+	}
+}
+
+/// The signature of [`RenderOptions::map_attribute`] functions: `(element_name, attribute_name, value) -> value`.
+#[cfg(feature = "alloc")]
+pub type MapAttribute = for<'v> fn(&str, &str, &'v str) -> Option<Cow<'v, str>>;
+
+/// A [`RenderOptions::map_attribute`] function, compared by address.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy)]
+struct AttributeMapper(MapAttribute);
+
+#[cfg(feature = "alloc")]
+impl PartialEq for AttributeMapper {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 as usize == other.0 as usize
+	}
+}
+#[cfg(feature = "alloc")]
+impl Eq for AttributeMapper {}
+
+#[cfg(feature = "alloc")]
+impl Debug for AttributeMapper {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "AttributeMapper({:#x})", self.0 as usize)
+	}
+}
+
+/// A [`RenderOptions::map_element_name`] function, compared by address.
+#[derive(Clone, Copy)]
+struct ElementNameMapper(fn(&str) -> Option<&str>);
+
+impl PartialEq for ElementNameMapper {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 as usize == other.0 as usize
+	}
+}
+impl Eq for ElementNameMapper {}
+
+impl Debug for ElementNameMapper {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "ElementNameMapper({:#x})", self.0 as usize)
+	}
+}
+
+/// A [`RenderOptions::should_render`] function, compared by address.
+#[derive(Clone, Copy)]
+struct ElementFilter(fn(&Element<ThreadBound>) -> bool);
+
+impl PartialEq for ElementFilter {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 as usize == other.0 as usize
+	}
+}
+impl Eq for ElementFilter {}
+
+impl Debug for ElementFilter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "ElementFilter({:#x})", self.0 as usize)
+	}
+}
+
+/// Formatting options for readable output. See [`RenderOptions::with_pretty`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOptions {
+	max_line_width: usize,
+}
+
+impl Default for PrettyOptions {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PrettyOptions {
+	/// Creates a new [`PrettyOptions`] instance with default settings.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			max_line_width: 100,
+		}
+	}
+
+	/// How long a start tag may be, in bytes, before its attributes are written on separate lines.
+	///
+	/// Each attribute is then written on its own line, indented by one tab.  
+	/// The start tag is measured on its own, regardless of where on the line it starts.
+	///
+	/// Defaults to `100`.
+	#[must_use]
+	pub const fn max_line_width(&self) -> usize {
+		self.max_line_width
+	}
+
+	/// Sets how long a start tag may be before its attributes are written on separate lines.
+	///
+	/// See [`PrettyOptions::max_line_width`].
+	#[must_use]
+	pub const fn with_max_line_width(self, max_line_width: usize) -> Self {
+		Self { max_line_width }
+	}
+}
+
+/// Options for [`render_text_only_with_options`].
+///
+/// The defaults match [`render_text_only`]'s behaviour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextOnlyOptions {
+	collapse_whitespace: bool,
+	block_boundaries: bool,
+}
+
+impl TextOnlyOptions {
+	/// Creates a new [`TextOnlyOptions`] instance with default settings.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			collapse_whitespace: false,
+			block_boundaries: false,
+		}
+	}
+
+	/// Whether each run of ASCII whitespace in [`Node::Text`]s is written as a single space, like a browser displays it.
+	///
+	/// Whitespace across adjacent [`Node::Text`]s is collapsed too.  
+	/// Text inside elements classified as [`WhitespaceSensitivity::Pre`] is still written as-is.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn collapse_whitespace(&self) -> bool {
+		self.collapse_whitespace
+	}
+
+	/// Sets whether runs of whitespace are written as a single space.
+	///
+	/// See [`TextOnlyOptions::collapse_whitespace`].
+	#[must_use]
+	pub const fn with_collapse_whitespace(self, collapse_whitespace: bool) -> Self {
+		Self {
+			collapse_whitespace,
+			..self
+		}
+	}
+
+	/// Whether the text of HTML elements classified as [`WhitespaceSensitivity::Block`] is separated from surrounding text by a newline.
+	///
+	/// Consecutive boundaries (and any whitespace collapsed next to them) result in a single `'\n'`.
+	///
+	/// Defaults to `false`.
+	#[must_use]
+	pub const fn block_boundaries(&self) -> bool {
+		self.block_boundaries
+	}
+
+	/// Sets whether block elements' text is separated by newlines.
+	///
+	/// See [`TextOnlyOptions::block_boundaries`].
+	#[must_use]
+	pub const fn with_block_boundaries(self, block_boundaries: bool) -> Self {
+		Self {
+			block_boundaries,
+			..self
+		}
+	}
+}
+
+/// How [`Node::Comment`]s are made safe to render. See [`RenderOptions::with_comment_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommentPolicy {
+	/// Dashes are replaced and guard characters inserted where necessary, as described in the [crate-level caveats](`crate`#caveats).
+	///
+	/// This is the default.
+	Legalize,
+	/// The comment text is written exactly as given.
+	///
+	/// This is useful for e.g. conditional comments (`[if IE]>…<![endif]`),
+	/// which the [`Legalize`](`CommentPolicy::Legalize`) policy would break up.
+	///
+	/// > **Warning:** Any `-->` or `--!>` in the comment closes it early, and what follows is parsed as HTML!  
+	/// > Never use this with comment text that isn't fully trusted.
+	Verbatim,
+	/// Like [`Legalize`](`CommentPolicy::Legalize`), but rendering a comment is an error if that would replace any of its characters.
+	///
+	/// Guard characters (see [`RenderOptions::comment_guard_char`]) are still inserted, since they don't change the original text.
+	ErrorIfLossy,
+}
+
+impl Default for CommentPolicy {
+	fn default() -> Self {
+		Self::Legalize
+	}
+}
+
+/// How the [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype) is written. See [`RenderOptions::with_doctype_style`].
+///
+/// Both are equally valid HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DoctypeStyle {
+	/// `<!DOCTYPE html>`, as browsers serialize it.
+	///
+	/// This is the default.
+	Uppercase,
+	/// `<!doctype html>`, as some formatters and linters prefer.
+	Lowercase,
+}
+
+impl Default for DoctypeStyle {
+	fn default() -> Self {
+		Self::Uppercase
+	}
+}
+
+impl DoctypeStyle {
+	/// The DOCTYPE in this style.
+	#[must_use]
+	pub const fn doctype(self) -> &'static str {
+		match self {
+			DoctypeStyle::Uppercase => "<!DOCTYPE html>",
+			DoctypeStyle::Lowercase => "<!doctype html>",
+		}
+	}
+}
+
+/// A callback for [`render_fragment_with_visitor`].
+type Visitor<'a, S> = dyn 'a + for<'n> FnMut(&'n Node<'n, S>) -> Result<(), Error<'n, S>>;
+
+/// State shared by all recursive render function calls for one top-level call.
+struct Context<'a, S: ThreadSafety> {
+	options: &'a RenderOptions<'a>,
+	/// Only present when rendering through [`render_fragment_with_visitor`].
+	visitor: Option<&'a RefCell<&'a mut Visitor<'a, S>>>,
+	/// Only present when rendering through [`render_fragment_with_stats`].
+	stats: Option<&'a Cell<RenderStats>>,
+	/// Only present when rendering through [`render_fragment_cached`].
+	#[cfg(feature = "alloc")]
+	memo_cache: Option<&'a RefCell<&'a mut MemoCache>>,
+	/// Only present when rendering through [`render_fragment_with_mapping`].
+	#[cfg(feature = "alloc")]
+	mapping: Option<&'a mapping::Mapping>,
+	/// Only present when rendering through [`render_fragment_with_warnings`], which also sets [`Context::mapping`] for the paths.
+	#[cfg(feature = "alloc")]
+	warnings: Option<&'a RefCell<alloc::vec::Vec<Warning>>>,
+	/// Only present when rendering through [`render_fragment_roundtrip_safe`], which also sets [`Context::mapping`] for the positions.
+	#[cfg(feature = "alloc")]
+	manifest: Option<&'a RefCell<RoundtripManifest>>,
+	/// Whether the last thing written was a non-empty [`Node::Text`]. See [`RenderOptions::roundtrip_safe`].
+	after_text: Cell<bool>,
+	/// How often each [`Node`] was rendered so far, by [`RepeatKey`]. Only filled if there's a [`RenderOptions::repeat_limit`].
+	#[cfg(feature = "alloc")]
+	repeats: RefCell<alloc::collections::BTreeMap<RepeatKey, usize>>,
+}
+
+impl<'a, S: ThreadSafety> Context<'a, S> {
+	fn new(options: &'a RenderOptions<'a>) -> Self {
+		Self {
+			options,
+			visitor: None,
+			stats: None,
+			#[cfg(feature = "alloc")]
+			memo_cache: None,
+			#[cfg(feature = "alloc")]
+			mapping: None,
+			#[cfg(feature = "alloc")]
+			warnings: None,
+			#[cfg(feature = "alloc")]
+			manifest: None,
+			after_text: Cell::new(false),
+			#[cfg(feature = "alloc")]
+			repeats: RefCell::default(),
+		}
+	}
+
+	/// Does the bookkeeping for and checks common to all [`Node`]s about to be rendered at `depth`.
+	fn enter<'n>(&self, vdom: &'n Node<'n, S>, depth: Depth) -> Result<(), Error<'n, S>> {
+		if is_transparent(vdom) {
+			self.check_transparent_depth(vdom, depth)?
+		} else if depth.remaining == 0 {
+			return Err(Error(ErrorKind::DepthLimitExceeded {
+				node: ByAddress(vdom),
+				depth_limit: self.options.depth_limit,
+			}));
+		}
+		self.record(vdom, depth);
+		self.visit(vdom)?;
+		self.count_repeat(vdom)
+	}
+
+	/// Errors iff `vdom`, a transparent [`Node`] or element rendered as content only, is nested too deeply in others.
+	///
+	/// See [`RenderOptions::transparent_depth_limit`].
+	fn check_transparent_depth<'n>(
+		&self,
+		vdom: &'n Node<'n, S>,
+		depth: Depth,
+	) -> Result<(), Error<'n, S>> {
+		if depth.transparent >= self.options.transparent_depth_limit {
+			Err(Error(ErrorKind::TransparentDepthLimitExceeded {
+				node: ByAddress(vdom),
+				transparent_depth_limit: self.options.transparent_depth_limit,
+			}))
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Records a [`Warning`] about the [`Node`] currently being rendered, if rendering through [`render_fragment_with_warnings`].
+	#[cfg(feature = "alloc")]
+	fn warn(&self, warning: fn(NodePath) -> Warning) {
+		if let (Some(warnings), Some(mapping)) = (self.warnings, self.mapping) {
+			warnings.borrow_mut().push(warning(mapping.path()))
+		}
+	}
+
+	/// How many bytes were written so far, if rendering through a function that keeps track of this.
+	#[cfg(feature = "alloc")]
+	fn position(&self) -> Option<usize> {
+		self.mapping.map(mapping::Mapping::position)
+	}
+
+	/// Reports that the comment written since `start` had to be changed.
+	#[cfg(feature = "alloc")]
+	fn comment_legalized(&self, start: Option<usize>) {
+		self.warn(Warning::CommentLegalized);
+		if let (Some(manifest), Some(start), Some(end)) = (self.manifest, start, self.position()) {
+			manifest.borrow_mut().legalized_comments.push(start..end)
+		}
+	}
+
+	/// Writes an empty comment into `target` to separate text nodes. See [`RenderOptions::roundtrip_safe`].
+	#[cfg_attr(not(feature = "alloc"), allow(clippy::unused_self))]
+	fn write_text_boundary(&self, target: &mut impl Write) -> fmt::Result {
+		#[cfg(feature = "alloc")]
+		let start = self.position();
+		target.write_str("<!---->")?;
+		#[cfg(feature = "alloc")]
+		if let (Some(manifest), Some(start), Some(end)) = (self.manifest, start, self.position()) {
+			manifest.borrow_mut().boundaries.push(start..end)
+		}
+		Ok(())
+	}
+
+	/// Calls the [`render_fragment_with_visitor`] callback with `vdom`, if there is one.
+	fn visit<'n>(&self, vdom: &'n Node<'n, S>) -> Result<(), Error<'n, S>> {
+		match self.visitor {
+			Some(visitor) => (visitor.borrow_mut())(vdom),
+			None => Ok(()),
+		}
+	}
+
+	/// Counts another rendering of `vdom`, erroring if this exceeds the [`RenderOptions::repeat_limit`].
+	#[cfg_attr(
+		not(feature = "alloc"),
+		allow(clippy::unused_self, clippy::unnecessary_wraps)
+	)]
+	fn count_repeat<'n>(&self, vdom: &'n Node<'n, S>) -> Result<(), Error<'n, S>> {
+		#[cfg(feature = "alloc")]
+		if let Some(repeat_limit) = self.options.repeat_limit {
+			if let Some(key) = RepeatKey::of(vdom) {
+				let mut repeats = self.repeats.borrow_mut();
+				let count = repeats.entry(key).or_insert(0);
+				*count += 1;
+				if *count > repeat_limit {
+					return Err(Error(ErrorKind::RepeatLimitExceeded {
+						node: ByAddress(vdom),
+						repeat_limit,
+					}));
+				}
+			}
+		}
+		#[cfg(not(feature = "alloc"))]
+		let _ = vdom;
+		Ok(())
+	}
+
+	/// Records `node` about to be rendered at `depth`, if [`RenderStats`] are collected.
+	fn record(&self, node: &Node<S>, depth: Depth) {
+		if let Some(stats) = self.stats {
+			let mut current = stats.get();
+			current.node_count += 1;
+			if !is_transparent(node) {
+				current.max_depth = current
+					.max_depth
+					.max(self.options.depth_limit - depth.remaining + 1);
+			}
+			match node {
+				Node::Comment { .. } => current.comments += 1,
+				Node::HtmlElement { .. } => current.html_elements += 1,
+				Node::MathMlElement { .. } => current.math_ml_elements += 1,
+				Node::SvgElement { .. } => current.svg_elements += 1,
+				Node::Memoized { .. } => current.memoized += 1,
+				Node::Multi(_) => current.multi += 1,
+				Node::Keyed(_) => current.keyed += 1,
+				Node::Text { .. } => current.text_nodes += 1,
+				// Not rendered.
+				Node::RemnantSite(_) => (),
+			}
+			stats.set(current);
+		}
+	}
+}
+
+/// The namespace an element is created in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Namespace {
+	Html,
+	Svg,
+	MathMl,
+}
+
+impl Namespace {
+	/// See <https://infra.spec.whatwg.org/#namespaces>.
+	fn uri(self) -> &'static str {
+		match self {
+			Namespace::Html => "http://www.w3.org/1999/xhtml",
+			Namespace::Svg => "http://www.w3.org/2000/svg",
+			Namespace::MathMl => "http://www.w3.org/1998/Math/MathML",
+		}
+	}
+}
+
+/// The namespace context a [`Node`] is rendered in, as set up by its closest ancestor element.
+///
+/// Elements only switch namespaces where an HTML parser would:
 ///
-/// Iff `vdom` is found to represent invalid HTML.
+/// - [`Node::SvgElement`]s and [`Node::MathMlElement`]s always start their respective namespace.
+/// - [`Node::HtmlElement`]s are in the HTML namespace at the top level, inside other HTML elements and directly inside HTML integration points.
+///   Elsewhere, they inherit the (foreign) namespace of their parent element.
 ///
-/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
-pub fn render_document<'a, S: ThreadSafety>(
+/// See <https://html.spec.whatwg.org/multipage/parsing.html#tree-construction>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Scope {
+	/// The namespace of the parent element, or [`Namespace::Html`] at the top level.
+	parent_namespace: Namespace,
+	/// Whether the parent element is an [***HTML integration point***](https://html.spec.whatwg.org/multipage/parsing.html#html-integration-point),
+	/// like SVG `foreignObject`.
+	html_integration_point: bool,
+	/// Whether there is no parent element at all.
+	top_level: bool,
+	/// Where in an HTML document this is. Only tracked for [`RenderOptions::strict_document_structure`].
+	section: DocumentSection,
+}
+
+/// A part of an HTML document with restricted content. See [`RenderOptions::strict_document_structure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentSection {
+	/// Anywhere else.
+	Other,
+	/// Directly inside `head`.
+	Head,
+	/// Anywhere inside `body`.
+	Body,
+}
+
+impl Scope {
+	const TOP_LEVEL: Self = Self {
+		parent_namespace: Namespace::Html,
+		html_integration_point: false,
+		top_level: true,
+		section: DocumentSection::Other,
+	};
+
+	/// The namespace an element represented by `vdom` is created in.
+	fn namespace_of<S: ThreadSafety>(self, vdom: &Node<S>) -> Namespace {
+		match vdom {
+			Node::SvgElement { .. } => Namespace::Svg,
+			Node::MathMlElement { .. } => Namespace::MathMl,
+			_ if self.html_integration_point => Namespace::Html,
+			_ => self.parent_namespace,
+		}
+	}
+
+	/// The [`Scope`] of the content of an element named `name` with `attributes` in `namespace`, where the element itself is in `self`.
+	fn enter(self, namespace: Namespace, name: &str, attributes: &[Attribute]) -> Self {
+		Self {
+			parent_namespace: namespace,
+			html_integration_point: namespaces::is_html_integration_point(
+				namespace, name, attributes,
+			),
+			top_level: false,
+			section: match namespace {
+				Namespace::Html if name.eq_ignore_ascii_case("head") => DocumentSection::Head,
+				Namespace::Html if name.eq_ignore_ascii_case("body") => DocumentSection::Body,
+				_ if self.section == DocumentSection::Body => DocumentSection::Body,
+				_ => DocumentSection::Other,
+			},
+		}
+	}
+}
+
+fn render_node<'a, S: ThreadSafety>(
 	vdom: &'a Node<'a, S>,
 	target: &mut impl Write,
-	depth_limit: usize,
+	depth: Depth,
+	context: &Context<S>,
+	scope: Scope,
 ) -> Result<(), Error<'a, S>> {
-	if depth_limit == 0 {
-		return Err(Error(ErrorKind::DepthLimitExceeded(vdom)));
+	#[cfg(feature = "alloc")]
+	if let Some(mapping) = context.mapping {
+		let entry = mapping.enter(vdom);
+		render_node_unmapped(vdom, target, depth, context, scope)?;
+		mapping.exit(entry);
+		return Ok(());
 	}
-	write!(target, "<!DOCTYPE html>")?;
-	render_fragment(vdom, target, depth_limit)
+	render_node_unmapped(vdom, target, depth, context, scope)
 }
 
-/// Renders `vdom` into `target` as HTML fragment *without* [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype).
-///
-/// `depth_limit` is measured in [`Node`]s and must be at least `1` to not error on it.
-///
-/// # Errors
-///
-/// Iff `vdom` is found to represent invalid HTML.
-///
-/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+/// [`render_node`] without the bookkeeping for [`render_fragment_with_mapping`].
 #[allow(clippy::items_after_statements)]
 #[allow(clippy::too_many_lines)]
-pub fn render_fragment<'a, S: ThreadSafety>(
+fn render_node_unmapped<'a, S: ThreadSafety>(
 	vdom: &'a Node<'a, S>,
 	target: &mut impl Write,
-	depth_limit: usize,
+	depth: Depth,
+	context: &Context<S>,
+	scope: Scope,
 ) -> Result<(), Error<'a, S>> {
-	if depth_limit == 0 {
-		return Err(Error(ErrorKind::DepthLimitExceeded(vdom)));
-	}
+	context.enter(vdom, depth)?;
+	let options = context.options;
 	match *vdom {
 		// See <https://html.spec.whatwg.org/multipage/syntax.html#comments>.
 		Node::Comment {
 			comment,
 			dom_binding: _,
 		} => {
+			if options.ascii_only && !comment.is_ascii() {
+				return Err(Error(ErrorKind::UnescapableNonAscii(comment)));
+			}
+			let guard = options.comment_guard_char;
+			let lossless = options.comment_policy == CommentPolicy::ErrorIfLossy;
+			if options.comment_policy != CommentPolicy::Verbatim
+				&& (matches!(guard, '-' | '<' | '>')
+					|| guard.is_control()
+					|| options.ascii_only && !guard.is_ascii())
+			{
+				return Err(Error(ErrorKind::InvalidCommentGuardChar(guard)));
+			}
+
+			context.after_text.set(false);
+			#[cfg(feature = "alloc")]
+			let start = context.position();
+
 			// This is just a comment, so it shouldn't break the app.
 			target.write_str("<!--")?;
+
+			if options.comment_policy == CommentPolicy::Verbatim {
+				options.check_nul(comment)?;
+				target.write_str(comment)?;
+				target.write_str("-->")?;
+				return Ok(());
+			}
+
+			// Whether any of the comment's text was replaced.
+			#[cfg(feature = "alloc")]
+			let mut legalized = false;
+
+			// See <https://www.w3.org/TR/xml/#sec-comments>.
+			if options.xml {
+				let mut previous_dash = false;
+				for c in comment.chars() {
+					let lossy = c == '\0' || c == '-' && previous_dash;
+					if lossless && lossy {
+						return Err(Error(ErrorKind::IllegalCommentText(comment)));
+					}
+					#[cfg(feature = "alloc")]
+					{
+						legalized |= lossy;
+					}
+					if c == '\0' {
+						// Not allowed anywhere in XML.
+						target.write_char('\u{FFFD}')?;
+						previous_dash = false;
+					} else if c == '-' && previous_dash {
+						target.write_char('=')?;
+						previous_dash = false;
+					} else {
+						target.write_char(c)?;
+						previous_dash = c == '-';
+					}
+				}
+				if previous_dash {
+					target.write_char(guard)?
+				}
+				target.write_str("-->")?;
+				#[cfg(feature = "alloc")]
+				if legalized {
+					context.comment_legalized(start)
+				}
+				return Ok(());
+			}
+
 			if comment.starts_with('>') || comment.starts_with("->") {
-				target.write_char('|')?
+				target.write_char(guard)?
 			}
 
 			#[derive(Logos)]
-			enum CommentToken {
+			enum CommentToken<'a> {
 				#[token("<!--")]
 				LtBangDashDash,
 				#[token("-->")]
 				DashDashGt,
 				#[token("--!>")]
 				DashDashBangGt,
-				#[regex("(?s).", |lex| lex.slice().parse())]
-				Other(char),
+				#[token("\0")]
+				Nul,
+				#[token("<")]
+				Lt,
+				#[token("-")]
+				Dash,
+				// Matching whole runs rather than single characters also keeps multi-byte characters intact.
+				#[regex("[^<\\-\\x00]+")]
+				Verbatim(&'a str),
 				#[error]
 				Error,
 			}
 
 			for token in CommentToken::lexer(comment) {
-				let replacement = match token {
+				let lossy = matches!(
+					token,
+					CommentToken::LtBangDashDash
+						| CommentToken::DashDashGt
+						| CommentToken::DashDashBangGt
+						| CommentToken::Nul
+				);
+				if lossless && lossy {
+					return Err(Error(ErrorKind::IllegalCommentText(comment)));
+				}
+				#[cfg(feature = "alloc")]
+				{
+					legalized |= lossy;
+				}
+				target.write_str(match token {
 					CommentToken::LtBangDashDash => "<!==",
 					CommentToken::DashDashGt => "==>",
 					CommentToken::DashDashBangGt => "==!>",
-					CommentToken::Other(c) => {
-						target.write_char(c)?;
-						continue;
-					}
-					CommentToken::Error => unreachable!(),
-				};
-				target.write_str(replacement)?
+					// An HTML parser would do the same replacement. See <https://html.spec.whatwg.org/multipage/parsing.html#comment-state>.
+					CommentToken::Nul => "\u{FFFD}",
+					CommentToken::Lt => "<",
+					CommentToken::Dash => "-",
+					CommentToken::Verbatim(str) => str,
+					// Any text matches one of the other tokens.
+					CommentToken::Error => return Err(Error(ErrorKind::Internal)),
+				})?
 			}
 
 			if comment.ends_with("<!-") {
-				target.write_char('|')?
+				target.write_char(guard)?
 			}
 			target.write_str("-->")?;
+			#[cfg(feature = "alloc")]
+			if legalized {
+				context.comment_legalized(start)
+			}
 		}
 
 		// See <https://html.spec.whatwg.org/multipage/syntax.html#elements-2>.
@@ -144,302 +2866,652 @@ pub fn render_fragment<'a, S: ThreadSafety>(
 		| Node::SvgElement {
 			element,
 			dom_binding: _,
-		} => {
-			let &Element {
+		} => match start_element(vdom, element, target, context, scope)? {
+			ElementStart::Skipped => (),
+			ElementStart::ContentOnly => {
+				context.check_transparent_depth(vdom, depth)?;
+				render_node(
+					&element.content,
+					target,
+					depth.transparent_content(),
+					context,
+					scope,
+				)?
+			}
+			ElementStart::Tag {
 				name,
-				creation_options,
-				attributes,
-				ref content,
-				event_bindings: _,
-			} = element;
-
-			/// See <https://html.spec.whatwg.org/multipage/syntax.html#syntax-attribute-name>.
-			fn validate_attribute_name<S: ThreadSafety>(name: &str) -> Result<&str, Error<S>> {
-				if name == "is" {
-					return Err(Error(ErrorKind::ReservedAttributeName(name)));
-				}
+				kind,
+				content_scope,
+			} => {
+				render_element_content(element, name, kind, content_scope, target, depth, context)?;
+				write_end_tag(name, kind, context, target)?
+			}
+		},
 
-				for c in name.chars() {
-					match c {
-						// <https://infra.spec.whatwg.org/#control>
-						// <https://infra.spec.whatwg.org/#c0-control>
-						'\0'..='\u{1F}' | '\u{7F}'..='\u{9F}' |
-
-						// <https://html.spec.whatwg.org/multipage/syntax.html#syntax-attribute-name>
-						' ' | '"' | '\'' | '>' | '/' | '=' |
-
-						// <https://infra.spec.whatwg.org/#noncharacter>
-						'\u{FDD0}'..='\u{FDEF}' => {
-							return Err(Error(ErrorKind::InvalidAttributeName(name)))
-						}
-						c if ((c as u32) & 0xffff >= 0xfffe) && (c as u32) >> 16 <= 0x10 => {
-							return Err(Error(ErrorKind::InvalidAttributeName(name)))
-						}
-						_ => (),
-					}
-				}
-				Ok(name)
-			}
-
-			let kind = ElementKind::detect(name)
-				.map_err(|name| Error(ErrorKind::InvalidElementName(name)))?;
-
-			//TODO: Validate distinction between HTML and SVG elements.
-
-			// Opening tag:
-			write!(target, "<{}", name)?;
-
-			fn write_attribute<'a, S: ThreadSafety>(
-				target: &mut impl Write,
-				validated_attribute_name: &str,
-				value: &str,
-			) -> Result<(), Error<'a, S>> {
-				write!(target, " {}", validated_attribute_name)?;
-
-				let value_mode = AttributeValueMode::detect(value);
-				target.write_str(match value_mode {
-					AttributeValueMode::Empty => return Ok(()),
-					AttributeValueMode::Unquoted => "=",
-					AttributeValueMode::SingleQuoted => "='",
-					AttributeValueMode::DoubleQuoted => "\"",
-				})?;
-				for c in value.chars() {
-					match c {
-						'&' => target.write_str("&amp;"),
-						'"' if value_mode == AttributeValueMode::DoubleQuoted => {
-							target.write_str("&quot;")
-						}
-						c => target.write_char(c),
-					}?
-				}
-				match value_mode {
-					AttributeValueMode::Empty => unreachable!(),
-					AttributeValueMode::Unquoted => (),
-					AttributeValueMode::SingleQuoted => target.write_char('\'')?,
-					AttributeValueMode::DoubleQuoted => target.write_char('"')?,
+		Node::Memoized { state_key, content } => {
+			#[cfg(feature = "alloc")]
+			if let Some(cache) = context.memo_cache {
+				if let Some(rendered) = cache.borrow().get(state_key, scope) {
+					target.write_str(rendered)?;
+					return Ok(());
 				}
-				Ok(())
+				let mut rendered = alloc::string::String::new();
+				render_node(
+					content,
+					&mut rendered,
+					depth.transparent_content(),
+					context,
+					scope,
+				)?;
+				target.write_str(&rendered)?;
+				cache.borrow_mut().insert(state_key, scope, rendered);
+				return Ok(());
 			}
-			if let Some(is) = creation_options.is() {
-				write_attribute(target, "is", is)?
-			}
-			for &Attribute {
-				name: attribute_name,
-				value,
-			} in attributes
-			{
-				write_attribute(target, validate_attribute_name(attribute_name)?, value)?
+			#[cfg(not(feature = "alloc"))]
+			let _ = state_key;
+
+			render_node(content, target, depth.transparent_content(), context, scope)?
+		}
+
+		Node::Multi(nodes) => {
+			for node in nodes {
+				render_node(node, target, depth.transparent_content(), context, scope)?;
 			}
-			if kind == ElementKind::ForeignSelfClosing {
-				// Note the space! This is required in case the last attribute was unquoted.
-				target.write_str(" />")?
-			} else {
-				target.write_char('>')?;
+		}
+		Node::Keyed(reorderable_fragments) => {
+			check_keys(vdom, reorderable_fragments, context)?;
+			for fragment in keyed_in_order(reorderable_fragments, options) {
+				render_node(
+					&fragment.content,
+					target,
+					depth.transparent_content(),
+					context,
+					scope,
+				)?
 			}
+		}
 
-			// See <https://html.spec.whatwg.org/multipage/syntax.html#element-restrictions>.
-			// Just adding the newline here unconditionally isn't "perfect", but it's most likely faster than checking if it's necessary.
-			match kind {
-				ElementKind::EscapableRawTextTextarea | ElementKind::NormalPre => {
-					target.write_char('\n')?
-				}
-				_ => (),
+		Node::Text {
+			text,
+			dom_binding: _,
+		} => {
+			options.check_nul(text)?;
+			if options.roundtrip_safe && !text.is_empty() && context.after_text.replace(true) {
+				context.write_text_boundary(target)?
 			}
+			escaping::write_text(
+				text,
+				&mut CharacterReferences::new(target, options),
+				options.xml || options.browser_canonical,
+				options.smart_ampersands,
+			)?
+		}
 
-			// Content:
-			match kind {
-				ElementKind::Void | ElementKind::ForeignSelfClosing => {
-					if !content.dom_empty() {
-						return Err(Error(ErrorKind::NonEmptyVoidElementContent(content)));
-					}
-				}
-				ElementKind::Template
-				| ElementKind::Normal
-				| ElementKind::NormalPre
-				| ElementKind::ForeignNotSelfClosing => render_fragment(content, target, depth_limit - 1)?,
-				ElementKind::RawText => render_raw_text(content, target, name, depth_limit - 1)?,
+		//TODO
+		Node::RemnantSite(_) => return Err(Error(ErrorKind::UnsupportedNode(ByAddress(vdom)))),
+	}
+	Ok(())
+}
 
-				ElementKind::EscapableRawText | ElementKind::EscapableRawTextTextarea => {
-					render_escapable_raw_text(content, target, depth_limit - 1)?
-				}
-				ElementKind::PotentialCustomElementNameCharacter
-				| ElementKind::Dash
-				| ElementKind::Invalid => {
-					unreachable!()
-				}
-			}
+/// What [`start_element`] did.
+enum ElementStart<'a> {
+	/// Nothing was written. The element's content should be skipped too.
+	Skipped,
+	/// Nothing was written. The element's content should be rendered in its place, in the same [`Scope`].
+	ContentOnly,
+	/// The start tag was written.
+	Tag {
+		name: &'a str,
+		kind: ElementKind,
+		/// The [`Scope`] of the element's content.
+		content_scope: Scope,
+	},
+}
+
+/// Validates the element `vdom` and writes its start tag (and any newline that must follow it) into `target`.
+///
+/// `scope` is the [`Scope`] `vdom` appears in.
+#[allow(clippy::too_many_lines)]
+fn start_element<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	element: &'a Element<'a, S>,
+	target: &mut impl Write,
+	context: &Context<S>,
+	scope: Scope,
+) -> Result<ElementStart<'a>, Error<'a, S>> {
+	let options = context.options;
+	let &Element {
+		name,
+		creation_options: _,
+		attributes,
+		ref content,
+		event_bindings: _,
+	} = element;
+
+	match options.should_render {
+		Some(ElementFilter(should_render)) if !should_render(element.align_ref()) => {
+			return Ok(ElementStart::Skipped)
+		}
+		_ => (),
+	}
+
+	#[cfg(feature = "alloc")]
+	if !element.event_bindings.is_empty() {
+		context.warn(Warning::EventBindingsIgnored)
+	}
+
+	if options.fragment_element_name == Some(name) {
+		if !attributes.is_empty() || element.creation_options.is().is_some() {
+			return Err(Error(ErrorKind::FragmentWithAttributes(ByAddress(vdom))));
+		}
+		return Ok(ElementStart::ContentOnly);
+	}
+
+	let name = match options.map_element_name {
+		None => name,
+		Some(ElementNameMapper(map)) => match map(name) {
+			Some(name) => name,
+			None => return Ok(ElementStart::ContentOnly),
+		},
+	};
+
+	let mut kind = ElementKind::detect(name)
+		.map_err(|reason| Error(ErrorKind::InvalidElementName(name, reason)))?;
+	if options.ascii_only && !name.is_ascii() {
+		return Err(Error(ErrorKind::UnescapableNonAscii(name)));
+	}
+	let namespace = scope.namespace_of(vdom);
+	let foreign = namespace != Namespace::Html;
+	let name = if options.normalize_svg_case && namespace == Namespace::Svg {
+		svg_case::element_name(name)
+	} else {
+		name
+	};
+	if !foreign
+		&& options
+			.extra_void_elements
+			.iter()
+			.any(|void| void.eq_ignore_ascii_case(name))
+	{
+		kind = ElementKind::Void;
+	} else if !foreign
+		&& options
+			.extra_raw_text_elements
+			.iter()
+			.any(|raw_text| raw_text.eq_ignore_ascii_case(name))
+	{
+		kind = ElementKind::RawText;
+	}
+	if foreign {
+		// Foreign elements don't have any of the special content models of HTML elements.
+		// Any of them may be self-closing, but only if empty.
+		kind = if content.dom_empty() && !options.browser_canonical {
+			ElementKind::ForeignSelfClosing
+		} else {
+			ElementKind::ForeignNotSelfClosing
+		};
+	}
 
-			// Closing tag:
-			match kind {
-				ElementKind::Void | ElementKind::ForeignSelfClosing => (),
-				ElementKind::Template
-				| ElementKind::RawText
+	if options.xml
+		&& matches!(
+			kind,
+			ElementKind::RawText
 				| ElementKind::EscapableRawText
 				| ElementKind::EscapableRawTextTextarea
-				| ElementKind::ForeignNotSelfClosing
-				| ElementKind::Normal
-				| ElementKind::NormalPre => write!(target, "</{}>", name)?,
-				ElementKind::PotentialCustomElementNameCharacter
-				| ElementKind::Dash
-				| ElementKind::Invalid => {
-					unreachable!()
-				}
+				| ElementKind::NormalPre
+		) {
+		// XML has neither special text content models nor newline stripping.
+		kind = ElementKind::Normal;
+	}
+
+	if options.strict_namespaces {
+		match namespaces::exclusive_namespace(name) {
+			Some(exclusive) if exclusive != namespace => {
+				return Err(Error(ErrorKind::ElementInWrongNamespace(ByAddress(vdom))))
 			}
+			_ => (),
 		}
+	}
 
-		Node::Memoized {
-			state_key: _,
-			content,
-		} => render_fragment(content, target, depth_limit - 1)?,
+	let lacks = |attribute_name: &str| {
+		!attributes
+			.iter()
+			.any(|attribute| attribute.name.eq_ignore_ascii_case(attribute_name))
+	};
+	let missing_lang = scope.top_level
+		&& namespace == Namespace::Html
+		&& name.eq_ignore_ascii_case("html")
+		&& lacks("lang");
+	if missing_lang && options.require_html_lang {
+		return Err(Error(ErrorKind::MissingLangAttribute(ByAddress(vdom))));
+	}
+	let missing_alt =
+		namespace == Namespace::Html && name.eq_ignore_ascii_case("img") && lacks("alt");
+	if missing_alt && options.require_img_alt {
+		return Err(Error(ErrorKind::MissingAltAttribute(ByAddress(vdom))));
+	}
+	#[cfg(feature = "alloc")]
+	{
+		if missing_lang {
+			context.warn(Warning::MissingLangAttribute)
+		}
+		if missing_alt {
+			context.warn(Warning::MissingAltAttribute)
+		}
+	}
+	if options.strict_document_structure && namespace == Namespace::Html {
+		check_document_structure(vdom, name, attributes, scope)?;
+	}
 
-		Node::Multi(nodes) => {
-			for node in nodes {
-				render_fragment(node, target, depth_limit - 1)?;
+	context.after_text.set(false);
+	write_open_tag(element, name, kind, namespace, scope, options, target)?;
+
+	// See <https://html.spec.whatwg.org/multipage/syntax.html#element-restrictions>.
+	// Just adding the newline here unconditionally isn't "perfect", but it's most likely faster than checking if it's necessary.
+	// With `browser_canonical`, the newline is only added where the parser would otherwise drop one from the content.
+	match kind {
+		ElementKind::EscapableRawTextTextarea | ElementKind::NormalPre
+			if !options.browser_canonical
+				|| starts_with_newline(content, 0, options).unwrap_or(false) =>
+		{
+			target.write_char('\n')?
+		}
+		_ => (),
+	}
+
+	Ok(ElementStart::Tag {
+		name,
+		kind,
+		content_scope: scope.enter(namespace, name, attributes),
+	})
+}
+
+/// Checks that the HTML element `vdom` named `name` isn't out of place in `scope`. See [`RenderOptions::strict_document_structure`].
+fn check_document_structure<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	name: &str,
+	attributes: &[Attribute],
+	scope: Scope,
+) -> Result<(), Error<'a, S>> {
+	let is_any_of = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(name));
+	let expected_section = match scope.section {
+		DocumentSection::Head => {
+			let head_content = [
+				"base", "link", "meta", "noscript", "script", "style", "template", "title",
+			];
+			if is_any_of(&head_content) {
+				return Ok(());
 			}
+			"body"
 		}
-		Node::Keyed(reorderable_fragments) => {
-			for fragment in reorderable_fragments {
-				render_fragment(&fragment.content, target, depth_limit - 1)?
+		DocumentSection::Body => {
+			// `meta` elements with `itemprop` are microdata, which may appear in `body`.
+			let microdata = attributes
+				.iter()
+				.any(|attribute| attribute.name.eq_ignore_ascii_case("itemprop"));
+			let head_only = is_any_of(&["base", "head", "title"])
+				|| name.eq_ignore_ascii_case("meta") && !microdata;
+			if !head_only {
+				return Ok(());
 			}
+			"head"
 		}
+		DocumentSection::Other => return Ok(()),
+	};
+	Err(Error(ErrorKind::MisplacedElement {
+		element: ByAddress(vdom),
+		expected_section,
+	}))
+}
 
-		Node::Text {
-			text,
-			dom_binding: _,
-		} => {
-			//FIXME: I haven't found the actual reference on this yet.
+/// Renders the `content` of an element that was started by [`start_element`] into `target`.
+///
+/// `depth` is that of the element itself.
+fn render_element_content<'a, S: ThreadSafety>(
+	element: &'a Element<'a, S>,
+	name: &'a str,
+	kind: ElementKind,
+	content_scope: Scope,
+	target: &mut impl Write,
+	depth: Depth,
+	context: &Context<S>,
+) -> Result<(), Error<'a, S>> {
+	let Element { content, .. } = element;
+	match kind {
+		ElementKind::Void => {
+			if !renders_empty(content) {
+				return Err(Error(ErrorKind::NonEmptyVoidElementContent(ByAddress(
+					content,
+				))));
+			}
+		}
+		// Only chosen for empty content, so there's nothing to render or validate.
+		ElementKind::ForeignSelfClosing => (),
+		ElementKind::Template
+		| ElementKind::Normal
+		| ElementKind::NormalPre
+		| ElementKind::ForeignNotSelfClosing => render_node(
+			content,
+			target,
+			depth.element_content(),
+			context,
+			content_scope,
+		)?,
+		ElementKind::RawText => {
+			render_raw_text(content, target, name, depth.element_content(), context)?
+		}
 
-			#[derive(Logos)]
-			enum PlainTextToken<'a> {
-				/// This could close this element or start a new one.
-				#[token("<")]
-				Lt,
-				/// See <https://html.spec.whatwg.org/multipage/syntax.html#character-references>.
-				///
-				/// This could be an ambiguous ampersand or part something that would be parsed as character reference, so it's escaped unconditionally.
-				#[token("&")]
-				Ampersand,
-				#[regex("[^<&]+")]
-				SafeVerbatim(&'a str),
-				#[error]
-				Error,
+		ElementKind::EscapableRawText | ElementKind::EscapableRawTextTextarea => {
+			render_escapable_raw_text(content, target, name, depth.element_content(), context)?
+		}
+		// Never returned by `ElementKind::detect`.
+		ElementKind::PotentialCustomElementNameCharacter
+		| ElementKind::Dash
+		| ElementKind::Invalid => return Err(Error(ErrorKind::Internal)),
+	}
+
+	Ok(())
+}
+
+/// Writes the end tag of an element that was started by [`start_element`] into `target`, if it has one.
+fn write_end_tag<'a, S: ThreadSafety>(
+	name: &str,
+	kind: ElementKind,
+	context: &Context<S>,
+	target: &mut impl Write,
+) -> Result<(), Error<'a, S>> {
+	let options = context.options;
+	context.after_text.set(false);
+	match kind {
+		ElementKind::Void | ElementKind::ForeignSelfClosing => (),
+		ElementKind::Template
+		| ElementKind::RawText
+		| ElementKind::EscapableRawText
+		| ElementKind::EscapableRawTextTextarea
+		| ElementKind::ForeignNotSelfClosing
+		| ElementKind::Normal
+		| ElementKind::NormalPre => {
+			target.write_str("</")?;
+			write_name(
+				target,
+				name,
+				options.browser_canonical && kind != ElementKind::ForeignNotSelfClosing,
+			)?;
+			target.write_char('>')?
+		}
+		// Never returned by `ElementKind::detect`.
+		ElementKind::PotentialCustomElementNameCharacter
+		| ElementKind::Dash
+		| ElementKind::Invalid => return Err(Error(ErrorKind::Internal)),
+	}
+	Ok(())
+}
+
+/// Identifies what a [`Node`] borrows, for [`RenderOptions::repeat_limit`].
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum RepeatKey {
+	Element(usize),
+	Memoized(usize),
+	Multi(usize),
+	Keyed(usize),
+}
+
+#[cfg(feature = "alloc")]
+impl RepeatKey {
+	/// The key `vdom` is counted under, if at all.
+	fn of<S: ThreadSafety>(vdom: &Node<S>) -> Option<Self> {
+		match vdom {
+			Node::HtmlElement { element, .. }
+			| Node::MathMlElement { element, .. }
+			| Node::SvgElement { element, .. } => {
+				Some(Self::Element(core::ptr::addr_of!(**element) as usize))
 			}
+			Node::Memoized { content, .. }
+				if matches!(content, Node::Text { .. } | Node::Comment { .. }) =>
+			{
+				Some(Self::Memoized(core::ptr::addr_of!(**content) as usize))
+			}
+			Node::Multi(nodes) if !nodes.is_empty() => Some(Self::Multi(nodes.as_ptr() as usize)),
+			Node::Keyed(reorderable_fragments) if !reorderable_fragments.is_empty() => {
+				Some(Self::Keyed(reorderable_fragments.as_ptr() as usize))
+			}
+			_ => None,
+		}
+	}
+}
 
-			for token in PlainTextToken::lexer(text) {
-				match token {
-					PlainTextToken::Lt => target.write_str("&lt;"),
-					PlainTextToken::Ampersand => target.write_str("&amp;"),
-					PlainTextToken::SafeVerbatim(str) => target.write_str(str),
-					PlainTextToken::Error => unreachable!(),
-				}?
+/// Whether `vdom` only groups its content, which is then at the same depth, so that it doesn't count against [`RenderOptions::depth_limit`] itself.
+const fn is_transparent<S: ThreadSafety>(vdom: &Node<S>) -> bool {
+	matches!(
+		vdom,
+		Node::Memoized { .. } | Node::Multi(_) | Node::Keyed(_)
+	)
+}
+
+/// How deep a [`Node`] is rendered, see [`RenderOptions::depth_limit`] and [`RenderOptions::transparent_depth_limit`].
+#[derive(Clone, Copy)]
+struct Depth {
+	/// How many more element nesting levels are allowed.
+	remaining: usize,
+	/// How many transparent [`Node`]s and elements rendered as content only are around this one.
+	transparent: usize,
+}
+
+impl Depth {
+	/// The depth of the outermost [`Node`]s, with `depth_limit` element nesting levels remaining.
+	const fn top_level(depth_limit: usize) -> Self {
+		Self {
+			remaining: depth_limit,
+			transparent: 0,
+		}
+	}
+
+	/// The depth of an element's content. Must only be called once the element was checked against the depth limit.
+	const fn element_content(self) -> Self {
+		Self {
+			remaining: self.remaining - 1,
+			..self
+		}
+	}
+
+	/// The depth of the content of a transparent [`Node`] or an element rendered as content only.
+	const fn transparent_content(self) -> Self {
+		Self {
+			transparent: self.transparent + 1,
+			..self
+		}
+	}
+}
+
+/// Whether `vdom` is [`Node::dom_empty`] or consists only of empty [`Node::Text`]s, so that it doesn't render to anything.
+///
+/// Void elements may have such content, since the parser wouldn't create any (text) nodes for it either.
+/// [`Node::Comment`]s are rendered even if empty, so they do count as content.
+fn renders_empty<S: ThreadSafety>(vdom: &Node<S>) -> bool {
+	match vdom {
+		Node::Text { text, .. } => text.is_empty(),
+		Node::Memoized { content, .. } => renders_empty(content),
+		Node::Multi(nodes) => nodes.iter().all(renders_empty),
+		Node::Keyed(reorderable_fragments) => reorderable_fragments
+			.iter()
+			.all(|fragment| renders_empty(&fragment.content)),
+		_ => vdom.dom_empty(),
+	}
+}
+
+/// Whether the text rendered for `vdom` starts with a line feed, or [`None`] if it renders no text or other nodes at all.
+///
+/// Elements count as not starting with one, unless [`RenderOptions`] may render them as content only or skip them,
+/// in which case they conservatively do. The same goes for nodes nested more than [`RenderOptions::transparent_depth_limit`] deep.
+fn starts_with_newline<S: ThreadSafety>(
+	vdom: &Node<S>,
+	transparent_depth: usize,
+	options: &RenderOptions,
+) -> Option<bool> {
+	if transparent_depth >= options.transparent_depth_limit {
+		return Some(true);
+	}
+	match vdom {
+		Node::Text { text, .. } => text.chars().next().map(|c| c == '\n'),
+		Node::Memoized { content, .. } => {
+			starts_with_newline(content, transparent_depth + 1, options)
+		}
+		Node::Multi(nodes) => nodes
+			.iter()
+			.find_map(|node| starts_with_newline(node, transparent_depth + 1, options)),
+		Node::Keyed(reorderable_fragments) => keyed_in_order(reorderable_fragments, options)
+			.find_map(|fragment| {
+				starts_with_newline(&fragment.content, transparent_depth + 1, options)
+			}),
+		Node::HtmlElement { .. } | Node::MathMlElement { .. } | Node::SvgElement { .. } => Some(
+			options.should_render.is_some()
+				|| options.fragment_element_name.is_some()
+				|| options.map_element_name.is_some(),
+		),
+		_ => Some(false),
+	}
+}
+
+/// Makes sure the `dom_key`s of `keyed` (a [`Node::Keyed`]'s content) are unique, if [`RenderOptions::strict_keys`] is enabled.
+#[cfg_attr(
+	not(feature = "alloc"),
+	allow(unused_variables, clippy::unnecessary_wraps)
+)]
+fn check_keys<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	keyed: &'a [ReorderableFragment<'a, S>],
+	context: &Context<S>,
+) -> Result<(), Error<'a, S>> {
+	#[cfg(feature = "alloc")]
+	if context.options.strict_keys {
+		let mut seen = alloc::collections::BTreeSet::new();
+		for fragment in keyed {
+			if !seen.insert(fragment.dom_key) {
+				return Err(Error(ErrorKind::DuplicateKey {
+					node: ByAddress(vdom),
+					dom_key: fragment.dom_key,
+				}));
 			}
 		}
+	}
+	Ok(())
+}
 
-		Node::RemnantSite(_) => {
-			unreachable!("`RemnantSite` can't be constructed in this version of `lignin`.")
-		} //TODO
+/// Iterates over `fragments` in their given order or, with [`RenderOptions::sort_keyed`], stably sorted by [`ReorderableFragment::dom_key`].
+fn keyed_in_order<'b, 'a, S: ThreadSafety>(
+	fragments: &'b [ReorderableFragment<'a, S>],
+	options: &RenderOptions,
+) -> impl DoubleEndedIterator<Item = &'b ReorderableFragment<'a, S>> {
+	#[cfg(feature = "alloc")]
+	let sorted = options.sort_keyed.then(|| {
+		let mut sorted: alloc::vec::Vec<_> = fragments.iter().collect();
+		sorted.sort_by_key(|fragment| fragment.dom_key);
+		sorted
+	});
+	#[cfg(not(feature = "alloc"))]
+	let sorted: Option<[&ReorderableFragment<'a, S>; 0]> = {
+		let _ = options;
+		None
 	};
-	Ok(())
+
+	let given = if sorted.is_some() { &[][..] } else { fragments };
+	sorted.into_iter().flatten().chain(given)
 }
 
-#[allow(clippy::items_after_statements)]
-#[allow(clippy::too_many_lines)]
+/// Renders `vdom` as the complete raw text content of an element named `element_name`.
+///
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#elements-2> and <https://html.spec.whatwg.org/multipage/syntax.html#cdata-rcdata-restrictions>.
 fn render_raw_text<'a, S: ThreadSafety>(
 	vdom: &'a Node<'a, S>,
 	target: &mut impl Write,
 	element_name: &'a str,
-	depth_limit: usize,
+	depth: Depth,
+	context: &Context<S>,
 ) -> Result<(), Error<'a, S>> {
-	if depth_limit == 0 {
-		return Err(Error(ErrorKind::DepthLimitExceeded(vdom)));
+	let mut tokenizer = RawTextTokenizer::new(element_name);
+	render_raw_text_nodes(vdom, target, &mut tokenizer, depth, context)?;
+	if tokenizer.end_tag_closes() {
+		Ok(())
+	} else {
+		Err(Error(ErrorKind::UnclosedScriptDoubleEscape(element_name)))
 	}
+}
+
+/// Renders `vdom` as part of raw text content, continuing with the state of `tokenizer`.
+fn render_raw_text_nodes<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	tokenizer: &mut RawTextTokenizer<'a>,
+	depth: Depth,
+	context: &Context<S>,
+) -> Result<(), Error<'a, S>> {
+	context.enter(vdom, depth)?;
 
 	match vdom {
 		Node::Comment { .. }
 		| Node::HtmlElement { .. }
 		| Node::MathMlElement { .. }
-		| Node::SvgElement { .. } => return Err(Error(ErrorKind::NonTextDomNodeInRawTextPosition(vdom))),
+		| Node::SvgElement { .. } => {
+			return Err(Error(ErrorKind::NonTextDomNodeInRawTextPosition {
+				element: tokenizer.element_name(),
+				node: ByAddress(vdom),
+			}))
+		}
 		Node::Memoized {
 			state_key: _,
 			content,
-		} => render_raw_text(content, target, element_name, depth_limit - 1)?,
+		} => render_raw_text_nodes(
+			content,
+			target,
+			tokenizer,
+			depth.transparent_content(),
+			context,
+		)?,
 		Node::Multi(nodes) => {
 			for node in *nodes {
-				render_raw_text(node, target, element_name, depth_limit - 1)?
+				render_raw_text_nodes(
+					node,
+					target,
+					tokenizer,
+					depth.transparent_content(),
+					context,
+				)?
 			}
 		}
 		Node::Keyed(pairs) => {
-			for pair in *pairs {
-				render_raw_text(&pair.content, target, element_name, depth_limit - 1)?
+			check_keys(vdom, pairs, context)?;
+			for pair in keyed_in_order(pairs, context.options) {
+				render_raw_text_nodes(
+					&pair.content,
+					target,
+					tokenizer,
+					depth.transparent_content(),
+					context,
+				)?
 			}
 		}
 		Node::Text {
 			text,
 			dom_binding: _,
 		} => {
-			/// See <https://html.spec.whatwg.org/multipage/syntax.html#elements-2> and <https://html.spec.whatwg.org/multipage/syntax.html#cdata-rcdata-restrictions>.
-			///
-			/// Unlike with escapable raw text, it's not possible to run escape the sequence (of course), so the error has to be a lot more precise.
-			#[derive(Logos)]
-			#[logos(extras = &'s mut RawTextExtras<'s>)]
-			enum RawTextToken<'a> {
-				#[token("<")]
-				Lt,
-				#[token("</", check_for_error)]
-				LtSolidus(Result<(), Range<usize>>),
-				#[regex("[^<]+")]
-				SafeVerbatim(&'a str),
-				#[error]
-				Error,
-			}
-
-			struct RawTextExtras<'a> {
-				pub element_name: &'a str,
-				pub text: &'a str,
-			}
-
-			fn check_for_error<'a>(
-				lex: &mut Lexer<'a, RawTextToken<'a>>,
-			) -> Result<(), Range<usize>> {
-				let start = lex.span().start;
-				let end = lex.span().end;
-				let extras = &mut *lex.extras;
-
-				let name_range = end..end + extras.element_name.len();
-				if name_range.end + 1 > extras.text.len() {
-					return Ok(());
-				}
-
-				if !extras.text[name_range.clone()].eq_ignore_ascii_case(extras.element_name) {
-					return Ok(());
-				}
-
-				// It is more clear to say we're slicing one past the name.
-				#[allow(clippy::range_plus_one)]
-				match extras.text.as_bytes()[name_range.end] {
-					b'\t' | b'\n' | 0xC /* FORM FEED */ | b'\r' | b' ' | b'>' | b'/' => {
-						Err(start..name_range.end+1)
-					}
-					_ => Ok(())
-				}
+			if context.options.ascii_only && !text.is_ascii() {
+				return Err(Error(ErrorKind::UnescapableNonAscii(text)));
 			}
+			context.options.check_nul(text)?;
 
-			let mut extras = RawTextExtras { element_name, text };
-			for token in RawTextToken::lexer_with_extras(text, &mut extras) {
-				match token {
-					RawTextToken::Lt => target.write_char('<'),
-					RawTextToken::LtSolidus(Ok(())) => target.write_str("</"),
-					RawTextToken::LtSolidus(Err(invalid_range)) => {
-						return Err(Error(ErrorKind::ElementClosedInRawText(
-							&text[invalid_range],
-						)))
-					}
-					RawTextToken::SafeVerbatim(str) => target.write_str(str),
-					RawTextToken::Error => unreachable!(),
-				}?
-			}
+			// Unlike with escapable raw text, it's not possible to escape the sequence (of course), so the error has to be a lot more precise.
+			tokenizer.feed(text).map_err(|invalid_range| {
+				Error(ErrorKind::ElementClosedInRawText {
+					element: tokenizer.element_name(),
+					offset: invalid_range.start,
+					text: &text[invalid_range],
+				})
+			})?;
+			target.write_str(text)?
 		}
-		Node::RemnantSite(_) => todo!("`RemnantSite`"),
+		//TODO
+		Node::RemnantSite(_) => return Err(Error(ErrorKind::UnsupportedNode(ByAddress(vdom)))),
 	}
 	Ok(())
 }
@@ -449,32 +3521,52 @@ fn render_raw_text<'a, S: ThreadSafety>(
 fn render_escapable_raw_text<'a, S: ThreadSafety>(
 	vdom: &'a Node<'a, S>,
 	target: &mut impl Write,
-	depth_limit: usize,
+	element_name: &'a str,
+	depth: Depth,
+	context: &Context<S>,
 ) -> Result<(), Error<'a, S>> {
-	if depth_limit == 0 {
-		return Err(Error(ErrorKind::DepthLimitExceeded(vdom)));
-	}
+	context.enter(vdom, depth)?;
 	match vdom {
 		Node::Comment { .. }
 		| Node::HtmlElement { .. }
 		| Node::MathMlElement { .. }
 		| Node::SvgElement { .. } => {
-			return Err(Error(ErrorKind::NonTextDomNodeInEscapableRawTextPosition(
-				vdom,
-			)))
+			return Err(Error(ErrorKind::NonTextDomNodeInEscapableRawTextPosition {
+				element: element_name,
+				node: ByAddress(vdom),
+			}))
 		}
 		Node::Memoized {
 			state_key: _,
 			content,
-		} => render_escapable_raw_text(content, target, depth_limit - 1)?,
+		} => render_escapable_raw_text(
+			content,
+			target,
+			element_name,
+			depth.transparent_content(),
+			context,
+		)?,
 		Node::Multi(nodes) => {
 			for node in *nodes {
-				render_escapable_raw_text(node, target, depth_limit - 1)?
+				render_escapable_raw_text(
+					node,
+					target,
+					element_name,
+					depth.transparent_content(),
+					context,
+				)?
 			}
 		}
 		Node::Keyed(pairs) => {
-			for pair in *pairs {
-				render_escapable_raw_text(&pair.content, target, depth_limit - 1)?
+			check_keys(vdom, pairs, context)?;
+			for pair in keyed_in_order(pairs, context.options) {
+				render_escapable_raw_text(
+					&pair.content,
+					target,
+					element_name,
+					depth.transparent_content(),
+					context,
+				)?
 			}
 		}
 		Node::Text {
@@ -492,32 +3584,65 @@ fn render_escapable_raw_text<'a, S: ThreadSafety>(
 				LtSolidus,
 				/// See <https://html.spec.whatwg.org/multipage/syntax.html#character-references>.
 				///
-				/// This could be an ambiguous ampersand or part something that would be parsed as character reference, so it's escaped unconditionally.
+				/// This could be an ambiguous ampersand or part something that would be parsed as character reference, so it's escaped
+				/// unless it starts a known character reference and [`RenderOptions::smart_ampersands`] or [`RenderOptions::precise_escapable_raw_text`] is enabled.
 				#[token("&")]
 				Ampersand,
-				#[regex("[^<&]+")]
+				/// Only escaped with [`RenderOptions::browser_canonical`].
+				#[token(">")]
+				Gt,
+				#[regex("[^<&>]+")]
 				SafeVerbatim(&'a str),
 				#[error]
 				Error,
 			}
 
-			for token in EscapableRawTextToken::lexer(text) {
+			context.options.check_nul(text)?;
+			let canonical = context.options.browser_canonical;
+			let precise = context.options.precise_escapable_raw_text;
+			// Whether the `<` at `index` could begin the element's end tag.
+			let may_close = |index: usize| {
+				!precise || raw_text::may_start_end_tag(&text[index + 1..], element_name)
+			};
+			let target = &mut CharacterReferences::new(target, context.options);
+			let mut lexer = EscapableRawTextToken::lexer(text);
+			while let Some(token) = lexer.next() {
 				match token {
+					EscapableRawTextToken::Lt if canonical => target.write_str("&lt;"),
+					// A `<` at the very end may be followed by `/` in the next [`Node::Text`].
+					EscapableRawTextToken::Lt if precise && may_close(lexer.span().start) => {
+						target.write_str("&lt;")
+					}
 					EscapableRawTextToken::Lt => target.write_char('<'),
-					EscapableRawTextToken::LtSolidus => target.write_str("&lt;/"),
+					EscapableRawTextToken::LtSolidus if may_close(lexer.span().start) => {
+						target.write_str("&lt;/")
+					}
+					EscapableRawTextToken::LtSolidus => target.write_str("</"),
+					EscapableRawTextToken::Ampersand
+						if (context.options.smart_ampersands || precise)
+							&& escaping::starts_with_character_reference(
+								&text[lexer.span().start..],
+							) =>
+					{
+						target.write_char('&')
+					}
 					EscapableRawTextToken::Ampersand => target.write_str("&amp;"),
+					EscapableRawTextToken::Gt if canonical => target.write_str("&gt;"),
+					EscapableRawTextToken::Gt => target.write_char('>'),
 					EscapableRawTextToken::SafeVerbatim(str) => target.write_str(str),
-					EscapableRawTextToken::Error => unreachable!(),
+					// Any text matches one of the other tokens.
+					EscapableRawTextToken::Error => return Err(Error(ErrorKind::Internal)),
 				}?
 			}
 		}
-		Node::RemnantSite(_) => todo!("`RemnantSite`"),
+		//TODO
+		Node::RemnantSite(_) => return Err(Error(ErrorKind::UnsupportedNode(ByAddress(vdom)))),
 	}
 	Ok(())
 }
 
 //FIXME?: This probably blows up the text size. Check and, if necessary, replace it with a better categorization algorithm.
-#[derive(Logos, PartialEq)]
+#[derive(Logos, Clone, Copy, PartialEq)]
 enum ElementKind {
 	/// See <https://html.spec.whatwg.org/multipage/syntax.html#void-elements>.
 	#[regex("(?i)AREA")]
@@ -550,16 +3675,20 @@ enum ElementKind {
 	#[regex("(?i)TITLE")]
 	EscapableRawText,
 	/// See <https://html.spec.whatwg.org/multipage/syntax.html#foreign-elements>.
-	//TODO
+	///
+	/// Empty foreign element. Never detected from the name alone.
 	ForeignSelfClosing,
 	/// See <https://html.spec.whatwg.org/multipage/syntax.html#foreign-elements>.
-	//TODO
-	#[allow(dead_code)]
+	///
+	/// Non-empty foreign element. Never detected from the name alone.
 	ForeignNotSelfClosing,
 	/// See <https://html.spec.whatwg.org/multipage/syntax.html#normal-elements>.
 	/// See <https://html.spec.whatwg.org/multipage/syntax.html#element-restrictions> for special handling.
-	//TODO
-	#[allow(dead_code)]
+	///
+	/// `listing` isn't mentioned there, but parsers also drop a newline directly after its start tag.
+	/// See <https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inbody>.
+	#[regex("(?i)PRE")]
+	#[regex("(?i)LISTING")]
 	NormalPre,
 	/// See <https://html.spec.whatwg.org/multipage/syntax.html#normal-elements>,
 	/// <https://html.spec.whatwg.org/multipage/syntax.html#syntax-tag-name>  
@@ -595,21 +3724,30 @@ enum ElementKind {
 }
 
 impl ElementKind {
-	pub fn detect(element_name: &str) -> Result<Self, &str> {
+	pub fn detect(element_name: &str) -> Result<Self, InvalidElementNameReason> {
+		let first_char = |lexer: &Lexer<Self>| lexer.slice().chars().next().unwrap_or_default();
+
 		let mut lexer = Self::lexer(element_name);
 		let mut kind = match lexer.next() {
+			None => return Err(InvalidElementNameReason::Empty),
 			// These may not appear first.
-			None | Some(Self::Dash | Self::PotentialCustomElementNameCharacter | Self::Invalid) => {
-				return Err(element_name)
+			Some(Self::Dash | Self::PotentialCustomElementNameCharacter | Self::Invalid) => {
+				return Err(InvalidElementNameReason::InvalidFirstCharacter(first_char(
+					&lexer,
+				)))
 			}
 			Some(kind) => kind,
 		};
 		let mut dashed = false;
 		let mut custom = false;
-		for next in lexer {
+		while let Some(next) = lexer.next() {
 			// If more than one token can be found, it's either a normal element starting with one of the others' names or invalid.
 			match next {
-				ElementKind::Invalid => return Err(element_name),
+				ElementKind::Invalid => {
+					return Err(InvalidElementNameReason::InvalidCharacter(first_char(
+						&lexer,
+					)))
+				}
 				ElementKind::PotentialCustomElementNameCharacter => custom = true,
 				ElementKind::Dash => dashed = true,
 				_ => (),
@@ -617,15 +3755,565 @@ impl ElementKind {
 			kind = ElementKind::Normal;
 		}
 		if custom && !dashed {
-			Err(element_name)
+			Err(InvalidElementNameReason::CustomNameWithoutDash)
+		} else if custom && element_name.bytes().any(|b| b.is_ascii_uppercase()) {
+			Err(InvalidElementNameReason::UppercaseCustomName)
 		} else {
 			Ok(kind)
 		}
 	}
 }
 
+/// Why [`ElementKind::detect`] rejected an element name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InvalidElementNameReason {
+	Empty,
+	InvalidFirstCharacter(char),
+	InvalidCharacter(char),
+	/// Only [***valid custom element names***](https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name) may contain non-ASCII characters.
+	CustomNameWithoutDash,
+	/// See <https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name>.
+	UppercaseCustomName,
+}
+
+impl Display for InvalidElementNameReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			InvalidElementNameReason::Empty => write!(f, "it's empty"),
+			InvalidElementNameReason::InvalidFirstCharacter(c) => write!(
+				f,
+				"it must start with an ASCII letter or digit, not {:?}",
+				c
+			),
+			InvalidElementNameReason::InvalidCharacter(c) => {
+				write!(f, "{:?} can't appear in element names", c)
+			}
+			InvalidElementNameReason::CustomNameWithoutDash => write!(
+				f,
+				"non-ASCII characters are only allowed in custom element names, which must contain a dash ('-')"
+			),
+			InvalidElementNameReason::UppercaseCustomName => write!(
+				f,
+				"custom element names can't contain ASCII uppercase letters"
+			),
+		}
+	}
+}
+
+/// Writes the start tag of `element` into `target`, including attributes and, if `kind` calls for it, the self-closing ` /`.
+///
+/// `name` is the (possibly [mapped](`RenderOptions::map_element_name`)) element name to write.
+///
+/// `namespace` is the namespace `element` is created in, which may be different from what its [`Node`] variant implies.
+/// See [`Scope`].
+fn write_open_tag<'a, S: ThreadSafety>(
+	element: &'a Element<'a, S>,
+	name: &'a str,
+	kind: ElementKind,
+	namespace: Namespace,
+	scope: Scope,
+	options: &RenderOptions,
+	target: &mut impl Write,
+) -> Result<(), Error<'a, S>> {
+	/// Only counts what's written.
+	struct Discard;
+	impl Write for Discard {
+		fn write_str(&mut self, _: &str) -> fmt::Result {
+			Ok(())
+		}
+	}
+
+	// Separate writes are much faster than `write!` here. See `benches/tags.rs`.
+	target.write_char('<')?;
+	write_name(
+		target,
+		name,
+		options.browser_canonical && namespace == Namespace::Html,
+	)?;
+
+	let mut separator = " ";
+	if let Some(pretty) = options.pretty {
+		let mut single_line = CountingWriter::new(Discard);
+		write_attributes(
+			element,
+			name,
+			namespace,
+			scope,
+			options,
+			&mut single_line,
+			separator,
+		)?;
+		// `<`, the name, the attributes and `>`.
+		if name.len() + single_line.count() + 2 > pretty.max_line_width {
+			separator = "\n\t";
+		}
+	}
+	let last_value_mode =
+		write_attributes(element, name, namespace, scope, options, target, separator)?;
+
+	if kind == ElementKind::ForeignSelfClosing || options.xml && kind == ElementKind::Void {
+		if last_value_mode == AttributeValueMode::Unquoted {
+			// Otherwise, the solidus would become part of the value.
+			target.write_char(' ')?
+		}
+		target.write_str("/>")?
+	} else {
+		target.write_char('>')?;
+	}
+	Ok(())
+}
+
+/// Writes `name` into `target`, in ASCII lowercase if `lowercase` is `true`.
+fn write_name(target: &mut impl Write, name: &str, lowercase: bool) -> fmt::Result {
+	if lowercase && name.bytes().any(|b| b.is_ascii_uppercase()) {
+		for c in name.chars() {
+			target.write_char(c.to_ascii_lowercase())?
+		}
+		Ok(())
+	} else {
+		target.write_str(name)
+	}
+}
+
+/// Writes the attributes of `element`'s start tag into `target`, each preceded by `separator`. See [`write_open_tag`].
+///
+/// Returns how the last attribute value was written.
+#[allow(clippy::too_many_lines)]
+fn write_attributes<'a, S: ThreadSafety>(
+	element: &'a Element<'a, S>,
+	element_name: &'a str,
+	namespace: Namespace,
+	scope: Scope,
+	options: &RenderOptions,
+	target: &mut impl Write,
+	separator: &str,
+) -> Result<AttributeValueMode, Error<'a, S>> {
+	let foreign = namespace != Namespace::Html;
+	let lowercase_names = options.browser_canonical && !foreign;
+
+	// HTML parsers switch back to the HTML namespace by themselves (e.g. inside `foreignObject`), but XML parsers don't.
+	let mut last_value_mode = AttributeValueMode::Empty;
+	if options.xmlns
+		&& (foreign || options.xml)
+		&& namespace != scope.parent_namespace
+		&& !element
+			.attributes
+			.iter()
+			.any(|attribute| attribute.name == "xmlns")
+	{
+		last_value_mode =
+			write_attribute(target, separator, "xmlns", false, namespace.uri(), options)?
+	}
+	if let Some(is) = element.creation_options.is() {
+		options.check_nul(is)?;
+		last_value_mode = write_attribute(target, separator, "is", false, is, options)?
+	}
+	let mut harden_rel = options.harden_blank_targets
+		&& namespace == Namespace::Html
+		&& element_name.eq_ignore_ascii_case("a")
+		&& element.attributes.iter().any(|attribute| {
+			attribute.name.eq_ignore_ascii_case("target")
+				&& attribute.value.eq_ignore_ascii_case("_blank")
+		});
+	for &Attribute { name, value } in
+		attributes_in_order(element.attributes, options.browser_canonical)
+	{
+		let name = validate_attribute_name(name, foreign)?;
+		if options.strict_data_attributes {
+			check_data_attribute_name(name)?;
+		}
+		let name = if options.normalize_svg_case && namespace == Namespace::Svg {
+			svg_case::attribute_name(name)
+		} else {
+			name
+		};
+		let original_value = value;
+		options.check_nul(original_value)?;
+		#[cfg(feature = "alloc")]
+		let mapped;
+		#[cfg(feature = "alloc")]
+		let value = match options.map_attribute {
+			None => value,
+			Some(AttributeMapper(map)) => match map(element_name, name, value) {
+				Some(value) => {
+					mapped = value;
+					&*mapped
+				}
+				None => continue,
+			},
+		};
+		#[cfg(feature = "alloc")]
+		if options.reject_nul && value.contains('\0') {
+			// The mapped value doesn't live long enough to be part of the error.
+			return Err(Error(ErrorKind::NulByteInOutput(original_value)));
+		}
+		#[cfg(feature = "alloc")]
+		let resolved;
+		#[cfg(feature = "alloc")]
+		let value = match options.base_href {
+			Some(base_href)
+				if (name.eq_ignore_ascii_case("href") || name.eq_ignore_ascii_case("src"))
+					&& !(namespace == Namespace::Html
+						&& element_name.eq_ignore_ascii_case("base")) =>
+			{
+				match base_href::resolve(base_href, value) {
+					Some(url) => {
+						resolved = url;
+						&*resolved
+					}
+					None => value,
+				}
+			}
+			_ => value,
+		};
+		if options.ascii_only && !name.is_ascii() {
+			return Err(Error(ErrorKind::UnescapableNonAscii(name)));
+		}
+		if harden_rel && name.eq_ignore_ascii_case("rel") {
+			// Only the first `rel` attribute counts.
+			harden_rel = false;
+			last_value_mode =
+				write_hardened_rel(target, separator, name, lowercase_names, value, options)?;
+			continue;
+		}
+		last_value_mode = write_attribute(target, separator, name, lowercase_names, value, options)?
+	}
+	if harden_rel {
+		last_value_mode = write_attribute(
+			target,
+			separator,
+			"rel",
+			false,
+			"noopener noreferrer",
+			options,
+		)?
+	}
+	if let Some(nonce) = options.csp_nonce {
+		if namespace == Namespace::Html
+			&& (element_name.eq_ignore_ascii_case("script")
+				|| element_name.eq_ignore_ascii_case("style"))
+			&& !element
+				.attributes
+				.iter()
+				.any(|attribute| attribute.name.eq_ignore_ascii_case("nonce"))
+		{
+			last_value_mode = write_attribute(target, separator, "nonce", false, nonce, options)?
+		}
+	}
+	if options.image_perf_defaults
+		&& namespace == Namespace::Html
+		&& element_name.eq_ignore_ascii_case("img")
+	{
+		for &(name, value) in &[("loading", "lazy"), ("decoding", "async")] {
+			if !element
+				.attributes
+				.iter()
+				.any(|attribute| attribute.name.eq_ignore_ascii_case(name))
+			{
+				last_value_mode = write_attribute(target, separator, name, false, value, options)?
+			}
+		}
+	}
+	Ok(last_value_mode)
+}
+
+/// Iterates over `attributes` in their original order or, if `sorted`, stably sorted by name (ASCII-case-insensitively).
+///
+/// This doesn't allocate, so sorting takes quadratic time, which is fine for the few attributes an element usually has.
+fn attributes_in_order<'a, 'b>(
+	attributes: &'b [Attribute<'a>],
+	sorted: bool,
+) -> impl Iterator<Item = &'b Attribute<'a>> {
+	let order = move |i: usize, j: usize| {
+		let lowercase = |name: &'a str| name.bytes().map(|byte| byte.to_ascii_lowercase());
+		lowercase(attributes[i].name)
+			.cmp(lowercase(attributes[j].name))
+			.then(i.cmp(&j))
+	};
+	let mut previous: Option<usize> = None;
+	iter::from_fn(move || {
+		let next = if sorted {
+			(0..attributes.len())
+				.filter(|&i| match previous {
+					Some(previous) => order(i, previous) == Ordering::Greater,
+					None => true,
+				})
+				.min_by(|&i, &j| order(i, j))?
+		} else {
+			previous.map_or(0, |previous| previous + 1)
+		};
+		previous = Some(next);
+		attributes.get(next)
+	})
+}
+
+/// Writes the `rel` attribute `validated_attribute_name` like [`write_attribute`],
+/// but with `noopener` and `noreferrer` appended to `rel` unless already present.
+///
+/// The value then always contains a space, so it's double-quoted like with [`write_token_list_attribute`].
+///
+/// See <https://html.spec.whatwg.org/multipage/links.html#linkTypes>.
+fn write_hardened_rel(
+	target: &mut impl Write,
+	separator: &str,
+	validated_attribute_name: &str,
+	lowercase_name: bool,
+	rel: &str,
+	options: &RenderOptions,
+) -> Result<AttributeValueMode, fmt::Error> {
+	if options.redacts(validated_attribute_name) {
+		return write_attribute(
+			target,
+			separator,
+			validated_attribute_name,
+			lowercase_name,
+			rel,
+			options,
+		);
+	}
+
+	target.write_str(separator)?;
+	write_name(target, validated_attribute_name, lowercase_name)?;
+	target.write_str("=\"")?;
+	let rel = rel.trim_matches(|c: char| c.is_ascii_whitespace());
+	write_attribute_value(target, rel, AttributeValueMode::DoubleQuoted, options)?;
+	let mut empty = rel.is_empty();
+	for &keyword in &["noopener", "noreferrer"] {
+		if !rel
+			.split(|c: char| c.is_ascii_whitespace())
+			.any(|token| token.eq_ignore_ascii_case(keyword))
+		{
+			if !empty {
+				target.write_char(' ')?
+			}
+			target.write_str(keyword)?;
+			empty = false;
+		}
+	}
+	target.write_char('"')?;
+	Ok(AttributeValueMode::DoubleQuoted)
+}
+
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#syntax-attribute-name>.
+///
+/// Colons (`:`) are accepted in attribute names on HTML elements, since some frameworks use them as part of their own syntax.
+///
+/// On foreign elements, a colon instead separates a namespace prefix, which must be one of `xlink`, `xml` or `xmlns`.
+/// See <https://html.spec.whatwg.org/multipage/parsing.html#adjust-foreign-attributes>.
+fn validate_attribute_name<S: ThreadSafety>(
+	name: &str,
+	foreign: bool,
+) -> Result<&str, Error<'_, S>> {
+	if name == "is" {
+		return Err(Error(ErrorKind::ReservedAttributeName(name)));
+	}
+
+	if name.is_empty() {
+		return Err(Error(ErrorKind::InvalidAttributeName(name, None)));
+	}
+
+	if foreign {
+		if let Some((prefix, local_name)) = name.split_once(':') {
+			if !matches!(prefix, "xlink" | "xml" | "xmlns")
+				|| local_name.is_empty()
+				|| local_name.contains(':')
+			{
+				return Err(Error(ErrorKind::InvalidAttributeName(name, None)));
+			}
+		}
+	}
+
+	for c in name.chars() {
+		match c {
+				// <https://infra.spec.whatwg.org/#control>
+				// <https://infra.spec.whatwg.org/#c0-control>
+				'\0'..='\u{1F}' | '\u{7F}'..='\u{9F}' |
+
+				// <https://html.spec.whatwg.org/multipage/syntax.html#syntax-attribute-name>
+				' ' | '"' | '\'' | '>' | '/' | '=' => {
+					return Err(Error(ErrorKind::InvalidAttributeName(name, None)))
+				}
+				c if is_noncharacter(c) => {
+					return Err(Error(ErrorKind::InvalidAttributeName(name, None)))
+				}
+				_ => (),
+			}
+	}
+	Ok(name)
+}
+
+/// Why an attribute name that is syntactically fine was rejected anyway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InvalidAttributeNameReason {
+	/// See [`RenderOptions::strict_data_attributes`].
+	UppercaseDataAttribute,
+}
+
+impl Display for InvalidAttributeNameReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			InvalidAttributeNameReason::UppercaseDataAttribute => write!(
+				f,
+				"`data-*` attribute names can't contain ASCII uppercase letters, since parsers lowercase them \
+				 (`data-foo-bar` is `dataset.fooBar`)"
+			),
+		}
+	}
+}
+
+/// Errors iff `name` starts with `data-` (ASCII-case-insensitively) and contains ASCII uppercase letters.
+///
+/// See <https://html.spec.whatwg.org/multipage/dom.html#embedding-custom-non-visible-data-with-the-data-*-attributes>.
+fn check_data_attribute_name<S: ThreadSafety>(name: &str) -> Result<(), Error<'_, S>> {
+	let data = matches!(name.get(..5), Some(prefix) if prefix.eq_ignore_ascii_case("data-"));
+	if data && name.bytes().any(|byte| byte.is_ascii_uppercase()) {
+		Err(Error::uppercase_data_attribute_name(name))
+	} else {
+		Ok(())
+	}
+}
+
+/// See <https://infra.spec.whatwg.org/#noncharacter>.
+///
+/// The last two code points of each plane are listed explicitly, exactly as in the specification.
+fn is_noncharacter(c: char) -> bool {
+	matches!(
+		c,
+		'\u{FDD0}'
+			..='\u{FDEF}'
+				| '\u{FFFE}' | '\u{FFFF}'
+				| '\u{1FFFE}'
+				| '\u{1FFFF}'
+				| '\u{2FFFE}'
+				| '\u{2FFFF}'
+				| '\u{3FFFE}'
+				| '\u{3FFFF}'
+				| '\u{4FFFE}'
+				| '\u{4FFFF}'
+				| '\u{5FFFE}'
+				| '\u{5FFFF}'
+				| '\u{6FFFE}'
+				| '\u{6FFFF}'
+				| '\u{7FFFE}'
+				| '\u{7FFFF}'
+				| '\u{8FFFE}'
+				| '\u{8FFFF}'
+				| '\u{9FFFE}'
+				| '\u{9FFFF}'
+				| '\u{AFFFE}'
+				| '\u{AFFFF}'
+				| '\u{BFFFE}'
+				| '\u{BFFFF}'
+				| '\u{CFFFE}'
+				| '\u{CFFFF}'
+				| '\u{DFFFE}'
+				| '\u{DFFFF}'
+				| '\u{EFFFE}'
+				| '\u{EFFFF}'
+				| '\u{FFFFE}'
+				| '\u{FFFFF}'
+				| '\u{10FFFE}'
+				| '\u{10FFFF}'
+	)
+}
+
+/// Whether `attribute_name` matches any of `patterns`, ASCII-case-insensitively and with a trailing `*` matching any rest.
+fn matches_attribute_name(patterns: &[&str], attribute_name: &str) -> bool {
+	patterns
+		.iter()
+		.any(|&pattern| match pattern.strip_suffix('*') {
+			Some(prefix) => matches!(
+				attribute_name.get(..prefix.len()),
+				Some(start) if start.eq_ignore_ascii_case(prefix)
+			),
+			None => attribute_name.eq_ignore_ascii_case(pattern),
+		})
+}
+
+/// Writes `separator`, then `validated_attribute_name=value` into `target`, quoting and escaping `value` as necessary.
+///
+/// `separator` must be non-empty ASCII whitespace.
+/// The name is written in ASCII lowercase iff `lowercase_name` is `true`.
+///
+/// Returns how `value` was written.
+fn write_attribute(
+	target: &mut impl Write,
+	separator: &str,
+	validated_attribute_name: &str,
+	lowercase_name: bool,
+	value: &str,
+	options: &RenderOptions,
+) -> Result<AttributeValueMode, fmt::Error> {
+	target.write_str(separator)?;
+	write_name(target, validated_attribute_name, lowercase_name)?;
+
+	let value = if options.redacts(validated_attribute_name) {
+		RenderOptions::REDACTED
+	} else {
+		value
+	};
+
+	// See <https://www.w3.org/TR/xml/#NT-AttValue>.
+	let value_mode = if options.xml
+		|| (options.browser_canonical && !value.is_empty())
+		|| (value.is_empty()
+			&& matches_attribute_name(options.quote_empty_attributes, validated_attribute_name))
+	{
+		AttributeValueMode::DoubleQuoted
+	} else {
+		AttributeValueMode::detect(value)
+	};
+	target.write_str(match value_mode {
+		AttributeValueMode::Empty => return Ok(value_mode),
+		AttributeValueMode::Unquoted => "=",
+		AttributeValueMode::SingleQuoted => "='",
+		AttributeValueMode::DoubleQuoted => "=\"",
+	})?;
+	write_attribute_value(target, value, value_mode, options)?;
+	match value_mode {
+		AttributeValueMode::Empty | AttributeValueMode::Unquoted => (),
+		AttributeValueMode::SingleQuoted => target.write_char('\'')?,
+		AttributeValueMode::DoubleQuoted => target.write_char('"')?,
+	}
+	Ok(value_mode)
+}
+
+/// Writes `value` into `target`, escaped for an attribute value written in `value_mode` (but without quotes).
+fn write_attribute_value(
+	target: &mut impl Write,
+	value: &str,
+	value_mode: AttributeValueMode,
+	options: &RenderOptions,
+) -> fmt::Result {
+	let xml = options.xml;
+	let canonical = options.browser_canonical;
+	for (i, c) in value.char_indices() {
+		match c {
+			'&' if options.smart_ampersands
+				&& escaping::starts_with_character_reference(&value[i..]) =>
+			{
+				target.write_char('&')
+			}
+			'&' => target.write_str("&amp;"),
+			'<' if xml || canonical => target.write_str("&lt;"),
+			'>' if canonical => target.write_str("&gt;"),
+			'"' if value_mode == AttributeValueMode::DoubleQuoted => target.write_str("&quot;"),
+			// `AttributeValueMode::detect` shouldn't pick this mode if there's an apostrophe in `value`,
+			// but this keeps the output safe even if the two ever diverge.
+			'\'' if value_mode == AttributeValueMode::SingleQuoted => target.write_str("&#39;"),
+			// Parsers turn literal `\r` into `\n` (see <https://html.spec.whatwg.org/multipage/parsing.html#preprocessing-the-input-stream>),
+			// and XML parsers also turn whitespace into spaces (see <https://www.w3.org/TR/xml/#AVNormalize>).
+			'\r' => target.write_str("&#13;"),
+			'\t' if xml => target.write_str("&#9;"),
+			'\n' if xml => target.write_str("&#10;"),
+			c => CharacterReferences::new(target, options).write_char(c),
+		}?
+	}
+	Ok(())
+}
+
 /// See <https://html.spec.whatwg.org/multipage/syntax.html#attributes-2>.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum AttributeValueMode {
 	Empty,
 	Unquoted,
@@ -666,65 +4354,453 @@ impl AttributeValueMode {
 }
 
 /// [lignin-html](`self`) error.
-#[derive(Debug)]
+///
+/// Errors compare equal if they are of the same kind and refer to equal text and the *same* [`Node`]s (by address),
+/// since [`Node`] itself can't be compared in general.
+///
+/// ```rust
+/// use lignin::{Element, ElementCreationOptions, Node};
+/// use lignin_html::{render_fragment, Error};
+///
+/// assert_eq!(
+///     render_fragment(
+///         &Node::HtmlElement {
+///             element: &Element {
+///                 name: "x y",
+///                 creation_options: ElementCreationOptions::new(),
+///                 attributes: &[],
+///                 content: Node::Multi(&[]),
+///                 event_bindings: &[],
+///             },
+///             dom_binding: None,
+///         }
+///         .prefer_thread_safe(),
+///         &mut String::new(),
+///         1,
+///     ),
+///     Err(Error::invalid_element_name("x y")),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Error<'a, S: ThreadSafety>(ErrorKind<'a, S>);
 
-#[derive(Debug)]
+impl<'a, S: ThreadSafety> Error<'a, S> {
+	/// Formats this error into an [`OwnedError`] that can outlive the input.
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn into_owned(self) -> OwnedError {
+		use alloc::string::ToString;
+
+		OwnedError {
+			message: self.to_string(),
+			fmt_error: match self.0 {
+				ErrorKind::FmtError(fmt_error) => Some(fmt_error),
+				_ => None,
+			},
+		}
+	}
+
+	/// Creates the error for an invalid element name, e.g. to compare against in tests.
+	///
+	/// # Panics
+	///
+	/// Iff `name` is a valid element name.
+	#[must_use]
+	pub fn invalid_element_name(name: &'a str) -> Self {
+		match ElementKind::detect(name) {
+			Ok(_) => panic!("{:?} is a valid element name", name),
+			Err(reason) => Self(ErrorKind::InvalidElementName(name, reason)),
+		}
+	}
+
+	/// Creates the error for an invalid attribute name, e.g. to compare against in tests.
+	#[must_use]
+	pub const fn invalid_attribute_name(name: &'a str) -> Self {
+		Self(ErrorKind::InvalidAttributeName(name, None))
+	}
+
+	/// Creates the error for a `data-*` attribute name with ASCII uppercase letters, as rejected with [`RenderOptions::strict_data_attributes`],
+	/// e.g. to compare against in tests.
+	#[must_use]
+	pub const fn uppercase_data_attribute_name(name: &'a str) -> Self {
+		Self(ErrorKind::InvalidAttributeName(
+			name,
+			Some(InvalidAttributeNameReason::UppercaseDataAttribute),
+		))
+	}
+
+	/// Creates the error for a reserved attribute name like `is`, e.g. to compare against in tests.
+	#[must_use]
+	pub const fn reserved_attribute_name(name: &'a str) -> Self {
+		Self(ErrorKind::ReservedAttributeName(name))
+	}
+
+	/// Creates the error for raw `text` that would close the `element` it's in early, e.g. to compare against in tests.
+	///
+	/// `offset` is the byte offset of `text` within the offending [`Node::Text`].
+	#[must_use]
+	pub const fn element_closed_in_raw_text(
+		element: &'a str,
+		text: &'a str,
+		offset: usize,
+	) -> Self {
+		Self(ErrorKind::ElementClosedInRawText {
+			element,
+			text,
+			offset,
+		})
+	}
+
+	/// Creates the error for exceeding `depth_limit` at `node`, e.g. to compare against in tests.
+	#[must_use]
+	pub const fn depth_limit_exceeded(node: &'a Node<'a, S>, depth_limit: usize) -> Self {
+		Self(ErrorKind::DepthLimitExceeded {
+			node: ByAddress(node),
+			depth_limit,
+		})
+	}
+
+	/// Creates the error for exceeding `transparent_depth_limit` at `node`, e.g. to compare against in tests.
+	///
+	/// See [`RenderOptions::transparent_depth_limit`].
+	#[must_use]
+	pub const fn transparent_depth_limit_exceeded(
+		node: &'a Node<'a, S>,
+		transparent_depth_limit: usize,
+	) -> Self {
+		Self(ErrorKind::TransparentDepthLimitExceeded {
+			node: ByAddress(node),
+			transparent_depth_limit,
+		})
+	}
+
+	/// Creates an error rejecting `node`, e.g. from a [`render_fragment_with_visitor`] callback.
+	#[must_use]
+	pub const fn rejected(node: &'a Node<'a, S>) -> Self {
+		Self(ErrorKind::Rejected(ByAddress(node)))
+	}
+
+	/// Creates the error for a `node` that can't be rendered, like a [`Node::RemnantSite`], e.g. to compare against in tests.
+	#[must_use]
+	pub const fn unsupported_node(node: &'a Node<'a, S>) -> Self {
+		Self(ErrorKind::UnsupportedNode(ByAddress(node)))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ErrorKind<'a, S: ThreadSafety> {
-	InvalidElementName(&'a str),
+	InvalidElementName(&'a str, InvalidElementNameReason),
+	ElementInWrongNamespace(NodeRef<'a, S>),
 	ReservedAttributeName(&'a str),
-	InvalidAttributeName(&'a str),
-	NonEmptyVoidElementContent(&'a Node<'a, S>),
-	NonTextDomNodeInRawTextPosition(&'a Node<'a, S>),
-	NonTextDomNodeInEscapableRawTextPosition(&'a Node<'a, S>),
-	ElementClosedInRawText(&'a str),
-	DepthLimitExceeded(&'a Node<'a, S>),
+	InvalidAttributeName(&'a str, Option<InvalidAttributeNameReason>),
+	MissingLangAttribute(NodeRef<'a, S>),
+	MissingAltAttribute(NodeRef<'a, S>),
+	FragmentWithAttributes(NodeRef<'a, S>),
+	Rejected(NodeRef<'a, S>),
+	MisplacedElement {
+		element: NodeRef<'a, S>,
+		expected_section: &'static str,
+	},
+	UnescapableNonAscii(&'a str),
+	/// The text, comment or attribute value containing NUL, with [`RenderOptions::reject_nul`].
+	///
+	/// For attribute values changed by [`RenderOptions::map_attribute`], this is the original value.
+	NulByteInOutput(&'a str),
+	NonEmptyVoidElementContent(NodeRef<'a, S>),
+	NonTextDomNodeInRawTextPosition {
+		/// The name of the raw text element.
+		element: &'a str,
+		node: NodeRef<'a, S>,
+	},
+	NonTextDomNodeInEscapableRawTextPosition {
+		/// The name of the escapable raw text element.
+		element: &'a str,
+		node: NodeRef<'a, S>,
+	},
+	ElementClosedInRawText {
+		/// The name of the raw text element.
+		element: &'a str,
+		/// The offending end tag (start), e.g. `</script>`.
+		text: &'a str,
+		/// The byte offset of `text` within its [`Node::Text`].
+		offset: usize,
+	},
+	/// The name of the `script` element, whose content ends within `<!--<script>`.
+	UnclosedScriptDoubleEscape(&'a str),
+	NonSvgDocumentRoot(NodeRef<'a, S>),
+	DocumentRootNotHtml(NodeRef<'a, S>),
+	InvalidId(&'a str),
+	InvalidProcessingInstructionTarget(&'a str),
+	InvalidProcessingInstructionData(&'a str),
+	/// See [`RenderOptions::comment_guard_char`].
+	InvalidCommentGuardChar(char),
+	/// The comment text, which [`CommentPolicy::ErrorIfLossy`] can't render unchanged.
+	IllegalCommentText(&'a str),
+	UnsupportedNode(NodeRef<'a, S>),
+	#[cfg(feature = "alloc")]
+	RepeatLimitExceeded {
+		node: NodeRef<'a, S>,
+		repeat_limit: usize,
+	},
+	#[cfg(feature = "alloc")]
+	DuplicateKey {
+		node: NodeRef<'a, S>,
+		dom_key: u32,
+	},
+	/// A bug in `lignin-html`.
+	Internal,
+	DepthLimitExceeded {
+		node: NodeRef<'a, S>,
+		depth_limit: usize,
+	},
+	TransparentDepthLimitExceeded {
+		node: NodeRef<'a, S>,
+		transparent_depth_limit: usize,
+	},
 	FmtError(fmt::Error),
 }
 
-impl<'a, S: ThreadSafety> From<fmt::Error> for Error<'a, S> {
+impl<S: ThreadSafety> From<fmt::Error> for Error<'_, S> {
 	fn from(fmt_error: fmt::Error) -> Self {
 		Self(ErrorKind::FmtError(fmt_error))
 	}
 }
 
-impl<'a, S: ThreadSafety> Display for Error<'a, S> {
+/// Lets `?` turn rendering errors into [`fmt::Error`] in [`Display`] implementations and other functions returning [`fmt::Result`].
+///
+/// This is lossy: [`fmt::Error`] can't carry any detail about what went wrong.
+/// Use [`render_fragment_into_formatter`] to keep the [`Error`] around instead.
+///
+/// ```
+/// use core::fmt::{self, Display};
+/// use lignin_html::{lignin::{Node, ThreadSafe}, render_fragment};
+///
+/// struct Page<'a>(Node<'a, ThreadSafe>);
+///
+/// impl Display for Page<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("<main>")?;
+///         render_fragment(&self.0, f, 256)?;
+///         f.write_str("</main>")
+///     }
+/// }
+///
+/// let page = Page(Node::Text { text: "<3", dom_binding: None });
+/// assert_eq!(page.to_string(), "<main>&lt;3</main>");
+/// ```
+impl<'a, S: ThreadSafety> From<Error<'a, S>> for fmt::Error {
+	fn from(_: Error<'a, S>) -> Self {
+		fmt::Error
+	}
+}
+
+impl<S: ThreadSafety> Display for Error<'_, S> {
+	#[allow(clippy::too_many_lines)]
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match &self.0 {
-			ErrorKind::InvalidElementName(str) => write!(f, "Invalid element name {:?}", str),
+			ErrorKind::InvalidElementName(str, reason) => {
+				write!(f, "Invalid element name {:?}: {}", str, reason)
+			}
+			ErrorKind::ElementInWrongNamespace(node) => write!(
+				f,
+				"Element in wrong namespace: {} belongs to another namespace",
+				Summary(node.0)
+			),
 			ErrorKind::ReservedAttributeName(str) => write!(
 				f,
 				"Reserved attribute name {:?}; specify through `Element::creation_options` instead",
 				str
 			),
-			ErrorKind::InvalidAttributeName(str) => write!(f, "Invalid attribute name {:?}", str),
+			ErrorKind::InvalidAttributeName(str, None) => write!(f, "Invalid attribute name {:?}", str),
+			ErrorKind::InvalidAttributeName(str, Some(reason)) => {
+				write!(f, "Invalid attribute name {:?}: {}", str, reason)
+			}
+			ErrorKind::MissingLangAttribute(node) => {
+				write!(f, "{} is missing a `lang` attribute", Summary(node.0))
+			}
+			ErrorKind::MissingAltAttribute(node) => {
+				write!(f, "{} is missing an `alt` attribute", Summary(node.0))
+			}
+			ErrorKind::FragmentWithAttributes(node) => write!(
+				f,
+				"{} is a fragment, which can't have attributes",
+				Summary(node.0)
+			),
+			ErrorKind::Rejected(node) => write!(f, "{} was rejected", Summary(node.0)),
+			ErrorKind::MisplacedElement {
+				element,
+				expected_section,
+			} => write!(
+				f,
+				"{} belongs in `<{}>`",
+				Summary(element.0),
+				expected_section
+			),
+			ErrorKind::UnescapableNonAscii(str) => write!(
+				f,
+				"Non-ASCII characters in {:?} can't be escaped in ASCII-only mode",
+				str
+			),
+			ErrorKind::NulByteInOutput(str) => {
+				write!(f, "{:?} contains NUL, which would be written as-is", str)
+			}
 			ErrorKind::NonEmptyVoidElementContent(node) => {
 				write!(f, "Non-empty void element content {:?}", node)
 			}
-			ErrorKind::NonTextDomNodeInRawTextPosition(node) => {
-				write!(f, "Non-text DOM node in raw text position {:?}", node)
+			ErrorKind::NonTextDomNodeInRawTextPosition { element, node } => write!(
+				f,
+				"{} can't appear in the raw text element `<{}>`, which may only contain text",
+				Summary(node.0),
+				element
+			),
+			ErrorKind::NonTextDomNodeInEscapableRawTextPosition { element, node } => write!(
+				f,
+				"{} can't appear in the escapable raw text element `<{}>`, which may only contain text",
+				Summary(node.0),
+				element
+			),
+			ErrorKind::ElementClosedInRawText {
+				element,
+				text,
+				offset,
+			} => write!(
+				f,
+				"Element closed in raw text of `<{}>` at byte {}: {:?}",
+				element, offset, text
+			),
+			ErrorKind::UnclosedScriptDoubleEscape(element) => write!(
+				f,
+				"Raw text of `<{0}>` ends within `<!--<script>`, where `</{0}>` doesn't close the element",
+				element
+			),
+			ErrorKind::NonSvgDocumentRoot(node) => {
+				write!(f, "SVG document root is not an SVG element: {:?}", node)
+			}
+			ErrorKind::DocumentRootNotHtml(node) => write!(
+				f,
+				"HTML document root is not a single `<html>` element, but {}",
+				Summary(node.0)
+			),
+			ErrorKind::InvalidId(str) => write!(f, "Invalid ID {:?}", str),
+			ErrorKind::InvalidProcessingInstructionTarget(str) => {
+				write!(f, "Invalid processing instruction target {:?}", str)
+			}
+			ErrorKind::InvalidProcessingInstructionData(str) => {
+				write!(f, "Invalid processing instruction data {:?}", str)
 			}
-			ErrorKind::NonTextDomNodeInEscapableRawTextPosition(node) => {
-				write!(
-					f,
-					"Non-text DOM node in escapable raw text position {:?}",
-					node
-				)
+			ErrorKind::InvalidCommentGuardChar(c) => {
+				write!(f, "Invalid comment guard character {:?}", c)
 			}
-			ErrorKind::ElementClosedInRawText(str) => {
-				write!(f, "Element closed in raw text: {:?}", str)
+			ErrorKind::IllegalCommentText(str) => {
+				write!(f, "Comment text {:?} can't be rendered unchanged", str)
 			}
-			ErrorKind::DepthLimitExceeded(_) => write!(f, "Depth limit exceeded"),
+			ErrorKind::UnsupportedNode(node) => write!(f, "Unsupported node {:?}", node),
+			#[cfg(feature = "alloc")]
+			ErrorKind::RepeatLimitExceeded { node, repeat_limit } => write!(
+				f,
+				"{} was rendered more than {} times",
+				Summary(node.0),
+				repeat_limit
+			),
+			#[cfg(feature = "alloc")]
+			ErrorKind::DuplicateKey { node, dom_key } => {
+				write!(f, "Duplicate key {} in {}", dom_key, Summary(node.0))
+			}
+			ErrorKind::DepthLimitExceeded { node, depth_limit } => write!(
+				f,
+				"Depth limit of {} exceeded at {}",
+				depth_limit,
+				Summary(node.0)
+			),
+			ErrorKind::TransparentDepthLimitExceeded {
+				node,
+				transparent_depth_limit,
+			} => write!(
+				f,
+				"Transparent depth limit of {} exceeded at {}",
+				transparent_depth_limit,
+				Summary(node.0)
+			),
+			ErrorKind::Internal => write!(
+				f,
+				"Internal error in lignin-html; please report this at <https://github.com/Tamschi/lignin-html/issues>"
+			),
 			ErrorKind::FmtError(fmt_error) => Display::fmt(fmt_error, f),
 		}
 	}
 }
 
+/// [lignin-html](`self`) error that doesn't borrow from the input, created by [`Error::into_owned`].
+///
+/// It keeps the [`Display`] output of the original [`Error`], in place of the parts of the input it referred to.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedError {
+	message: alloc::string::String,
+	/// The [`fmt::Error`] the original [`Error`] wrapped, if any, as [`source`](`std::error::Error::source`).
+	fmt_error: Option<fmt::Error>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, S: ThreadSafety> From<Error<'a, S>> for OwnedError {
+	fn from(error: Error<'a, S>) -> Self {
+		error.into_owned()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Display for OwnedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+/// A [`Node`] reference in an [`ErrorKind`], compared by address.
+type NodeRef<'a, S> = ByAddress<&'a Node<'a, S>>;
+
+/// Debug-formats like the referenced value, but is only equal to references to the same instance.
+#[derive(Clone, Copy)]
+struct ByAddress<T>(T);
+
+impl<T: ?Sized> PartialEq for ByAddress<&T> {
+	fn eq(&self, other: &Self) -> bool {
+		ptr::eq(self.0, other.0)
+	}
+}
+impl<T: ?Sized> Eq for ByAddress<&T> {}
+
+impl<T: Debug> Debug for ByAddress<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+/// Briefly describes a [`Node`] for error messages.
+struct Summary<'a, S: ThreadSafety>(&'a Node<'a, S>);
+
+impl<S: ThreadSafety> Display for Summary<'_, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.0 {
+			Node::Comment { .. } => write!(f, "a comment"),
+			Node::HtmlElement { element, .. } => write!(f, "a `<{}>` element", element.name),
+			Node::MathMlElement { element, .. } => {
+				write!(f, "a MathML `<{}>` element", element.name)
+			}
+			Node::SvgElement { element, .. } => write!(f, "an SVG `<{}>` element", element.name),
+			Node::Memoized { .. } => write!(f, "a `Memoized` node"),
+			Node::Multi(_) => write!(f, "a `Multi` node"),
+			Node::Keyed(_) => write!(f, "a `Keyed` node"),
+			Node::Text { .. } => write!(f, "a text node"),
+			Node::RemnantSite(_) => write!(f, "a `RemnantSite`"),
+		}
+	}
+}
+
 #[cfg(feature = "std")]
 extern crate std;
 
 #[cfg(feature = "std")]
-impl<'a, S: ThreadSafety> std::error::Error for Error<'a, S> {
+impl<S: ThreadSafety> std::error::Error for Error<'_, S> {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
 		if let ErrorKind::FmtError(fmt_error) = &self.0 {
 			Some(fmt_error)
@@ -733,3 +4809,12 @@ impl<'a, S: ThreadSafety> std::error::Error for Error<'a, S> {
 		}
 	}
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for OwnedError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		self.fmt_error
+			.as_ref()
+			.map(|fmt_error| fmt_error as &(dyn std::error::Error + 'static))
+	}
+}
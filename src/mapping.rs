@@ -0,0 +1,158 @@
+//! Mapping rendered output back to the [`Node`]s it came from.
+
+use crate::{is_transparent, render_node, Context, Depth, Error, RenderOptions, Scope};
+use alloc::vec::Vec;
+use core::{
+	cell::{Cell, RefCell},
+	fmt::{self, Write},
+	ops::Range,
+};
+use lignin::{Node, ThreadSafety};
+
+/// The position of a [`Node`] in the tree passed to [`render_fragment_with_mapping`], as child indices starting at the root.
+///
+/// Each index selects a [`Node`] in a [`Node::Multi`], a [`ReorderableFragment`](`lignin::ReorderableFragment`)'s content in a [`Node::Keyed`] (both in the given order),
+/// or, as `0`, the content of an element or [`Node::Memoized`]. The root itself has an empty path.
+pub type NodePath = Vec<usize>;
+
+/// Bookkeeping for [`render_fragment_with_mapping`].
+pub(crate) struct Mapping {
+	/// How many bytes were written so far. Updated by [`PositionWriter`].
+	position: Cell<usize>,
+	state: RefCell<MappingState>,
+}
+
+#[derive(Default)]
+struct MappingState {
+	/// The path of the [`Node`] currently being rendered.
+	path: NodePath,
+	/// How many children each [`Node`] along `path` has entered so far.
+	child_counts: Vec<usize>,
+	entries: Vec<(Range<usize>, NodePath)>,
+}
+
+impl Mapping {
+	pub(crate) fn new() -> Self {
+		Self {
+			position: Cell::new(0),
+			state: RefCell::default(),
+		}
+	}
+
+	/// How many bytes were written so far.
+	pub(crate) fn position(&self) -> usize {
+		self.position.get()
+	}
+
+	/// The [`NodePath`] of the [`Node`] currently being rendered.
+	pub(crate) fn path(&self) -> NodePath {
+		self.state.borrow().path.clone()
+	}
+
+	/// Starts the mapping of `vdom`, which is about to be rendered.
+	///
+	/// Returns the index of its entry, if it gets one.
+	pub(crate) fn enter<S: ThreadSafety>(&self, vdom: &Node<S>) -> Option<usize> {
+		let state = &mut *self.state.borrow_mut();
+		if let Some(child_count) = state.child_counts.last_mut() {
+			state.path.push(*child_count);
+			*child_count += 1;
+		}
+		state.child_counts.push(0);
+		if is_transparent(vdom) {
+			return None;
+		}
+		let position = self.position.get();
+		state.entries.push((position..position, state.path.clone()));
+		Some(state.entries.len() - 1)
+	}
+
+	/// Ends the mapping of the [`Node`] that was most recently [entered](`Mapping::enter`), with the returned `entry`.
+	pub(crate) fn exit(&self, entry: Option<usize>) {
+		let state = &mut *self.state.borrow_mut();
+		state.child_counts.pop();
+		state.path.pop();
+		if let Some(entry) = entry {
+			state.entries[entry].0.end = self.position.get();
+		}
+	}
+}
+
+/// Forwards to `inner` and keeps [`Mapping::position`] up to date.
+pub(crate) struct PositionWriter<'m, W: Write> {
+	inner: W,
+	position: &'m Cell<usize>,
+}
+
+impl<'m, W: Write> PositionWriter<'m, W> {
+	pub(crate) fn new(inner: W, mapping: &'m Mapping) -> Self {
+		Self {
+			inner,
+			position: &mapping.position,
+		}
+	}
+}
+
+impl<W: Write> Write for PositionWriter<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.inner.write_str(s)?;
+		self.position.set(self.position.get() + s.len());
+		Ok(())
+	}
+}
+
+/// Like [`render_fragment`](`crate::render_fragment`), but also returns which byte range of the output each element,
+/// [`Node::Text`] and [`Node::Comment`] in `vdom` was rendered as, e.g. for click-to-source developer tools.
+///
+/// Entries are ordered by where their range starts, with outer elements before their content.
+/// An element's range covers its start tag, content and end tag.
+///
+/// [`Node::Multi`], [`Node::Keyed`] and [`Node::Memoized`] don't get entries of their own, but are part of the [`NodePath`]s.  
+/// The content of raw text elements like `script` and escapable raw text elements like `title` isn't mapped separately.
+///
+/// ```
+/// use lignin_html::{lignin::{Element, ElementCreationOptions, Node, ThreadSafe}, render_fragment_with_mapping};
+///
+/// let vdom: Node<ThreadSafe> = Node::HtmlElement {
+///     element: &Element {
+///         name: "p",
+///         creation_options: ElementCreationOptions::new(),
+///         attributes: &[],
+///         content: Node::Multi(&[
+///             Node::Text { text: "a", dom_binding: None },
+///             Node::Comment { comment: "b", dom_binding: None },
+///         ]),
+///         event_bindings: &[],
+///     },
+///     dom_binding: None,
+/// };
+///
+/// let mut html = String::new();
+/// let mapping = render_fragment_with_mapping(&vdom, &mut html, 2).unwrap();
+/// assert_eq!(html, "<p>a<!--b--></p>");
+/// assert_eq!(mapping, [(0..16, vec![]), (3..4, vec![0, 0]), (4..12, vec![0, 1])]);
+/// ```
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_with_mapping<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<Vec<(Range<usize>, NodePath)>, Error<'a, S>> {
+	let mapping = Mapping::new();
+	render_node(
+		vdom,
+		&mut PositionWriter::new(target, &mapping),
+		Depth::top_level(depth_limit),
+		&Context {
+			mapping: Some(&mapping),
+			..Context::new(&RenderOptions::new().with_depth_limit(depth_limit))
+		},
+		Scope::TOP_LEVEL,
+	)?;
+	Ok(mapping.state.into_inner().entries)
+}
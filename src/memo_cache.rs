@@ -0,0 +1,63 @@
+//! Reuse of rendered [`Node::Memoized`](`lignin::Node::Memoized`) content across renders.
+
+use crate::Scope;
+use alloc::{collections::BTreeMap, string::String};
+
+/// Previously rendered [`Node::Memoized`](`lignin::Node::Memoized`) content, by `state_key`.
+///
+/// Used with [`render_fragment_cached`](`crate::render_fragment_cached`).
+///
+/// # Invalidation
+///
+/// A cached rendering is reused whenever a [`Node::Memoized`](`lignin::Node::Memoized`) with the same `state_key` is rendered in the same namespace context,
+/// **without** looking at its `content`.  
+/// Call [`MemoCache::remove`] or [`MemoCache::clear`] whenever the content behind a `state_key` changes.
+///
+/// The depth limit is not checked again for reused content.
+#[derive(Debug, Default)]
+pub struct MemoCache {
+	entries: BTreeMap<u64, (Scope, String)>,
+}
+
+impl MemoCache {
+	/// Creates a new empty [`MemoCache`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Forgets the rendering for `state_key`, if there is one.
+	///
+	/// Returns whether an entry was removed.
+	pub fn remove(&mut self, state_key: u64) -> bool {
+		self.entries.remove(&state_key).is_some()
+	}
+
+	/// Forgets all cached renderings.
+	pub fn clear(&mut self) {
+		self.entries.clear()
+	}
+
+	/// How many renderings are cached.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether no renderings are cached.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub(crate) fn get(&self, state_key: u64, scope: Scope) -> Option<&str> {
+		match self.entries.get(&state_key) {
+			Some((cached_scope, rendered)) if *cached_scope == scope => Some(rendered),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn insert(&mut self, state_key: u64, scope: Scope, rendered: String) {
+		self.entries.insert(state_key, (scope, rendered));
+	}
+}
@@ -0,0 +1,259 @@
+//! Element names that unambiguously belong to one namespace.
+//!
+//! Names shared between namespaces (like `a`, `title`, `script`, `style` and `font`) aren't listed.
+
+use crate::Namespace;
+use lignin::Attribute;
+
+/// See <https://html.spec.whatwg.org/multipage/indices.html#elements-3>.
+const HTML_ONLY: &[&str] = &[
+	"abbr",
+	"address",
+	"area",
+	"article",
+	"aside",
+	"audio",
+	"b",
+	"base",
+	"bdi",
+	"bdo",
+	"blockquote",
+	"body",
+	"br",
+	"button",
+	"canvas",
+	"caption",
+	"cite",
+	"code",
+	"col",
+	"colgroup",
+	"data",
+	"datalist",
+	"dd",
+	"del",
+	"details",
+	"dfn",
+	"dialog",
+	"div",
+	"dl",
+	"dt",
+	"em",
+	"embed",
+	"fieldset",
+	"figcaption",
+	"figure",
+	"footer",
+	"form",
+	"h1",
+	"h2",
+	"h3",
+	"h4",
+	"h5",
+	"h6",
+	"head",
+	"header",
+	"hgroup",
+	"hr",
+	"html",
+	"i",
+	"iframe",
+	"img",
+	"input",
+	"ins",
+	"kbd",
+	"label",
+	"legend",
+	"li",
+	"link",
+	"main",
+	"map",
+	"mark",
+	"menu",
+	"meta",
+	"meter",
+	"nav",
+	"noscript",
+	"object",
+	"ol",
+	"optgroup",
+	"option",
+	"output",
+	"p",
+	"param",
+	"picture",
+	"pre",
+	"progress",
+	"q",
+	"rp",
+	"rt",
+	"ruby",
+	"s",
+	"samp",
+	"section",
+	"select",
+	"slot",
+	"small",
+	"source",
+	"span",
+	"strong",
+	"sub",
+	"summary",
+	"sup",
+	"table",
+	"tbody",
+	"td",
+	"template",
+	"textarea",
+	"tfoot",
+	"th",
+	"thead",
+	"time",
+	"tr",
+	"track",
+	"u",
+	"ul",
+	"var",
+	"video",
+	"wbr",
+];
+
+/// See <https://www.w3.org/TR/SVG2/eltindex.html> and <https://www.w3.org/TR/filter-effects-1/#FilterPrimitivesOverview>.
+const SVG_ONLY: &[&str] = &[
+	"animate",
+	"animateMotion",
+	"animateTransform",
+	"circle",
+	"clipPath",
+	"defs",
+	"desc",
+	"ellipse",
+	"feBlend",
+	"feColorMatrix",
+	"feComponentTransfer",
+	"feComposite",
+	"feConvolveMatrix",
+	"feDiffuseLighting",
+	"feDisplacementMap",
+	"feDistantLight",
+	"feDropShadow",
+	"feFlood",
+	"feFuncA",
+	"feFuncB",
+	"feFuncG",
+	"feFuncR",
+	"feGaussianBlur",
+	"feImage",
+	"feMerge",
+	"feMergeNode",
+	"feMorphology",
+	"feOffset",
+	"fePointLight",
+	"feSpecularLighting",
+	"feSpotLight",
+	"feTile",
+	"feTurbulence",
+	"filter",
+	"foreignObject",
+	"g",
+	"image",
+	"line",
+	"linearGradient",
+	"marker",
+	"mask",
+	"metadata",
+	"mpath",
+	"path",
+	"pattern",
+	"polygon",
+	"polyline",
+	"radialGradient",
+	"rect",
+	"set",
+	"stop",
+	"svg",
+	"switch",
+	"symbol",
+	"text",
+	"textPath",
+	"tspan",
+	"use",
+	"view",
+];
+
+/// See <https://w3c.github.io/mathml-core/#mathml-elements-and-attributes>.
+const MATH_ML_ONLY: &[&str] = &[
+	"annotation",
+	"annotation-xml",
+	"math",
+	"merror",
+	"mfrac",
+	"mi",
+	"mmultiscripts",
+	"mn",
+	"mo",
+	"mover",
+	"mpadded",
+	"mphantom",
+	"mprescripts",
+	"mroot",
+	"mrow",
+	"ms",
+	"mspace",
+	"msqrt",
+	"mstyle",
+	"msub",
+	"msubsup",
+	"msup",
+	"mtable",
+	"mtd",
+	"mtext",
+	"mtr",
+	"munder",
+	"munderover",
+	"semantics",
+];
+
+/// Returns the only [`Namespace`] an element named `name` can be in, if this is unambiguous.
+///
+/// The comparison is ASCII-case-insensitive, since HTML element names are.
+pub(crate) fn exclusive_namespace(name: &str) -> Option<Namespace> {
+	let contains = |names: &[&str]| names.iter().any(|known| known.eq_ignore_ascii_case(name));
+	if contains(HTML_ONLY) {
+		Some(Namespace::Html)
+	} else if contains(SVG_ONLY) {
+		Some(Namespace::Svg)
+	} else if contains(MATH_ML_ONLY) {
+		Some(Namespace::MathMl)
+	} else {
+		None
+	}
+}
+
+/// Whether an element named `name` with `attributes` in `namespace` is an [***HTML integration point***](https://html.spec.whatwg.org/multipage/parsing.html#html-integration-point)
+/// or a [***MathML text integration point***](https://html.spec.whatwg.org/multipage/parsing.html#mathml-text-integration-point),
+/// i.e. whether HTML elements can appear directly inside it.
+pub(crate) fn is_html_integration_point(
+	namespace: Namespace,
+	name: &str,
+	attributes: &[Attribute],
+) -> bool {
+	match namespace {
+		Namespace::Html => false,
+		Namespace::Svg => ["foreignObject", "desc", "title"]
+			.iter()
+			.any(|known| known.eq_ignore_ascii_case(name)),
+		Namespace::MathMl => {
+			["mi", "mo", "mn", "ms", "mtext"]
+				.iter()
+				.any(|known| known.eq_ignore_ascii_case(name))
+				|| name.eq_ignore_ascii_case("annotation-xml")
+					&& attributes.iter().any(|attribute| {
+						attribute.name.eq_ignore_ascii_case("encoding")
+							&& (attribute.value.eq_ignore_ascii_case("text/html")
+								|| attribute
+									.value
+									.eq_ignore_ascii_case("application/xhtml+xml"))
+					})
+		}
+	}
+}
@@ -0,0 +1,270 @@
+//! Follows the HTML tokenizer through the content of raw text elements, to find text that would end them early.
+//!
+//! See <https://html.spec.whatwg.org/multipage/parsing.html#rawtext-state>,
+//! <https://html.spec.whatwg.org/multipage/parsing.html#script-data-state> and the states following each of them.
+
+use core::ops::Range;
+
+/// The tokenizer states that matter for finding end tags.
+///
+/// The ones starting with `Escaped` and `DoubleEscape` are only reachable in `script` elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+	/// The ***RAWTEXT state*** or ***script data state***.
+	Data,
+	LessThan,
+	EndTagOpen,
+	/// How much of the element name was matched so far.
+	EndTagName(usize),
+	EscapeStart,
+	EscapeStartDash,
+	Escaped,
+	EscapedDash,
+	EscapedDashDash,
+	EscapedLessThan,
+	EscapedEndTagOpen,
+	/// How much of the element name was matched so far.
+	EscapedEndTagName(usize),
+	/// How much of `script` was matched so far.
+	DoubleEscapeStart(usize),
+	DoubleEscaped,
+	DoubleEscapedDash,
+	DoubleEscapedDashDash,
+	DoubleEscapedLessThan,
+	/// How much of `script` was matched so far.
+	DoubleEscapeEnd(usize),
+}
+
+/// Tracks the tokenizer state across all [`Node::Text`](`lignin::Node::Text`)s in a raw text element,
+/// since the parser sees them concatenated.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RawTextTokenizer<'a> {
+	element_name: &'a str,
+	script: bool,
+	state: State,
+	/// How many bytes were fed so far.
+	position: usize,
+	/// Where the last `<` was fed.
+	tag_start: usize,
+}
+
+/// Ends tag names and double escape sequences.
+///
+/// `\r` is included since the parser normalizes it to `\n` first.
+const fn is_delimiter(byte: u8) -> bool {
+	matches!(byte, b'\t' | b'\n' | 0xC | b'\r' | b' ' | b'/' | b'>')
+}
+
+/// Whether `name` continues with `byte` after `matched` bytes.
+fn matches_ignoring_ascii_case(name: &[u8], matched: usize, byte: u8) -> bool {
+	name.get(matched).map(u8::to_ascii_lowercase) == Some(byte.to_ascii_lowercase())
+}
+
+/// Whether `rest`, the text after a `<`, could make that `<` begin the end tag of the element named `element_name`
+/// in the [***RCDATA state***](https://html.spec.whatwg.org/multipage/parsing.html#rcdata-state), if more text may follow it.
+pub(crate) fn may_start_end_tag(rest: &str, element_name: &str) -> bool {
+	let rest = rest.as_bytes();
+	let name = element_name.as_bytes();
+	match rest.split_first() {
+		None => true,
+		Some((b'/', rest)) => {
+			let matched = rest.len().min(name.len());
+			rest[..matched].eq_ignore_ascii_case(&name[..matched])
+				&& match rest.get(name.len()) {
+					None => true,
+					Some(&byte) => is_delimiter(byte),
+				}
+		}
+		Some(_) => false,
+	}
+}
+
+impl<'a> RawTextTokenizer<'a> {
+	pub(crate) fn new(element_name: &'a str) -> Self {
+		Self {
+			element_name,
+			script: element_name.eq_ignore_ascii_case("script"),
+			state: State::Data,
+			position: 0,
+			tag_start: 0,
+		}
+	}
+
+	pub(crate) const fn element_name(&self) -> &'a str {
+		self.element_name
+	}
+
+	/// Feeds `text` to the tokenizer.
+	///
+	/// # Errors
+	///
+	/// With the range in `text` of an end tag (start) that would close the element,
+	/// which starts at `0` if it began in earlier text.
+	pub(crate) fn feed(&mut self, text: &str) -> Result<(), Range<usize>> {
+		let text_start = self.position;
+		for (i, &byte) in text.as_bytes().iter().enumerate() {
+			if self.step(byte) {
+				return Err(self.tag_start.saturating_sub(text_start)..i + 1);
+			}
+		}
+		Ok(())
+	}
+
+	/// Whether the element's own end tag, written after all text fed so far, actually closes it.
+	///
+	/// This is only not the case in the middle of a `<!--<script>` section in a `script` element.
+	pub(crate) fn end_tag_closes(&self) -> bool {
+		let mut tokenizer = *self;
+		let end_tag = b"</".iter().chain(self.element_name.as_bytes()).chain(b">");
+		let mut closed = false;
+		for &byte in end_tag {
+			closed = tokenizer.step(byte);
+		}
+		closed
+	}
+
+	/// Consumes `byte`, returning whether it completes an end tag that closes the element.
+	#[allow(clippy::too_many_lines)]
+	fn step(&mut self, byte: u8) -> bool {
+		let position = self.position;
+		self.position += 1;
+		// Each `continue` reconsumes `byte` in the new state.
+		loop {
+			self.state = match self.state {
+				State::Data => match byte {
+					b'<' => {
+						self.tag_start = position;
+						State::LessThan
+					}
+					_ => State::Data,
+				},
+				State::LessThan => match byte {
+					b'/' => State::EndTagOpen,
+					b'!' if self.script => State::EscapeStart,
+					_ => {
+						self.state = State::Data;
+						continue;
+					}
+				},
+				State::EndTagOpen => {
+					self.state = if byte.is_ascii_alphabetic() {
+						State::EndTagName(0)
+					} else {
+						State::Data
+					};
+					continue;
+				}
+				State::EndTagName(matched) => match self.end_tag_name(matched, byte) {
+					Some(Ok(matched)) => State::EndTagName(matched),
+					Some(Err(())) => return true,
+					None => {
+						self.state = State::Data;
+						continue;
+					}
+				},
+
+				State::EscapeStart | State::EscapeStartDash if byte != b'-' => {
+					self.state = State::Data;
+					continue;
+				}
+				State::EscapeStart => State::EscapeStartDash,
+				State::EscapeStartDash => State::EscapedDashDash,
+				State::Escaped | State::EscapedDash | State::EscapedDashDash => match byte {
+					b'-' if self.state == State::Escaped => State::EscapedDash,
+					b'-' => State::EscapedDashDash,
+					b'<' => {
+						self.tag_start = position;
+						State::EscapedLessThan
+					}
+					b'>' if self.state == State::EscapedDashDash => State::Data,
+					_ => State::Escaped,
+				},
+				State::EscapedLessThan if byte == b'/' => State::EscapedEndTagOpen,
+				State::EscapedLessThan => {
+					self.state = if byte.is_ascii_alphabetic() {
+						State::DoubleEscapeStart(0)
+					} else {
+						State::Escaped
+					};
+					continue;
+				}
+				State::EscapedEndTagOpen => {
+					self.state = if byte.is_ascii_alphabetic() {
+						State::EscapedEndTagName(0)
+					} else {
+						State::Escaped
+					};
+					continue;
+				}
+				State::EscapedEndTagName(matched) => match self.end_tag_name(matched, byte) {
+					Some(Ok(matched)) => State::EscapedEndTagName(matched),
+					Some(Err(())) => return true,
+					None => {
+						self.state = State::Escaped;
+						continue;
+					}
+				},
+				State::DoubleEscapeStart(matched) | State::DoubleEscapeEnd(matched) => {
+					let start = matches!(self.state, State::DoubleEscapeStart(_));
+					let (on_match, otherwise) = if start {
+						(State::DoubleEscaped, State::Escaped)
+					} else {
+						(State::Escaped, State::DoubleEscaped)
+					};
+					if is_delimiter(byte) {
+						if matched == b"script".len() {
+							on_match
+						} else {
+							otherwise
+						}
+					} else if matches_ignoring_ascii_case(b"script", matched, byte) {
+						if start {
+							State::DoubleEscapeStart(matched + 1)
+						} else {
+							State::DoubleEscapeEnd(matched + 1)
+						}
+					} else if byte.is_ascii_alphabetic() {
+						// The name can't match anymore, and further letters don't do anything in `otherwise`.
+						otherwise
+					} else {
+						self.state = otherwise;
+						continue;
+					}
+				}
+
+				State::DoubleEscaped | State::DoubleEscapedDash | State::DoubleEscapedDashDash => {
+					match byte {
+						b'-' if self.state == State::DoubleEscaped => State::DoubleEscapedDash,
+						b'-' => State::DoubleEscapedDashDash,
+						b'<' => State::DoubleEscapedLessThan,
+						b'>' if self.state == State::DoubleEscapedDashDash => State::Data,
+						_ => State::DoubleEscaped,
+					}
+				}
+				State::DoubleEscapedLessThan if byte == b'/' => State::DoubleEscapeEnd(0),
+				State::DoubleEscapedLessThan => {
+					self.state = State::DoubleEscaped;
+					continue;
+				}
+			};
+			return false;
+		}
+	}
+
+	/// Continues matching the element name in an end tag after `matched` bytes of it.
+	///
+	/// Unlike in the parser, non-letters in the name are matched too, so that end tags of custom
+	/// [`RenderOptions::extra_raw_text_elements`](`crate::RenderOptions::extra_raw_text_elements`) are still caught.
+	///
+	/// [`None`] means `byte` should be reconsumed outside the end tag and `Some(Err(()))` that the element is closed.
+	fn end_tag_name(&self, matched: usize, byte: u8) -> Option<Result<usize, ()>> {
+		let name = self.element_name.as_bytes();
+		if matches_ignoring_ascii_case(name, matched, byte) {
+			Some(Ok(matched + 1))
+		} else if matched == name.len() && is_delimiter(byte) {
+			Some(Err(()))
+		} else {
+			None
+		}
+	}
+}
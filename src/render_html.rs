@@ -0,0 +1,62 @@
+//! An extension point for types that can be rendered as HTML fragments.
+
+use crate::{render_fragment, Error};
+use core::fmt::Write;
+use lignin::{Node, ThreadSafety};
+
+/// Something that can be rendered as HTML fragment, like a [`Node`].
+///
+/// Implement this for your own types to compose them with [`Node`]s.
+/// The trait is object-safe, so e.g. `&dyn RenderHtml<'a, S>` works too.
+///
+/// ```
+/// use core::fmt::Write;
+/// use lignin_html::{lignin::{Node, ThreadSafe}, Error, RenderHtml};
+///
+/// enum Content<'a> {
+///     Vdom(Node<'a, ThreadSafe>),
+///     Counter(usize),
+/// }
+///
+/// impl<'a> RenderHtml<'a, ThreadSafe> for Content<'a> {
+///     fn render_html(
+///         &'a self,
+///         target: &mut dyn Write,
+///         depth_limit: usize,
+///     ) -> Result<(), Error<'a, ThreadSafe>> {
+///         match self {
+///             Content::Vdom(vdom) => vdom.render_html(target, depth_limit),
+///             Content::Counter(count) => Ok(write!(target, "{}", count)?),
+///         }
+///     }
+/// }
+///
+/// let mut html = String::new();
+/// Content::Counter(3).render_html(&mut html, 1).unwrap();
+/// assert_eq!(html, "3");
+/// ```
+pub trait RenderHtml<'a, S: ThreadSafety> {
+	/// Renders `self` into `target` as HTML fragment, nested at most `depth_limit` deep.
+	///
+	/// # Errors
+	///
+	/// Iff `self` is found to represent invalid HTML or writing to `target` fails.
+	///
+	/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+	fn render_html(
+		&'a self,
+		target: &mut dyn Write,
+		depth_limit: usize,
+	) -> Result<(), Error<'a, S>>;
+}
+
+/// Delegates to [`render_fragment`].
+impl<'a, S: ThreadSafety> RenderHtml<'a, S> for Node<'a, S> {
+	fn render_html(
+		&'a self,
+		mut target: &mut dyn Write,
+		depth_limit: usize,
+	) -> Result<(), Error<'a, S>> {
+		render_fragment(self, &mut target, depth_limit)
+	}
+}
@@ -0,0 +1,72 @@
+//! Listing what [`RenderOptions::roundtrip_safe`] inserted.
+
+use crate::{
+	mapping::{Mapping, PositionWriter},
+	render_node, Context, Depth, Error, RenderOptions, Scope,
+};
+use alloc::vec::Vec;
+use core::{cell::RefCell, fmt::Write, ops::Range};
+use lignin::{Node, ThreadSafety};
+
+/// Where [`render_fragment_roundtrip_safe`] changed its output, so that a reader can undo it. Ranges are in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct RoundtripManifest {
+	/// The empty comments (`<!---->`) inserted between adjacent text nodes, in order.
+	///
+	/// See [`RenderOptions::roundtrip_safe`].
+	pub boundaries: Vec<Range<usize>>,
+	/// The comments whose text had to be changed to render them, in order, so they don't parse as the original [`Node::Comment`]s.
+	///
+	/// See [`CommentPolicy::Legalize`](`crate::CommentPolicy::Legalize`).
+	pub legalized_comments: Vec<Range<usize>>,
+}
+
+/// Like [`render_fragment`](`crate::render_fragment`), but with [`RenderOptions::roundtrip_safe`] enabled,
+/// and also returns where the output differs from a plain rendering of `vdom`.
+///
+/// ```
+/// use lignin_html::{lignin::Node, render_fragment_roundtrip_safe};
+///
+/// let vdom = Node::Multi(&[
+///     Node::Text { text: "a", dom_binding: None },
+///     Node::Text { text: "b", dom_binding: None },
+/// ])
+/// .prefer_thread_safe();
+///
+/// let mut html = String::new();
+/// let manifest = render_fragment_roundtrip_safe(&vdom, &mut html, 1).unwrap();
+/// assert_eq!(html, "a<!---->b");
+/// assert_eq!(manifest.boundaries, [1..8]);
+/// ```
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_roundtrip_safe<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<RoundtripManifest, Error<'a, S>> {
+	// Only used for the positions.
+	let mapping = Mapping::new();
+	let manifest = RefCell::default();
+	render_node(
+		vdom,
+		&mut PositionWriter::new(target, &mapping),
+		Depth::top_level(depth_limit),
+		&Context {
+			mapping: Some(&mapping),
+			manifest: Some(&manifest),
+			..Context::new(
+				&RenderOptions::new()
+					.with_depth_limit(depth_limit)
+					.with_roundtrip_safe(true),
+			)
+		},
+		Scope::TOP_LEVEL,
+	)?;
+	Ok(manifest.into_inner())
+}
@@ -0,0 +1,121 @@
+//! Rendering into targets that fail with their own error type.
+
+use crate::{render_fragment_with_options, Error, ErrorKind, RenderOptions};
+use core::fmt::{self, Write};
+use lignin::{Node, ThreadSafety};
+
+/// A target for rendered HTML that can fail with a richer error than [`fmt::Error`].
+///
+/// Every [`Write`] is an [`HtmlSink`] with [`fmt::Error`] as error type,
+/// so [`render_fragment_to_sink`] can be used with them too.
+pub trait HtmlSink {
+	/// The error writing to this sink can fail with.
+	type Error;
+
+	/// Appends `s` to the sink.
+	///
+	/// # Errors
+	///
+	/// Iff the sink can't accept `s`.
+	fn write_html(&mut self, s: &str) -> Result<(), Self::Error>;
+}
+
+impl<W: Write + ?Sized> HtmlSink for W {
+	type Error = fmt::Error;
+
+	fn write_html(&mut self, s: &str) -> fmt::Result {
+		self.write_str(s)
+	}
+}
+
+/// An error returned by [`render_fragment_to_sink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkError<'a, S: ThreadSafety, E> {
+	/// The [`Node`] couldn't be rendered.
+	Render(Error<'a, S>),
+	/// The [`HtmlSink`] failed.
+	Sink(E),
+}
+
+impl<S: ThreadSafety, E: fmt::Display> fmt::Display for SinkError<'_, S, E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SinkError::Render(error) => error.fmt(f),
+			SinkError::Sink(error) => error.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<S: ThreadSafety, E: std::error::Error + 'static> std::error::Error for SinkError<'_, S, E> {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			SinkError::Render(error) => std::error::Error::source(error),
+			SinkError::Sink(error) => Some(error),
+		}
+	}
+}
+
+/// An [`HtmlSink`] that writes UTF-8 to a [`std::io::Write`], failing with its [`std::io::Error`].
+///
+/// ```
+/// use lignin_html::{lignin::Node, render_fragment_to_sink, IoSink, RenderOptions};
+///
+/// let mut sink = IoSink(Vec::new());
+/// render_fragment_to_sink(
+///     &Node::Text { text: "<3", dom_binding: None }.prefer_thread_safe(),
+///     &mut sink,
+///     &RenderOptions::new(),
+/// ).unwrap();
+/// assert_eq!(sink.0, b"&lt;3");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IoSink<W: std::io::Write>(pub W);
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> HtmlSink for IoSink<W> {
+	type Error = std::io::Error;
+
+	fn write_html(&mut self, s: &str) -> std::io::Result<()> {
+		self.0.write_all(s.as_bytes())
+	}
+}
+
+/// Forwards to an [`HtmlSink`], keeping its first error instead of just failing with [`fmt::Error`].
+struct Adapter<'s, W: HtmlSink + ?Sized> {
+	sink: &'s mut W,
+	error: Option<W::Error>,
+}
+
+impl<W: HtmlSink + ?Sized> Write for Adapter<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.sink.write_html(s).map_err(|error| {
+			self.error = Some(error);
+			fmt::Error
+		})
+	}
+}
+
+/// Renders `vdom` into `sink` as HTML fragment, like [`render_fragment_with_options`],
+/// but surfaces the sink's own error if writing fails.
+///
+/// # Errors
+///
+/// [`SinkError::Sink`] iff writing to `sink` fails, otherwise [`SinkError::Render`] iff `vdom` is found to represent invalid HTML
+/// or is nested deeper than [`RenderOptions::depth_limit`].
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_to_sink<'a, S: ThreadSafety, W: HtmlSink + ?Sized>(
+	vdom: &'a Node<'a, S>,
+	sink: &mut W,
+	options: &RenderOptions,
+) -> Result<(), SinkError<'a, S, W::Error>> {
+	let mut adapter = Adapter { sink, error: None };
+	render_fragment_with_options(vdom, &mut adapter, options).map_err(|error| {
+		match (error, adapter.error.take()) {
+			(Error(ErrorKind::FmtError(_)), Some(sink_error)) => SinkError::Sink(sink_error),
+			(error, _) => SinkError::Render(error),
+		}
+	})
+}
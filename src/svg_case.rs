@@ -0,0 +1,122 @@
+//! The canonical case of SVG names that HTML parsers lowercase, for [`RenderOptions::normalize_svg_case`](`crate::RenderOptions::normalize_svg_case`).
+
+/// See <https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inforeign>.
+const ELEMENT_NAMES: &[&str] = &[
+	"altGlyph",
+	"altGlyphDef",
+	"altGlyphItem",
+	"animateColor",
+	"animateMotion",
+	"animateTransform",
+	"clipPath",
+	"feBlend",
+	"feColorMatrix",
+	"feComponentTransfer",
+	"feComposite",
+	"feConvolveMatrix",
+	"feDiffuseLighting",
+	"feDisplacementMap",
+	"feDistantLight",
+	"feDropShadow",
+	"feFlood",
+	"feFuncA",
+	"feFuncB",
+	"feFuncG",
+	"feFuncR",
+	"feGaussianBlur",
+	"feImage",
+	"feMerge",
+	"feMergeNode",
+	"feMorphology",
+	"feOffset",
+	"fePointLight",
+	"feSpecularLighting",
+	"feSpotLight",
+	"feTile",
+	"feTurbulence",
+	"foreignObject",
+	"glyphRef",
+	"linearGradient",
+	"radialGradient",
+	"textPath",
+];
+
+/// See <https://html.spec.whatwg.org/multipage/parsing.html#adjust-svg-attributes>.
+const ATTRIBUTE_NAMES: &[&str] = &[
+	"attributeName",
+	"attributeType",
+	"baseFrequency",
+	"baseProfile",
+	"calcMode",
+	"clipPathUnits",
+	"diffuseConstant",
+	"edgeMode",
+	"filterUnits",
+	"glyphRef",
+	"gradientTransform",
+	"gradientUnits",
+	"kernelMatrix",
+	"kernelUnitLength",
+	"keyPoints",
+	"keySplines",
+	"keyTimes",
+	"lengthAdjust",
+	"limitingConeAngle",
+	"markerHeight",
+	"markerUnits",
+	"markerWidth",
+	"maskContentUnits",
+	"maskUnits",
+	"numOctaves",
+	"pathLength",
+	"patternContentUnits",
+	"patternTransform",
+	"patternUnits",
+	"pointsAtX",
+	"pointsAtY",
+	"pointsAtZ",
+	"preserveAlpha",
+	"preserveAspectRatio",
+	"primitiveUnits",
+	"refX",
+	"refY",
+	"repeatCount",
+	"repeatDur",
+	"requiredExtensions",
+	"requiredFeatures",
+	"specularConstant",
+	"specularExponent",
+	"spreadMethod",
+	"startOffset",
+	"stdDeviation",
+	"stitchTiles",
+	"surfaceScale",
+	"systemLanguage",
+	"tableValues",
+	"targetX",
+	"targetY",
+	"textLength",
+	"viewBox",
+	"viewTarget",
+	"xChannelSelector",
+	"yChannelSelector",
+	"zoomAndPan",
+];
+
+/// Finds the entry of `known` that's ASCII-case-insensitively equal to `name`, or else returns `name` unchanged.
+fn canonical<'a>(known: &'static [&'static str], name: &'a str) -> &'a str {
+	known
+		.iter()
+		.find(|known| known.eq_ignore_ascii_case(name))
+		.map_or(name, |&known| known)
+}
+
+/// The canonical case of the SVG element name `name`, e.g. `linearGradient` for `lineargradient`.
+pub(crate) fn element_name(name: &str) -> &str {
+	canonical(ELEMENT_NAMES, name)
+}
+
+/// The canonical case of the SVG attribute name `name`, e.g. `viewBox` for `viewbox`.
+pub(crate) fn attribute_name(name: &str) -> &str {
+	canonical(ATTRIBUTE_NAMES, name)
+}
@@ -0,0 +1,35 @@
+//! Writing the same output to two targets.
+
+use core::fmt::{self, Write};
+
+/// A [`Write`] that forwards everything written to it to both `.0` and `.1`, in that order.
+///
+/// ```
+/// use lignin_html::{lignin::Node, render_fragment, Tee};
+///
+/// let mut response = String::new();
+/// let mut log = String::new();
+/// render_fragment(
+///     &Node::Text { text: "Hello!", dom_binding: None }.prefer_thread_safe(),
+///     &mut Tee(&mut response, &mut log),
+///     1,
+/// ).unwrap();
+/// assert_eq!(response, "Hello!");
+/// assert_eq!(log, "Hello!");
+/// ```
+///
+/// If writing to `.0` fails, nothing is written to `.1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Tee<A: Write, B: Write>(pub A, pub B);
+
+impl<A: Write, B: Write> Write for Tee<A, B> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0.write_str(s)?;
+		self.1.write_str(s)
+	}
+
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		self.0.write_char(c)?;
+		self.1.write_char(c)
+	}
+}
@@ -0,0 +1,146 @@
+//! Helpers for testing rendered HTML without depending on incidental formatting.
+//!
+//! Only available with the `"testing"` feature.
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+/// Asserts that `actual` and `expected` are the same HTML, apart from attribute order, attribute quoting and insignificant whitespace.
+///
+/// See [`normalize_html`] for what exactly is ignored.
+///
+/// # Panics
+///
+/// Iff the normalized HTML differs. The panic message shows both normalized versions.
+#[track_caller]
+pub fn assert_html_eq(actual: &str, expected: &str) {
+	let actual = normalize_html(actual);
+	let expected = normalize_html(expected);
+	assert!(
+		actual == expected,
+		"HTML differs after normalization:\n  actual: {}\nexpected: {}",
+		actual,
+		expected
+	);
+}
+
+/// Normalizes `html` for comparison, as used by [`assert_html_eq`]:
+///
+/// - Attributes are sorted by name and written as `name="value"` (or just `name`, if the value is empty).
+///   `"` in values is written as `&quot;`, regardless of the original quoting.
+/// - Whitespace inside tags is reduced to single spaces.
+/// - Text consisting only of whitespace is removed. Other runs of whitespace in text are collapsed into one space.
+/// - Comments and [***DOCTYPE***](https://html.spec.whatwg.org/multipage/syntax.html#the-doctype)s are kept as-is.
+///
+/// Character references aren't decoded and element names aren't case-folded.
+/// The content of raw text elements is treated like any other text.
+#[must_use]
+pub fn normalize_html(html: &str) -> String {
+	let mut normalized = String::new();
+	let mut rest = html;
+	while !rest.is_empty() {
+		if rest.starts_with("<!--") {
+			let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+			normalized.push_str(&rest[..end]);
+			rest = &rest[end..];
+		} else if rest.starts_with("<!") || rest.starts_with("</") {
+			let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+			normalized.push_str(&rest[..end]);
+			rest = &rest[end..];
+		} else if rest.starts_with('<') {
+			rest = normalize_start_tag(&rest[1..], &mut normalized);
+		} else {
+			let end = rest.find('<').unwrap_or(rest.len());
+			let text = &rest[..end];
+			if !text.trim_matches(is_ascii_whitespace).is_empty() {
+				let mut words = text.split(is_ascii_whitespace).peekable();
+				while let Some(word) = words.next() {
+					normalized.push_str(word);
+					if words.peek().is_some() {
+						// Collapses runs, since the words in-between are empty.
+						if !word.is_empty() || !normalized.ends_with(' ') {
+							normalized.push(' ')
+						}
+					}
+				}
+			}
+			rest = &rest[end..];
+		}
+	}
+	normalized
+}
+
+/// See <https://infra.spec.whatwg.org/#ascii-whitespace>.
+fn is_ascii_whitespace(c: char) -> bool {
+	matches!(c, '\t' | '\n' | '\u{C}' | '\r' | ' ')
+}
+
+/// Normalizes the start tag at the beginning of `rest` (after its `<`) into `normalized` and returns what remains after it.
+fn normalize_start_tag<'a>(rest: &'a str, normalized: &mut String) -> &'a str {
+	let name_end = rest
+		.find(|c| is_ascii_whitespace(c) || c == '/' || c == '>')
+		.unwrap_or(rest.len());
+	normalized.push('<');
+	normalized.push_str(&rest[..name_end]);
+	let mut rest = &rest[name_end..];
+
+	let mut attributes = Vec::new();
+	let mut self_closing = false;
+	loop {
+		rest = rest.trim_start_matches(is_ascii_whitespace);
+		if rest.is_empty() {
+			break;
+		} else if let Some(after) = rest.strip_prefix("/>") {
+			self_closing = true;
+			rest = after;
+			break;
+		} else if let Some(after) = rest.strip_prefix('>') {
+			rest = after;
+			break;
+		} else if let Some(after) = rest.strip_prefix('/') {
+			rest = after;
+			continue;
+		}
+
+		let name_end = rest
+			.find(|c| is_ascii_whitespace(c) || c == '/' || c == '>' || c == '=')
+			.unwrap_or(rest.len());
+		let name = &rest[..name_end];
+		rest = rest[name_end..].trim_start_matches(is_ascii_whitespace);
+
+		let mut value = String::new();
+		if let Some(after) = rest.strip_prefix('=') {
+			rest = after.trim_start_matches(is_ascii_whitespace);
+			let (raw, after) = if let Some(quote @ ('"' | '\'')) = rest.chars().next() {
+				let end = rest[1..].find(quote).map_or(rest.len(), |end| end + 1);
+				(&rest[1..end], rest.get(end + 1..).unwrap_or(""))
+			} else {
+				let end = rest
+					.find(|c| is_ascii_whitespace(c) || c == '>')
+					.unwrap_or(rest.len());
+				(&rest[..end], &rest[end..])
+			};
+			value = raw.replace('"', "&quot;");
+			rest = after;
+		}
+		attributes.push((name.to_string(), value));
+	}
+
+	attributes.sort();
+	for (name, value) in attributes {
+		normalized.push(' ');
+		normalized.push_str(&name);
+		if !value.is_empty() {
+			normalized.push_str("=\"");
+			normalized.push_str(&value);
+			normalized.push('"');
+		}
+	}
+	if self_closing {
+		normalized.push_str(" /");
+	}
+	normalized.push('>');
+	rest
+}
@@ -0,0 +1,110 @@
+//! Non-fatal observations about rendered [`Node`]s.
+
+use crate::{mapping::Mapping, render_node, Context, Depth, Error, NodePath, RenderOptions, Scope};
+use alloc::vec::Vec;
+use core::{
+	cell::RefCell,
+	fmt::{self, Display, Write},
+};
+use lignin::{Node, ThreadSafety};
+
+/// Something about a rendered [`Node`] that's likely not what was intended, but isn't an error, as returned by [`render_fragment_with_warnings`].
+///
+/// Each variant contains the [`NodePath`] of the [`Node`] it's about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+	/// A [`Node::Comment`]'s text had to be changed to render it, so it won't appear as-is in the DOM.
+	///
+	/// See [`CommentPolicy::Legalize`](`crate::CommentPolicy::Legalize`).
+	CommentLegalized(NodePath),
+	/// An element's [`event_bindings`](`lignin::Element::event_bindings`) were dropped, since they can't be rendered as HTML.
+	EventBindingsIgnored(NodePath),
+	/// A top-level `html` element has no `lang` attribute.
+	///
+	/// This is an error with [`RenderOptions::require_html_lang`].
+	MissingLangAttribute(NodePath),
+	/// An HTML `img` element has no `alt` attribute.
+	///
+	/// This is an error with [`RenderOptions::require_img_alt`].
+	MissingAltAttribute(NodePath),
+}
+
+impl Warning {
+	/// The [`NodePath`] of the [`Node`] this warning is about.
+	#[must_use]
+	pub fn path(&self) -> &NodePath {
+		match self {
+			Warning::CommentLegalized(path)
+			| Warning::EventBindingsIgnored(path)
+			| Warning::MissingLangAttribute(path)
+			| Warning::MissingAltAttribute(path) => path,
+		}
+	}
+}
+
+impl Display for Warning {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Warning::CommentLegalized(path) => {
+				write!(f, "The comment at {:?} was changed to render it", path)
+			}
+			Warning::EventBindingsIgnored(path) => write!(
+				f,
+				"The event bindings of the element at {:?} can't be rendered",
+				path
+			),
+			Warning::MissingLangAttribute(path) => write!(
+				f,
+				"The `html` element at {:?} has no `lang` attribute",
+				path
+			),
+			Warning::MissingAltAttribute(path) => {
+				write!(f, "The `img` element at {:?} has no `alt` attribute", path)
+			}
+		}
+	}
+}
+
+/// Like [`render_fragment`](`crate::render_fragment`), but also returns [`Warning`]s about parts of `vdom` that rendered,
+/// but likely not as intended, e.g. to catch them in CI without failing the render.
+///
+/// Warnings are in the order the [`Node`]s they're about were rendered.
+///
+/// ```
+/// use lignin_html::{lignin::Node, render_fragment_with_warnings, Warning};
+///
+/// let vdom = Node::Comment { comment: "a-->b", dom_binding: None }.prefer_thread_safe();
+///
+/// let mut html = String::new();
+/// let warnings = render_fragment_with_warnings(&vdom, &mut html, 1).unwrap();
+/// assert_eq!(html, "<!--a==>b-->");
+/// assert_eq!(warnings, [Warning::CommentLegalized(vec![])]);
+/// ```
+///
+/// # Errors
+///
+/// Iff `vdom` is found to represent invalid HTML.
+///
+/// > **Warning:** This function succeeding does not guarantee that the produced HTML is fully valid!
+pub fn render_fragment_with_warnings<'a, S: ThreadSafety>(
+	vdom: &'a Node<'a, S>,
+	target: &mut impl Write,
+	depth_limit: usize,
+) -> Result<Vec<Warning>, Error<'a, S>> {
+	// Only used for the paths.
+	let mapping = Mapping::new();
+	let warnings = RefCell::default();
+	render_node(
+		vdom,
+		target,
+		Depth::top_level(depth_limit),
+		&Context {
+			mapping: Some(&mapping),
+			warnings: Some(&warnings),
+			..Context::new(&RenderOptions::new().with_depth_limit(depth_limit))
+		},
+		Scope::TOP_LEVEL,
+	)?;
+	Ok(warnings.into_inner())
+}
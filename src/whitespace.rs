@@ -0,0 +1,120 @@
+//! How whitespace around and inside HTML elements affects rendering.
+
+/// How whitespace in and around an HTML element affects rendering, as returned by [`whitespace_sensitivity`].
+///
+/// This is meant for formatting tools like minifiers and pretty-printers, which must agree on where whitespace may be changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WhitespaceSensitivity {
+	/// All whitespace in the element's content is significant and must be kept exactly as is.
+	///
+	/// `pre`, `listing`, `plaintext`, `textarea` and `xmp`, as well as `script` and `style`, whose content isn't HTML.
+	Pre,
+	/// The element flows with the text around it, so whitespace next to and inside it may be visible (but can be collapsed).
+	///
+	/// Phrasing content like `a`, `b`, `span` or `img`, and any element not listed elsewhere, including custom elements.
+	Inline,
+	/// Whitespace directly around and at the start and end of the element's content is insignificant.
+	///
+	/// Sectioning and grouping content like `div`, `p`, `ul`, `li`, `table` or `h1`, and metadata like `head`, `meta` or `title`.
+	Block,
+}
+
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#element-restrictions> and
+/// <https://html.spec.whatwg.org/multipage/rendering.html#flow-content-3>.
+const PRE: &[&str] = &[
+	"listing",
+	"plaintext",
+	"pre",
+	"script",
+	"style",
+	"textarea",
+	"xmp",
+];
+
+/// See <https://html.spec.whatwg.org/multipage/rendering.html#the-css-user-agent-style-sheet-and-presentational-hints>.
+const BLOCK: &[&str] = &[
+	"address",
+	"article",
+	"aside",
+	"base",
+	"blockquote",
+	"body",
+	"caption",
+	"center",
+	"col",
+	"colgroup",
+	"dd",
+	"details",
+	"dialog",
+	"dir",
+	"div",
+	"dl",
+	"dt",
+	"fieldset",
+	"figcaption",
+	"figure",
+	"footer",
+	"form",
+	"h1",
+	"h2",
+	"h3",
+	"h4",
+	"h5",
+	"h6",
+	"head",
+	"header",
+	"hgroup",
+	"hr",
+	"html",
+	"legend",
+	"li",
+	"link",
+	"main",
+	"menu",
+	"meta",
+	"nav",
+	"ol",
+	"optgroup",
+	"option",
+	"p",
+	"search",
+	"section",
+	"summary",
+	"table",
+	"tbody",
+	"td",
+	"template",
+	"tfoot",
+	"th",
+	"thead",
+	"title",
+	"tr",
+	"ul",
+];
+
+/// Classifies the HTML element named `element_name` (ASCII-case-insensitively) by how whitespace affects its rendering.
+///
+/// See [`WhitespaceSensitivity`] for the elements in each class.
+/// This is based on default styles, so CSS can change what's actually visible.
+///
+/// ```
+/// use lignin_html::{whitespace_sensitivity, WhitespaceSensitivity};
+///
+/// assert_eq!(whitespace_sensitivity("PRE"), WhitespaceSensitivity::Pre);
+/// assert_eq!(whitespace_sensitivity("x-widget"), WhitespaceSensitivity::Inline);
+/// ```
+#[must_use]
+pub fn whitespace_sensitivity(element_name: &str) -> WhitespaceSensitivity {
+	let is_in = |names: &[&str]| {
+		names
+			.iter()
+			.any(|name| name.eq_ignore_ascii_case(element_name))
+	};
+	if is_in(PRE) {
+		WhitespaceSensitivity::Pre
+	} else if is_in(BLOCK) {
+		WhitespaceSensitivity::Block
+	} else {
+		WhitespaceSensitivity::Inline
+	}
+}
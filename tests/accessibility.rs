@@ -0,0 +1,111 @@
+mod common;
+
+use common::{html_element, render_with};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{render_document_with_options, RenderOptions};
+
+fn html(attributes: &'static [Attribute<'static>]) -> Node<'static, ThreadSafe> {
+	html_element("html", attributes, Node::Multi(&[]))
+}
+
+fn render_document<'a>(vdom: &'a Node<'a, ThreadSafe>) -> Result<String, String> {
+	let mut document = String::new();
+	render_document_with_options(
+		vdom,
+		&mut document,
+		&RenderOptions::new().with_require_html_lang(true),
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(document)
+}
+
+#[test]
+fn html_without_lang() {
+	assert_eq!(
+		render_document(&html(&[])).unwrap_err(),
+		"a `<html>` element is missing a `lang` attribute"
+	);
+}
+
+#[test]
+fn html_with_lang() {
+	assert_eq!(
+		render_document(&html(&[Attribute {
+			name: "lang",
+			value: "en",
+		}]))
+		.unwrap(),
+		"<!DOCTYPE html><html lang=en></html>"
+	);
+}
+
+#[test]
+fn nested_html_is_not_checked() {
+	let vdom = html_element("div", &[], html(&[]));
+	assert_eq!(
+		render_document(&vdom).unwrap(),
+		"<!DOCTYPE html><div><html></html></div>"
+	);
+}
+
+#[test]
+fn lang_not_required_by_default() {
+	let mut document = String::new();
+	render_document_with_options(&html(&[]), &mut document, &RenderOptions::new()).unwrap();
+	assert_eq!(document, "<!DOCTYPE html><html></html>");
+}
+
+fn render_img<'a>(attributes: &'a [Attribute<'a>]) -> Result<String, String> {
+	render_with(
+		&html_element("img", attributes, Node::Multi(&[])),
+		&RenderOptions::new().with_require_img_alt(true),
+	)
+}
+
+#[test]
+fn img_without_alt() {
+	assert_eq!(
+		render_img(&[Attribute {
+			name: "src",
+			value: "x",
+		}])
+		.unwrap_err(),
+		"a `<img>` element is missing an `alt` attribute"
+	);
+}
+
+#[test]
+fn img_with_empty_alt() {
+	assert_eq!(
+		render_img(&[
+			Attribute {
+				name: "src",
+				value: "x",
+			},
+			Attribute {
+				name: "alt",
+				value: "",
+			},
+		])
+		.unwrap(),
+		"<img src=x alt>"
+	);
+}
+
+#[test]
+fn img_with_alt() {
+	assert_eq!(
+		render_img(&[
+			Attribute {
+				name: "src",
+				value: "x",
+			},
+			Attribute {
+				name: "ALT",
+				value: "a cat",
+			},
+		])
+		.unwrap(),
+		r#"<img src=x ALT="a cat">"#
+	);
+}
@@ -0,0 +1,113 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render<'a>(vdom: &'a Node<'a, ThreadSafe>) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		vdom,
+		&mut fragment,
+		&RenderOptions::new().with_ascii_only(true),
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn text() {
+	assert_eq!(
+		render(&Node::Text {
+			text: "café 🦀 & <",
+			dom_binding: None,
+		})
+		.unwrap(),
+		"caf&#xe9; &#x1f980; &amp; &lt;"
+	);
+}
+
+#[test]
+fn attribute_value() {
+	assert_eq!(
+		render(&Node::HtmlElement {
+			element: &Element {
+				name: "p",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[Attribute {
+					name: "title",
+					value: "café",
+				}],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		})
+		.unwrap(),
+		"<p title=caf&#xe9;></p>"
+	);
+}
+
+#[test]
+fn escapable_raw_text() {
+	assert_eq!(
+		render(&Node::HtmlElement {
+			element: &Element {
+				name: "title",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text: "café",
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		})
+		.unwrap(),
+		"<title>caf&#xe9;</title>"
+	);
+}
+
+#[test]
+fn raw_text() {
+	assert_eq!(
+		render(&Node::HtmlElement {
+			element: &Element {
+				name: "script",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text: "alert('café')",
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		})
+		.unwrap_err(),
+		"Non-ASCII characters in \"alert('café')\" can't be escaped in ASCII-only mode"
+	);
+}
+
+#[test]
+fn comment() {
+	render(&Node::Comment {
+		comment: "café",
+		dom_binding: None,
+	})
+	.unwrap_err();
+}
+
+#[test]
+fn off_by_default() {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::Text {
+			text: "café",
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		&RenderOptions::new(),
+	)
+	.unwrap();
+	assert_eq!(fragment, "café");
+}
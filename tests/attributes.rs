@@ -0,0 +1,240 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment, render_svg_document};
+
+fn div_with_attributes(attributes: &[Attribute]) -> String {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "DIV",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		2,
+	)
+	.unwrap();
+	fragment
+}
+
+fn use_with_attributes(attributes: &[Attribute]) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::SvgElement {
+			element: &Element {
+				name: "use",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		2,
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn single_quoted() {
+	assert_eq!(
+		div_with_attributes(&[Attribute {
+			name: "title",
+			value: "say \"hi\" & leave",
+		}]),
+		"<DIV title='say \"hi\" &amp; leave'></DIV>"
+	);
+}
+
+#[test]
+fn double_quoted() {
+	assert_eq!(
+		div_with_attributes(&[Attribute {
+			name: "title",
+			value: "it's here",
+		}]),
+		"<DIV title=\"it's here\"></DIV>"
+	);
+}
+
+#[test]
+fn mixed_quotes() {
+	assert_eq!(
+		div_with_attributes(&[Attribute {
+			name: "title",
+			value: "it's \"here\"",
+		}]),
+		"<DIV title=\"it's &quot;here&quot;\"></DIV>"
+	);
+}
+
+#[test]
+fn xlink_href() {
+	assert_eq!(
+		use_with_attributes(&[Attribute {
+			name: "xlink:href",
+			value: "#shape",
+		}])
+		.unwrap(),
+		"<use xlink:href=#shape />"
+	);
+}
+
+#[test]
+fn xml_space() {
+	assert_eq!(
+		use_with_attributes(&[Attribute {
+			name: "xml:space",
+			value: "preserve",
+		}])
+		.unwrap(),
+		"<use xml:space=preserve />"
+	);
+}
+
+#[test]
+fn unknown_foreign_prefix() {
+	use_with_attributes(&[Attribute {
+		name: "foo:href",
+		value: "#shape",
+	}])
+	.unwrap_err();
+	use_with_attributes(&[Attribute {
+		name: "xlink:",
+		value: "#shape",
+	}])
+	.unwrap_err();
+}
+
+#[test]
+fn html_colon() {
+	assert_eq!(
+		div_with_attributes(&[Attribute {
+			name: "x-on:click",
+			value: "open",
+		}]),
+		"<DIV x-on:click=open></DIV>"
+	);
+}
+
+#[test]
+fn empty_name() {
+	for name in &["", " ", "\t"] {
+		let mut fragment = String::new();
+		let error = render_fragment(
+			&Node::HtmlElement {
+				element: &Element {
+					name: "DIV",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[Attribute { name, value: "x" }],
+					content: Node::Multi(&[]),
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			}
+			.prefer_thread_safe(),
+			&mut fragment,
+			2,
+		)
+		.unwrap_err()
+		.to_string();
+		assert_eq!(error, format!("Invalid attribute name {:?}", name));
+	}
+}
+
+#[test]
+fn whitespace_only_values() {
+	for &(value, rendered) in &[
+		(" ", "<DIV title=\" \"></DIV>"),
+		("   ", "<DIV title=\"   \"></DIV>"),
+		("\t", "<DIV title=\"\t\"></DIV>"),
+		("\n\n", "<DIV title=\"\n\n\"></DIV>"),
+		("\u{C}", "<DIV title=\"\u{C}\"></DIV>"),
+		(" \t\n", "<DIV title=\" \t\n\"></DIV>"),
+		// Parsers would read a literal `\r` as `\n`.
+		("\r", "<DIV title=\"&#13;\"></DIV>"),
+		("\r\n", "<DIV title=\"&#13;\n\"></DIV>"),
+	] {
+		assert_eq!(
+			div_with_attributes(&[Attribute {
+				name: "title",
+				value,
+			}]),
+			rendered,
+			"{:?}",
+			value
+		);
+	}
+}
+
+#[test]
+fn whitespace_only_values_xml() {
+	let mut svg = String::new();
+	render_svg_document(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[
+					Attribute {
+						name: "a",
+						value: " ",
+					},
+					Attribute {
+						name: "b",
+						value: "\t\n\r",
+					},
+				],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut svg,
+		1,
+	)
+	.unwrap();
+	assert_eq!(
+		svg,
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?><svg xmlns=\"http://www.w3.org/2000/svg\" a=\" \" b=\"&#9;&#10;&#13;\"/>"
+	);
+}
+
+#[test]
+fn style_values() {
+	for &(value, rendered) in &[
+		("color:red;", "<DIV style=color:red;></DIV>"),
+		(
+			"background:url('x')",
+			"<DIV style=\"background:url('x')\"></DIV>",
+		),
+		("content: \"x\"", "<DIV style='content: \"x\"'></DIV>"),
+		(
+			"font-family: \"A B\", 'C D'",
+			"<DIV style=\"font-family: &quot;A B&quot;, 'C D'\"></DIV>",
+		),
+		(
+			"content: '\\\"'",
+			"<DIV style=\"content: '\\&quot;'\"></DIV>",
+		),
+	] {
+		assert_eq!(
+			div_with_attributes(&[Attribute {
+				name: "style",
+				value,
+			}]),
+			rendered,
+			"{:?}",
+			value
+		);
+	}
+}
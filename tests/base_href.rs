@@ -0,0 +1,136 @@
+#![cfg(feature = "alloc")]
+
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render(element_name: &str, attribute_name: &str, value: &str, base_href: &str) -> String {
+	let attributes = [Attribute {
+		name: attribute_name,
+		value,
+	}];
+	let element = Element {
+		name: element_name,
+		creation_options: ElementCreationOptions::new(),
+		attributes: &attributes,
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	};
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &element,
+		dom_binding: None,
+	};
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&vdom,
+		&mut fragment,
+		&RenderOptions::new().with_base_href(Some(base_href)),
+	)
+	.unwrap();
+	fragment
+}
+
+fn img_src(src: &str, base_href: &str) -> String {
+	render("img", "src", src, base_href)
+}
+
+#[test]
+fn default() {
+	assert_eq!(RenderOptions::new().base_href(), None);
+}
+
+#[test]
+fn path_relative() {
+	assert_eq!(
+		img_src("img/a.png", "https://cdn.example.com/static/index.html?v=1"),
+		"<img src=https://cdn.example.com/static/img/a.png>"
+	);
+	assert_eq!(
+		img_src("../a.png", "https://cdn.example.com/static/"),
+		"<img src=https://cdn.example.com/static/../a.png>"
+	);
+	assert_eq!(
+		img_src("a.png", "https://cdn.example.com"),
+		"<img src=https://cdn.example.com/a.png>"
+	);
+}
+
+#[test]
+fn path_absolute() {
+	assert_eq!(
+		img_src("/a.png", "https://cdn.example.com/static/"),
+		"<img src=https://cdn.example.com/a.png>"
+	);
+}
+
+#[test]
+fn scheme_relative() {
+	assert_eq!(
+		img_src(
+			"//other.example.com/a.png",
+			"https://cdn.example.com/static/"
+		),
+		"<img src=https://other.example.com/a.png>"
+	);
+}
+
+#[test]
+fn absolute_untouched() {
+	for &src in &[
+		"http://example.com/a.png",
+		"data:image/png;base64,AAAA",
+		"MAILTO:someone@example.com",
+	] {
+		assert_eq!(
+			img_src(src, "https://cdn.example.com/"),
+			render("img", "src", src, "not a base"),
+		);
+	}
+	assert_eq!(
+		img_src("http://example.com/a.png", "https://cdn.example.com/"),
+		"<img src=http://example.com/a.png>"
+	);
+}
+
+#[test]
+fn ambiguous_untouched() {
+	for &src in &["", "#top", "?q", " a.png", "a\\b.png"] {
+		assert_eq!(
+			img_src(src, "https://cdn.example.com/"),
+			img_src(src, "not a base"),
+			"{:?}",
+			src
+		);
+	}
+}
+
+#[test]
+fn unusual_base_untouched() {
+	for &base_href in &["/static/", "about:blank", "file:///", "https:"] {
+		assert_eq!(
+			img_src("a.png", base_href),
+			"<img src=a.png>",
+			"{:?}",
+			base_href
+		);
+	}
+}
+
+#[test]
+fn href() {
+	assert_eq!(
+		render("a", "HREF", "page", "https://example.com/"),
+		"<a HREF=https://example.com/page></a>"
+	);
+	assert_eq!(
+		render("a", "title", "page", "https://example.com/"),
+		"<a title=page></a>"
+	);
+}
+
+#[test]
+fn base_element_untouched() {
+	assert_eq!(
+		render("base", "href", "static/", "https://example.com/"),
+		"<base href=static/>"
+	);
+}
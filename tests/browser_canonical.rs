@@ -0,0 +1,148 @@
+mod common;
+
+use common::{element, leak, render_with, text};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::RenderOptions;
+
+fn render(vdom: &Node<ThreadSafe>, options: &RenderOptions) -> String {
+	render_with(vdom, options).unwrap()
+}
+
+/// Roughly `<DIV ID=a Hidden CLASS='x y' title="a&quot;b>c"><BR><svg viewBox="0 0 1 1"><circle r="1"/></svg><PRE>x&nbsp;y > z</PRE><TEXTAREA>a<b</TEXTAREA></DIV>`.
+fn tree() -> Node<'static, ThreadSafe> {
+	Node::HtmlElement {
+		element: element(
+			"DIV",
+			&[
+				Attribute {
+					name: "ID",
+					value: "a",
+				},
+				Attribute {
+					name: "Hidden",
+					value: "",
+				},
+				Attribute {
+					name: "CLASS",
+					value: "x y",
+				},
+				Attribute {
+					name: "title",
+					value: "a\"b>c",
+				},
+			],
+			Node::Multi(leak([
+				Node::HtmlElement {
+					element: element("BR", &[], Node::Multi(&[])),
+					dom_binding: None,
+				},
+				Node::SvgElement {
+					element: element(
+						"svg",
+						&[Attribute {
+							name: "viewBox",
+							value: "0 0 1 1",
+						}],
+						Node::SvgElement {
+							element: element(
+								"circle",
+								&[Attribute {
+									name: "r",
+									value: "1",
+								}],
+								Node::Multi(&[]),
+							),
+							dom_binding: None,
+						},
+					),
+					dom_binding: None,
+				},
+				Node::HtmlElement {
+					element: element("PRE", &[], text("x\u{A0}y > z")),
+					dom_binding: None,
+				},
+				Node::HtmlElement {
+					element: element("TEXTAREA", &[], text("a<b")),
+					dom_binding: None,
+				},
+			])),
+		),
+		dom_binding: None,
+	}
+}
+
+#[test]
+fn canonical() {
+	// The `outerHTML` of the parsed markup above in current browsers, with sorted attributes and without `=""`.
+	assert_eq!(
+		render(&tree(), &RenderOptions::new().with_browser_canonical(true)),
+		"<div class=\"x y\" hidden id=\"a\" title=\"a&quot;b&gt;c\"><br><svg viewBox=\"0 0 1 1\"><circle r=\"1\"></circle></svg><pre>x&nbsp;y &gt; z</pre><textarea>a&lt;b</textarea></div>"
+	);
+}
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render(&tree(), &RenderOptions::new()),
+		"<DIV ID=a Hidden CLASS=\"x y\" title='a\"b>c'><BR><svg viewBox=\"0 0 1 1\"><circle r=1 /></svg><PRE>\nx\u{A0}y > z</PRE><TEXTAREA>\na<b</TEXTAREA></DIV>"
+	);
+}
+
+#[test]
+fn sorting_is_stable() {
+	let vdom = Node::HtmlElement {
+		element: element(
+			"p",
+			&[
+				Attribute {
+					name: "b",
+					value: "1",
+				},
+				Attribute {
+					name: "A",
+					value: "",
+				},
+				Attribute {
+					name: "b",
+					value: "2",
+				},
+				Attribute {
+					name: "a",
+					value: "3",
+				},
+			],
+			Node::Multi(&[]),
+		),
+		dom_binding: None,
+	};
+	assert_eq!(
+		render(&vdom, &RenderOptions::new().with_browser_canonical(true)),
+		"<p a a=\"3\" b=\"1\" b=\"2\"></p>"
+	);
+}
+
+#[test]
+fn leading_newlines_are_kept() {
+	let vdom = Node::Multi(leak([
+		Node::HtmlElement {
+			element: element("pre", &[], text("\nx")),
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: element("listing", &[], Node::Multi(leak([text(""), text("\n\ny")]))),
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: element("textarea", &[], text("\nz")),
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: element("pre", &[], text("x\n")),
+			dom_binding: None,
+		},
+	]));
+	assert_eq!(
+		render(&vdom, &RenderOptions::new().with_browser_canonical(true)),
+		"<pre>\n\nx</pre><listing>\n\n\ny</listing><textarea>\n\nz</textarea><pre>x\n</pre>"
+	);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "alloc")]
+
+use lignin_html::{
+	builder::{comment, el, multi, text, OwnedNode},
+	render_fragment, RenderOptions,
+};
+
+fn render(node: &OwnedNode) -> Result<String, String> {
+	node.with_node(|node| {
+		let mut html = String::new();
+		render_fragment(node, &mut html, RenderOptions::DEFAULT_DEPTH_LIMIT)
+			.map(|()| html)
+			.map_err(|error| error.to_string())
+	})
+	.map_err(|error| error.to_string())?
+}
+
+#[test]
+fn tree() {
+	let tree = multi(vec![
+		comment("list"),
+		el("ul")
+			.attr("class", "a b")
+			.child(el("li").child(text("1 < 2")))
+			.child(el("li").child(el("br")).child(text("x")))
+			.into(),
+		text("&"),
+	]);
+	assert_eq!(
+		render(&tree).unwrap(),
+		r#"<!--list--><ul class="a b"><li>1 &lt; 2</li><li><br>x</li></ul>&amp;"#
+	);
+}
+
+#[test]
+fn empty_element() {
+	assert_eq!(render(&el("div").into()).unwrap(), "<div></div>");
+}
+
+#[test]
+fn invalid_element_name() {
+	assert_eq!(
+		render(&el("div").child(el("a b")).into()).unwrap_err(),
+		"Invalid element name \"a b\": ' ' can't appear in element names"
+	);
+}
+
+#[test]
+fn invalid_attribute_name() {
+	assert_eq!(
+		render(&el("div").attr("a=b", "").into()).unwrap_err(),
+		"Invalid attribute name \"a=b\""
+	);
+}
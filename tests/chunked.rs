@@ -0,0 +1,69 @@
+#![cfg(feature = "std")]
+
+use core::fmt::Write as _;
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment, ChunkedWriter};
+use std::io;
+
+#[test]
+fn framing() {
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name: "p",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Text {
+				text: "Hello, world!",
+				dom_binding: None,
+			},
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+
+	let mut writer = ChunkedWriter::new(Vec::new());
+	render_fragment(&vdom, &mut writer, 2).unwrap();
+	assert_eq!(
+		String::from_utf8(writer.finish().unwrap()).unwrap(),
+		"1\r\n<\r\n1\r\np\r\n1\r\n>\r\nd\r\nHello, world!\r\n2\r\n</\r\n1\r\np\r\n1\r\n>\r\n0\r\n\r\n"
+	);
+}
+
+#[test]
+fn empty_writes_are_skipped() {
+	let mut writer = ChunkedWriter::new(Vec::new());
+	writer.write_str("").unwrap();
+	assert_eq!(writer.finish().unwrap(), b"0\r\n\r\n");
+}
+
+#[test]
+fn long_chunk() {
+	let mut writer = ChunkedWriter::new(Vec::new());
+	writer.write_str(&"a".repeat(300)).unwrap();
+	let body = writer.finish().unwrap();
+	assert!(body.starts_with(b"12c\r\naaa"));
+	assert!(body.ends_with(b"aaa\r\n0\r\n\r\n"));
+}
+
+#[derive(Debug)]
+struct Failing;
+impl io::Write for Failing {
+	fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+		Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+#[test]
+fn error() {
+	let mut writer = ChunkedWriter::new(Failing);
+	assert!(writer.write_str("a").is_err());
+	assert!(writer.write_str("").is_err());
+	assert_eq!(
+		writer.finish().unwrap_err().kind(),
+		io::ErrorKind::BrokenPipe
+	);
+}
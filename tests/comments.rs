@@ -0,0 +1,137 @@
+use lignin::{Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, CommentPolicy, RenderOptions};
+
+fn render_comment(comment: &str, options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::Comment {
+			comment,
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+const CONDITIONAL: &str = "[if IE]><p>Old browser!</p><![endif]";
+
+#[test]
+fn legalized_by_default() {
+	assert_eq!(
+		render_comment("a --> b", &RenderOptions::new()),
+		"<!--a ==> b-->"
+	);
+}
+
+#[test]
+fn verbatim() {
+	assert_eq!(
+		render_comment(
+			CONDITIONAL,
+			&RenderOptions::new().with_comment_policy(CommentPolicy::Verbatim)
+		),
+		"<!--[if IE]><p>Old browser!</p><![endif]-->"
+	);
+	assert_eq!(
+		render_comment(
+			"a --> b",
+			&RenderOptions::new().with_comment_policy(CommentPolicy::Verbatim)
+		),
+		"<!--a --> b-->"
+	);
+}
+
+#[test]
+fn nul() {
+	assert_eq!(
+		render_comment("a\0b", &RenderOptions::new()),
+		"<!--a\u{FFFD}b-->"
+	);
+	assert_eq!(
+		render_comment(
+			"a\0b",
+			&RenderOptions::new().with_comment_policy(CommentPolicy::Verbatim)
+		),
+		"<!--a\0b-->"
+	);
+}
+
+#[test]
+fn lt_bang_dash() {
+	assert_eq!(render_comment("<!-", &RenderOptions::new()), "<!--<!-|-->");
+	assert_eq!(
+		render_comment("<!-a<!-", &RenderOptions::new()),
+		"<!--<!-a<!-|-->"
+	);
+	assert_eq!(
+		render_comment("a<!--", &RenderOptions::new()),
+		"<!--a<!==-->"
+	);
+}
+
+#[test]
+fn guard_char() {
+	let options = RenderOptions::new().with_comment_guard_char(' ');
+	assert_eq!(
+		render_comment("> Hello! <!-", &options),
+		"<!-- > Hello! <!- -->"
+	);
+	assert_eq!(render_comment("plain", &options), "<!--plain-->");
+}
+
+#[test]
+fn invalid_guard_char() {
+	let vdom: Node<ThreadSafe> = Node::Comment {
+		comment: "plain",
+		dom_binding: None,
+	};
+	for &guard in &['-', '<', '>', '\0', '\n'] {
+		let mut fragment = String::new();
+		let error = render_fragment_with_options(
+			&vdom,
+			&mut fragment,
+			&RenderOptions::new().with_comment_guard_char(guard),
+		)
+		.unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			format!("Invalid comment guard character {:?}", guard)
+		);
+		assert_eq!(fragment, "");
+	}
+}
+
+#[test]
+fn guard_char_unused_when_verbatim() {
+	assert_eq!(
+		render_comment(
+			"> a",
+			&RenderOptions::new()
+				.with_comment_policy(CommentPolicy::Verbatim)
+				.with_comment_guard_char('-')
+		),
+		"<!--> a-->"
+	);
+}
+
+#[test]
+fn error_if_lossy() {
+	let options = RenderOptions::new().with_comment_policy(CommentPolicy::ErrorIfLossy);
+	// Only guards are inserted here.
+	assert_eq!(render_comment("> a <!-", &options), "<!--|> a <!-|-->");
+
+	for &comment in &["a --> b", "a --!> b", "a <!-- b", "a \0 b"] {
+		let vdom: Node<ThreadSafe> = Node::Comment {
+			comment,
+			dom_binding: None,
+		};
+		let error = render_fragment_with_options(&vdom, &mut String::new(), &options).unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			format!("Comment text {:?} can't be rendered unchanged", comment)
+		);
+	}
+}
@@ -0,0 +1,74 @@
+//! Fixtures shared by the integration tests, which each include this as `mod common;`.
+//!
+//! Elements and slices built at runtime are leaked, so that [`Node`]s can borrow them for as long as needed.
+
+#![allow(dead_code)]
+
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+/// Leaks `value` to borrow it for as long as needed.
+pub fn leak<'a, T: 'a>(value: T) -> &'a T {
+	Box::leak(Box::new(value))
+}
+
+/// An [`Element`] without [`ElementCreationOptions`] or event bindings.
+pub fn element<'a>(
+	name: &'a str,
+	attributes: &'a [Attribute<'a>],
+	content: Node<'a, ThreadSafe>,
+) -> &'a Element<'a, ThreadSafe> {
+	Box::leak(Box::new(Element {
+		name,
+		creation_options: ElementCreationOptions::new(),
+		attributes,
+		content,
+		event_bindings: &[],
+	}))
+}
+
+/// A [`Node::HtmlElement`] with the given [`element`].
+pub fn html_element<'a>(
+	name: &'a str,
+	attributes: &'a [Attribute<'a>],
+	content: Node<'a, ThreadSafe>,
+) -> Node<'a, ThreadSafe> {
+	Node::HtmlElement {
+		element: element(name, attributes, content),
+		dom_binding: None,
+	}
+}
+
+/// A [`Node::SvgElement`] with the given [`element`].
+pub fn svg_element<'a>(
+	name: &'a str,
+	attributes: &'a [Attribute<'a>],
+	content: Node<'a, ThreadSafe>,
+) -> Node<'a, ThreadSafe> {
+	Node::SvgElement {
+		element: element(name, attributes, content),
+		dom_binding: None,
+	}
+}
+
+pub fn text(text: &str) -> Node<'_, ThreadSafe> {
+	Node::Text {
+		text,
+		dom_binding: None,
+	}
+}
+
+pub fn comment(comment: &str) -> Node<'_, ThreadSafe> {
+	Node::Comment {
+		comment,
+		dom_binding: None,
+	}
+}
+
+/// Renders `vdom` as fragment with `options`, with any error as its message.
+pub fn render_with(vdom: &Node<ThreadSafe>, options: &RenderOptions) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment_with_options(vdom, &mut fragment, options)
+		.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
@@ -0,0 +1,13 @@
+use core::fmt::Write;
+use lignin_html::CountingWriter;
+
+#[test]
+fn counts_bytes() {
+	let mut writer = CountingWriter::new(String::new());
+	writer.write_str("ab").unwrap();
+	writer.write_char('ü').unwrap();
+	write!(writer, "{}", 10).unwrap();
+	assert_eq!(writer.count(), 6);
+	assert_eq!(writer.inner(), "abü10");
+	assert_eq!(writer.into_inner(), "abü10");
+}
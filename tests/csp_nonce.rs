@@ -0,0 +1,84 @@
+mod common;
+
+use common::element;
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render<'a>(vdom: &'a Node<'a, ThreadSafe>, nonce: Option<&str>) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		vdom,
+		&mut fragment,
+		&RenderOptions::new().with_csp_nonce(nonce),
+	)
+	.unwrap();
+	fragment
+}
+
+#[test]
+fn script_and_style_only() {
+	let script = element(
+		"script",
+		&[],
+		Node::Text {
+			text: "f()",
+			dom_binding: None,
+		},
+	);
+	let style = element("style", &[], Node::Multi(&[]));
+	let children = [
+		Node::HtmlElement {
+			element: script,
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: style,
+			dom_binding: None,
+		},
+	];
+	let div = element("div", &[], Node::Multi(&children));
+	let vdom = Node::HtmlElement {
+		element: div,
+		dom_binding: None,
+	};
+
+	assert_eq!(
+		render(&vdom, Some("r4nd0m")),
+		"<div><script nonce=r4nd0m>f()</script><style nonce=r4nd0m></style></div>"
+	);
+	assert_eq!(
+		render(&vdom, None),
+		"<div><script>f()</script><style></style></div>"
+	);
+}
+
+#[test]
+fn existing_nonce_and_escaping() {
+	let attributes = [Attribute {
+		name: "nonce",
+		value: "mine",
+	}];
+	let script = element("script", &attributes, Node::Multi(&[]));
+	assert_eq!(
+		render(
+			&Node::HtmlElement {
+				element: script,
+				dom_binding: None,
+			},
+			Some("a b"),
+		),
+		"<script nonce=mine></script>"
+	);
+
+	let style = element("style", &[], Node::Multi(&[]));
+	assert_eq!(
+		render(
+			&Node::HtmlElement {
+				element: style,
+				dom_binding: None,
+			},
+			Some("a\"b c"),
+		),
+		"<style nonce='a\"b c'></style>"
+	);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::{html_element, render_with};
+use lignin::{Node, ThreadSafe};
+use lignin_html::RenderOptions;
+
+fn render(
+	name: &'static str,
+	content: Node<'static, ThreadSafe>,
+	options: &RenderOptions,
+) -> Result<String, String> {
+	render_with(&html_element(name, &[], content), options)
+}
+
+#[test]
+fn extra_void_element() {
+	let options = RenderOptions::new().with_extra_void_elements(&["x-break"]);
+	assert_eq!(
+		render("X-BREAK", Node::Multi(&[]), &options).unwrap(),
+		"<X-BREAK>"
+	);
+	render(
+		"x-break",
+		Node::Text {
+			text: "x",
+			dom_binding: None,
+		},
+		&options,
+	)
+	.unwrap_err();
+	assert_eq!(
+		render("x-break", Node::Multi(&[]), &RenderOptions::new()).unwrap(),
+		"<x-break></x-break>"
+	);
+}
+
+#[test]
+fn extra_raw_text_element() {
+	let options = RenderOptions::new().with_extra_raw_text_elements(&["x-codeblock"]);
+	assert_eq!(
+		render(
+			"x-codeblock",
+			Node::Text {
+				text: "<b>bold</b> & co",
+				dom_binding: None,
+			},
+			&options
+		)
+		.unwrap(),
+		"<x-codeblock><b>bold</b> & co</x-codeblock>"
+	);
+	assert_eq!(
+		render(
+			"x-codeblock",
+			Node::Text {
+				text: "a</X-CODEBLOCK>b",
+				dom_binding: None,
+			},
+			&options
+		)
+		.unwrap_err(),
+		"Element closed in raw text of `<x-codeblock>` at byte 1: \"</X-CODEBLOCK>\""
+	);
+}
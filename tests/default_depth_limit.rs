@@ -0,0 +1,58 @@
+mod common;
+
+use common::{html_element, leak};
+use lignin::{Node, ThreadSafe};
+use lignin_html::{render_fragment, render_fragment_with_options, RenderOptions};
+
+/// Text at `depth`, inside `depth - 1` nested `<b>` elements.
+fn nested(depth: usize) -> &'static Node<'static, ThreadSafe> {
+	let mut node: &'static Node<'static, ThreadSafe> = &Node::Text {
+		text: "deep",
+		dom_binding: None,
+	};
+	for _ in 1..depth {
+		node = leak(html_element("b", &[], *node));
+	}
+	node
+}
+
+fn expected(depth: usize) -> String {
+	"<b>".repeat(depth - 1) + "deep" + &"</b>".repeat(depth - 1)
+}
+
+#[test]
+fn default_passes() {
+	let mut fragment = String::new();
+	render_fragment_with_options(nested(200), &mut fragment, &RenderOptions::default()).unwrap();
+	assert_eq!(fragment, expected(200));
+}
+
+#[test]
+fn default_fails() {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		nested(RenderOptions::DEFAULT_DEPTH_LIMIT + 1),
+		&mut fragment,
+		&RenderOptions::default(),
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn exact() {
+	let mut fragment = String::new();
+	render_fragment(
+		nested(RenderOptions::DEFAULT_DEPTH_LIMIT),
+		&mut fragment,
+		RenderOptions::DEFAULT_DEPTH_LIMIT,
+	)
+	.unwrap();
+	assert_eq!(fragment, expected(RenderOptions::DEFAULT_DEPTH_LIMIT));
+}
+
+#[test]
+fn unlimited() {
+	let mut fragment = String::new();
+	render_fragment(nested(200), &mut fragment, usize::MAX).unwrap();
+	assert_eq!(fragment, expected(200));
+}
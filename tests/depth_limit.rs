@@ -1,5 +1,11 @@
-use lignin::{Element, ElementCreationOptions, Node};
-use lignin_html::render_fragment;
+mod common;
+
+use common::{html_element, leak};
+use lignin::{Element, ElementCreationOptions, Node, ReorderableFragment, ThreadSafe};
+use lignin_html::{
+	estimate_output_len, render_fragment, render_fragment_with_options, render_fragment_with_stats,
+	render_text_only, Error, RenderOptions,
+};
 use std::fmt::Write;
 
 struct Drain;
@@ -33,7 +39,7 @@ fn div_pass() {
 		}
 		.prefer_thread_safe(),
 		&mut Drain,
-		3,
+		2,
 	)
 	.unwrap();
 }
@@ -63,7 +69,223 @@ fn div_fail() {
 		}
 		.prefer_thread_safe(),
 		&mut Drain,
-		2,
+		1,
 	)
 	.unwrap();
 }
+
+#[test]
+fn div_fail_message() {
+	let message = render_fragment(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "DIV",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::HtmlElement {
+					element: &Element {
+						name: "SPAN",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[],
+						content: Node::Multi(&[]),
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut Drain,
+		1,
+	)
+	.unwrap_err()
+	.to_string();
+	assert_eq!(message, "Depth limit of 1 exceeded at a `<SPAN>` element");
+}
+
+const TEXT: Node<'static, ThreadSafe> = Node::Text {
+	text: "a",
+	dom_binding: None,
+};
+
+const COMMENT: Node<'static, ThreadSafe> = Node::Comment {
+	comment: "b",
+	dom_binding: None,
+};
+
+fn p(content: Node<'static, ThreadSafe>) -> Node<'static, ThreadSafe> {
+	html_element("p", &[], content)
+}
+
+fn multi(nodes: Vec<Node<'static, ThreadSafe>>) -> Node<'static, ThreadSafe> {
+	Node::Multi(nodes.leak())
+}
+
+fn keyed(content: Node<'static, ThreadSafe>) -> Node<'static, ThreadSafe> {
+	Node::Keyed(leak([ReorderableFragment {
+		dom_key: 0,
+		content,
+	}]))
+}
+
+fn memoized(content: Node<'static, ThreadSafe>) -> Node<'static, ThreadSafe> {
+	Node::Memoized {
+		state_key: 0,
+		content: leak(content),
+	}
+}
+
+/// Asserts that `vdom` needs exactly `depth_limit`, consistently across renderers.
+fn assert_depth(vdom: Node<'static, ThreadSafe>, depth_limit: usize) {
+	let vdom = leak(vdom);
+	let stats = render_fragment_with_stats(vdom, &mut Drain, depth_limit).unwrap();
+	assert_eq!(stats.max_depth, depth_limit);
+	render_text_only(vdom, &mut Drain, depth_limit).unwrap();
+	if depth_limit > 0 {
+		render_fragment(vdom, &mut Drain, depth_limit - 1).unwrap_err();
+		render_text_only(vdom, &mut Drain, depth_limit - 1).unwrap_err();
+	}
+}
+
+#[test]
+fn leaves() {
+	assert_depth(TEXT, 1);
+	assert_depth(COMMENT, 1);
+}
+
+#[test]
+fn elements() {
+	assert_depth(p(multi(vec![])), 1);
+	assert_depth(p(TEXT), 2);
+	assert_depth(p(COMMENT), 2);
+	assert_depth(p(p(multi(vec![]))), 2);
+	assert_depth(p(p(TEXT)), 3);
+}
+
+#[test]
+fn empty_wrappers() {
+	assert_depth(multi(vec![]), 0);
+	assert_depth(Node::Keyed(&[]), 0);
+	assert_depth(memoized(multi(vec![])), 0);
+}
+
+#[test]
+fn transparent_wrappers() {
+	assert_depth(multi(vec![TEXT, COMMENT]), 1);
+	assert_depth(keyed(TEXT), 1);
+	assert_depth(memoized(TEXT), 1);
+	assert_depth(multi(vec![memoized(keyed(multi(vec![TEXT])))]), 1);
+	assert_depth(p(multi(vec![memoized(keyed(TEXT))])), 2);
+	assert_depth(multi(vec![TEXT, p(keyed(p(memoized(TEXT))))]), 3);
+}
+
+#[test]
+fn exceeded_below_wrappers() {
+	let vdom = leak(p(multi(vec![COMMENT, memoized(TEXT)])));
+	let content = match vdom {
+		Node::HtmlElement { element, .. } => &element.content,
+		_ => unreachable!(),
+	};
+	let comment = match content {
+		Node::Multi(nodes) => &nodes[0],
+		_ => unreachable!(),
+	};
+	assert_eq!(
+		render_fragment(vdom, &mut Drain, 1),
+		Err(Error::depth_limit_exceeded(comment, 1))
+	);
+	assert_eq!(
+		render_fragment(vdom, &mut Drain, 1)
+			.unwrap_err()
+			.to_string(),
+		"Depth limit of 1 exceeded at a comment"
+	);
+}
+
+#[test]
+fn fragment_elements() {
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name: "lignin:fragment",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: TEXT,
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+	let stats = render_fragment_with_stats(&vdom, &mut Drain, 1).unwrap();
+	assert_eq!(stats.max_depth, 1);
+	render_fragment(&vdom, &mut Drain, 0).unwrap_err();
+}
+
+/// `len` [`Node::Multi`]s nested in each other around [`TEXT`], and the one at `index` counting from the outside.
+fn multi_chain(
+	len: usize,
+	index: usize,
+) -> (
+	&'static Node<'static, ThreadSafe>,
+	&'static Node<'static, ThreadSafe>,
+) {
+	let mut vdom = TEXT;
+	let mut at_index = None;
+	for i in (0..len).rev() {
+		let content: &'static [_] = leak([vdom]);
+		if i + 1 == index {
+			at_index = Some(&content[0]);
+		}
+		vdom = Node::Multi(content);
+	}
+	let vdom = leak(vdom);
+	(vdom, at_index.unwrap_or(vdom))
+}
+
+#[test]
+fn deep_multi_chain() {
+	let limit = RenderOptions::DEFAULT_TRANSPARENT_DEPTH_LIMIT;
+	let (vdom, first_too_deep) = multi_chain(200_000, limit);
+	let expected = Err(Error::transparent_depth_limit_exceeded(
+		first_too_deep,
+		limit,
+	));
+	assert_eq!(render_fragment(vdom, &mut Drain, 10), expected);
+	assert_eq!(render_text_only(vdom, &mut Drain, 10), expected);
+	assert_eq!(estimate_output_len(vdom, 10), 0);
+	assert_eq!(
+		render_fragment(vdom, &mut Drain, 10)
+			.unwrap_err()
+			.to_string(),
+		"Transparent depth limit of 256 exceeded at a `Multi` node"
+	);
+}
+
+#[test]
+fn transparent_depth_limit() {
+	let options = RenderOptions::new().with_transparent_depth_limit(2);
+	let (vdom, _) = multi_chain(2, 0);
+	render_fragment_with_options(vdom, &mut Drain, &options).unwrap();
+	let (vdom, first_too_deep) = multi_chain(3, 2);
+	assert_eq!(
+		render_fragment_with_options(vdom, &mut Drain, &options),
+		Err(Error::transparent_depth_limit_exceeded(first_too_deep, 2))
+	);
+}
+
+#[test]
+fn transparent_depth_limit_counts_fragment_elements() {
+	let fragment = |content| html_element("lignin:fragment", &[], content);
+	let inner = leak(fragment(TEXT));
+	let vdom = fragment(Node::Memoized {
+		state_key: 0,
+		content: inner,
+	});
+	let options = RenderOptions::new().with_transparent_depth_limit(2);
+	assert_eq!(
+		render_fragment_with_options(&vdom, &mut Drain, &options),
+		Err(Error::transparent_depth_limit_exceeded(inner, 2))
+	);
+	render_fragment_with_options(&vdom, &mut Drain, &options.with_transparent_depth_limit(3))
+		.unwrap();
+}
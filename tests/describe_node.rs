@@ -0,0 +1,103 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::html_element;
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{describe_node, AttributeQuoting, ContentModel, NodeKind};
+
+#[test]
+fn script() {
+	let description = describe_node(&html_element("script", &[], Node::Multi(&[])));
+	assert_eq!(description.kind, NodeKind::HtmlElement);
+	let element = description.element.unwrap();
+	assert_eq!(element.name, "script");
+	assert_eq!(element.content_model, Some(ContentModel::RawText));
+	assert!(!element.newline_after_start_tag);
+}
+
+#[test]
+fn div_attribute_quoting() {
+	let element = describe_node(&html_element(
+		"div",
+		&[
+			Attribute {
+				name: "class",
+				value: "a b",
+			},
+			Attribute {
+				name: "id",
+				value: "x",
+			},
+			Attribute {
+				name: "title",
+				value: "\"",
+			},
+			Attribute {
+				name: "hidden",
+				value: "",
+			},
+		],
+		Node::Multi(&[]),
+	))
+	.element
+	.unwrap();
+	assert_eq!(element.content_model, Some(ContentModel::Normal));
+	assert_eq!(
+		element.attributes,
+		[
+			("class".to_string(), AttributeQuoting::DoubleQuoted),
+			("id".to_string(), AttributeQuoting::Unquoted),
+			("title".to_string(), AttributeQuoting::SingleQuoted),
+			("hidden".to_string(), AttributeQuoting::Empty),
+		]
+	);
+}
+
+#[test]
+fn newlines() {
+	for &name in &["pre", "LISTING", "textarea"] {
+		assert!(
+			describe_node(&html_element(name, &[], Node::Multi(&[])))
+				.element
+				.unwrap()
+				.newline_after_start_tag
+		);
+	}
+}
+
+#[test]
+fn foreign() {
+	let vdom: Node<ThreadSafe> = Node::SvgElement {
+		element: &Element {
+			name: "title",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Multi(&[]),
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+	assert_eq!(
+		describe_node(&vdom).element.unwrap().content_model,
+		Some(ContentModel::ForeignSelfClosing)
+	);
+}
+
+#[test]
+fn invalid_name() {
+	assert_eq!(
+		describe_node(&html_element("", &[], Node::Multi(&[])))
+			.element
+			.unwrap()
+			.content_model,
+		None
+	);
+}
+
+#[test]
+fn not_an_element() {
+	let description = describe_node(&Node::<ThreadSafe>::Multi(&[]));
+	assert_eq!(description.kind, NodeKind::Multi);
+	assert_eq!(description.element, None);
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{html_element, leak, svg_element};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{render_document_with_options, RenderOptions};
+
+fn el(
+	name: &'static str,
+	attributes: &'static [Attribute<'static>],
+	content: &'static [Node<'static, ThreadSafe>],
+) -> Node<'static, ThreadSafe> {
+	html_element(name, attributes, Node::Multi(content))
+}
+
+fn document(
+	head: Vec<Node<'static, ThreadSafe>>,
+	body: Vec<Node<'static, ThreadSafe>>,
+) -> Node<'static, ThreadSafe> {
+	el(
+		"html",
+		&[],
+		vec![el("head", &[], head.leak()), el("body", &[], body.leak())].leak(),
+	)
+}
+
+fn render(vdom: &Node<'static, ThreadSafe>, strict: bool) -> Result<String, String> {
+	let mut html = String::new();
+	render_document_with_options(
+		vdom,
+		&mut html,
+		&RenderOptions::new().with_strict_document_structure(strict),
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(html)
+}
+
+#[test]
+fn title_in_body() {
+	let vdom = document(vec![], vec![el("main", &[], leak([el("title", &[], &[])]))]);
+	assert_eq!(
+		render(&vdom, false).unwrap(),
+		"<!DOCTYPE html><html><head></head><body><main><title></title></main></body></html>"
+	);
+	assert_eq!(
+		render(&vdom, true).unwrap_err(),
+		"a `<title>` element belongs in `<head>`"
+	);
+}
+
+#[test]
+fn flow_content_in_head() {
+	let vdom = document(vec![el("div", &[], &[])], vec![]);
+	assert_eq!(
+		render(&vdom, true).unwrap_err(),
+		"a `<div>` element belongs in `<body>`"
+	);
+}
+
+#[test]
+fn valid() {
+	let vdom = document(
+		vec![
+			el(
+				"meta",
+				&[Attribute {
+					name: "charset",
+					value: "utf-8",
+				}],
+				&[],
+			),
+			el("title", &[], &[]),
+			el("link", &[], &[]),
+		],
+		vec![
+			el("p", &[], &[]),
+			el("link", &[], &[]),
+			el(
+				"meta",
+				&[Attribute {
+					name: "itemprop",
+					value: "x",
+				}],
+				&[],
+			),
+			svg_element("title", &[], Node::Multi(&[])),
+		],
+	);
+	render(&vdom, true).unwrap();
+}
@@ -0,0 +1,119 @@
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_document, render_document_with_options, DoctypeStyle, RenderOptions};
+
+const VDOM: Node<'static, ThreadSafe> = Node::Multi(&[]);
+
+const HTML: Node<'static, ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "html",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn no_bom_by_default() {
+	let mut document = String::new();
+	render_document(&VDOM, &mut document, 1).unwrap();
+	assert_eq!(document, "<!DOCTYPE html>");
+}
+
+#[test]
+fn bom() {
+	let mut document = String::new();
+	render_document_with_options(
+		&VDOM,
+		&mut document,
+		&RenderOptions::new().with_emit_bom(true),
+	)
+	.unwrap();
+	assert_eq!(document, "\u{FEFF}<!DOCTYPE html>");
+	assert_eq!(&document.as_bytes()[..3], b"\xEF\xBB\xBF");
+}
+
+#[test]
+fn no_trailing_newline_by_default() {
+	let mut document = String::new();
+	render_document_with_options(&VDOM, &mut document, &RenderOptions::new()).unwrap();
+	assert_eq!(document, "<!DOCTYPE html>");
+}
+
+#[test]
+fn trailing_newline() {
+	let mut document = String::new();
+	render_document_with_options(
+		&VDOM,
+		&mut document,
+		&RenderOptions::new().with_trailing_newline(true),
+	)
+	.unwrap();
+	assert_eq!(document, "<!DOCTYPE html>\n");
+}
+
+#[test]
+fn doctype_uppercase() {
+	let mut document = String::new();
+	render_document_with_options(
+		&VDOM,
+		&mut document,
+		&RenderOptions::new().with_doctype_style(DoctypeStyle::Uppercase),
+	)
+	.unwrap();
+	assert_eq!(document, "<!DOCTYPE html>");
+	assert_eq!(
+		RenderOptions::new().doctype_style(),
+		DoctypeStyle::Uppercase
+	);
+}
+
+#[test]
+fn doctype_lowercase() {
+	let mut document = String::new();
+	render_document_with_options(
+		&HTML,
+		&mut document,
+		&RenderOptions::new().with_doctype_style(DoctypeStyle::Lowercase),
+	)
+	.unwrap();
+	assert_eq!(document, "<!doctype html><html></html>");
+}
+
+#[test]
+fn require_html_root() {
+	let vdom = Node::Multi(&[
+		Node::Comment {
+			comment: "x",
+			dom_binding: None,
+		},
+		HTML,
+	]);
+
+	let mut document = String::new();
+	render_document_with_options(&vdom, &mut document, &RenderOptions::new()).unwrap();
+	assert_eq!(document, "<!DOCTYPE html><!--x--><html></html>");
+
+	let mut document = String::new();
+	assert_eq!(
+		render_document_with_options(
+			&vdom,
+			&mut document,
+			&RenderOptions::new().with_require_html_root(true)
+		)
+		.unwrap_err()
+		.to_string(),
+		"HTML document root is not a single `<html>` element, but a `Multi` node"
+	);
+	assert_eq!(document, "");
+
+	let mut document = String::new();
+	render_document_with_options(
+		&HTML,
+		&mut document,
+		&RenderOptions::new().with_require_html_root(true),
+	)
+	.unwrap();
+	assert_eq!(document, "<!DOCTYPE html><html></html>");
+}
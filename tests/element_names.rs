@@ -0,0 +1,68 @@
+use lignin::{Element, ElementCreationOptions, Node};
+use lignin_html::render_fragment;
+
+fn render_element(name: &str) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &Element {
+				name,
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		2,
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn custom_element() {
+	assert_eq!(
+		render_element("x-\u{E9}l").unwrap(),
+		"<x-\u{E9}l></x-\u{E9}l>"
+	);
+}
+
+#[test]
+fn invalid_names_are_rejected_not_internal() {
+	for name in ["", "-", "-x", "\u{E9}", ".x", "x\u{E9}", "x y", "x>"] {
+		let error = render_element(name).unwrap_err();
+		assert!(
+			error.starts_with("Invalid element name"),
+			"{}: {}",
+			name,
+			error
+		);
+	}
+}
+
+#[test]
+fn reasons() {
+	assert_eq!(
+		render_element("").unwrap_err(),
+		"Invalid element name \"\": it's empty"
+	);
+	assert_eq!(
+		render_element("-x").unwrap_err(),
+		"Invalid element name \"-x\": it must start with an ASCII letter or digit, not '-'"
+	);
+	assert_eq!(
+		render_element("x y").unwrap_err(),
+		"Invalid element name \"x y\": ' ' can't appear in element names"
+	);
+	assert_eq!(
+		render_element("x\u{E9}").unwrap_err(),
+		"Invalid element name \"x\u{E9}\": non-ASCII characters are only allowed in custom element names, which must contain a dash ('-')"
+	);
+	assert_eq!(
+		render_element("X-\u{E9}l").unwrap_err(),
+		"Invalid element name \"X-\u{E9}l\": custom element names can't contain ASCII uppercase letters"
+	);
+}
@@ -0,0 +1,72 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_attributes, render_fragment, render_fragment_in_context, Error};
+
+const TEXT: Node<'static, ThreadSafe> = Node::Text {
+	text: "a",
+	dom_binding: None,
+};
+
+#[test]
+fn attribute_names() {
+	assert_eq!(
+		render_attributes::<ThreadSafe>(
+			&[Attribute {
+				name: "a b",
+				value: "",
+			}],
+			&mut String::new(),
+		),
+		Err(Error::invalid_attribute_name("a b"))
+	);
+	assert_eq!(
+		render_attributes::<ThreadSafe>(
+			&[Attribute {
+				name: "is",
+				value: "x-y",
+			}],
+			&mut String::new(),
+		),
+		Err(Error::reserved_attribute_name("is"))
+	);
+}
+
+#[test]
+fn raw_text() {
+	assert_eq!(
+		render_fragment_in_context(
+			&Node::Text {
+				text: "x</script>",
+				dom_binding: None,
+			},
+			&mut String::new(),
+			1,
+			"script",
+		),
+		Err(Error::<ThreadSafe>::element_closed_in_raw_text(
+			"script",
+			"</script>",
+			1
+		))
+	);
+}
+
+#[test]
+fn nodes_by_address() {
+	let element = Element {
+		name: "div",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: TEXT,
+		event_bindings: &[],
+	};
+	let div = Node::HtmlElement {
+		element: &element,
+		dom_binding: None,
+	};
+	let error = render_fragment(&div, &mut String::new(), 1).unwrap_err();
+	assert_eq!(error, Error::depth_limit_exceeded(&element.content, 1));
+
+	// Equal, but not the same.
+	let other = TEXT;
+	assert_ne!(error, Error::depth_limit_exceeded(&other, 1));
+}
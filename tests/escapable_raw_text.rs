@@ -0,0 +1,111 @@
+mod common;
+
+use common::{html_element, leak, text};
+use lignin::{Node, ReorderableFragment, ThreadSafe};
+use lignin_html::render_fragment;
+
+fn render(vdom: &Node<ThreadSafe>) -> Result<String, String> {
+	let mut html = String::new();
+	render_fragment(vdom, &mut html, 10).map_err(|error| error.to_string())?;
+	Ok(html)
+}
+
+#[test]
+fn text_only() {
+	assert_eq!(
+		render(&html_element(
+			"title",
+			&[],
+			Node::Multi(leak([text("a"), text("<b>")]))
+		)),
+		Ok("<title>a<b></title>".to_string())
+	);
+}
+
+#[test]
+fn element_in_title() {
+	assert_eq!(
+		render(&html_element("title", &[], html_element("b", &[], text("x")))),
+		Err(
+			"a `<b>` element can't appear in the escapable raw text element `<title>`, which may only contain text"
+				.to_string()
+		)
+	);
+}
+
+#[test]
+fn comment_in_textarea() {
+	assert_eq!(
+		render(&html_element(
+			"textarea", &[],
+			Node::Comment {
+				comment: "x",
+				dom_binding: None,
+			}
+		)),
+		Err(
+			"a comment can't appear in the escapable raw text element `<textarea>`, which may only contain text"
+				.to_string()
+		)
+	);
+}
+
+#[test]
+fn wrapped_element() {
+	let b = leak(html_element("b", &[], text("x")));
+	let expected = Err(
+		"a `<b>` element can't appear in the escapable raw text element `<title>`, which may only contain text"
+			.to_string(),
+	);
+
+	assert_eq!(
+		render(&html_element(
+			"title",
+			&[],
+			Node::Memoized {
+				state_key: 0,
+				content: b,
+			}
+		)),
+		expected
+	);
+	assert_eq!(
+		render(&html_element(
+			"title",
+			&[],
+			Node::Multi(leak([text("a"), *b]))
+		)),
+		expected
+	);
+	assert_eq!(
+		render(&html_element(
+			"title",
+			&[],
+			Node::Keyed(leak([ReorderableFragment {
+				dom_key: 0,
+				content: *b,
+			}]))
+		)),
+		expected
+	);
+}
+
+#[test]
+fn element_in_script() {
+	assert_eq!(
+		render(&html_element("script", &[], html_element("b", &[], text("x")))),
+		Err(
+			"a `<b>` element can't appear in the raw text element `<script>`, which may only contain text"
+				.to_string()
+		)
+	);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_display() {
+	let vdom = html_element("title", &[], html_element("b", &[], text("x")));
+	let mut html = String::new();
+	let error = render_fragment(&vdom, &mut html, 10).unwrap_err();
+	assert_eq!(error.into_owned().to_string(), error.to_string());
+}
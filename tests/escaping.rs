@@ -0,0 +1,29 @@
+use lignin_html::write_escaped_text;
+
+#[test]
+fn escaped_text() {
+	let mut escaped = String::new();
+	write_escaped_text("a < b && c > d", &mut escaped).unwrap();
+	assert_eq!(escaped, "a &lt; b &amp;&amp; c > d");
+}
+
+#[cfg(feature = "alloc")]
+mod cow {
+	use lignin_html::escape_text_cow;
+	use std::borrow::Cow;
+
+	#[test]
+	fn borrowed() {
+		assert!(matches!(
+			escape_text_cow("safe > text"),
+			Cow::Borrowed("safe > text")
+		));
+	}
+
+	#[test]
+	fn owned() {
+		let escaped = escape_text_cow("a & b");
+		assert!(matches!(escaped, Cow::Owned(_)));
+		assert_eq!(escaped, "a &amp; b");
+	}
+}
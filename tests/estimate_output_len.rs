@@ -0,0 +1,57 @@
+mod common;
+
+use common::{comment, html_element, leak, text};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{estimate_output_len, render_fragment};
+
+fn assert_upper_bound(vdom: &Node<ThreadSafe>) {
+	let mut html = String::new();
+	render_fragment(vdom, &mut html, 10).unwrap();
+	let estimate = estimate_output_len(vdom, 10);
+	assert!(
+		html.len() <= estimate,
+		"{:?} is longer than estimated ({})",
+		html,
+		estimate
+	);
+}
+
+#[test]
+fn empty() {
+	assert_eq!(estimate_output_len(&Node::<ThreadSafe>::Multi(&[]), 10), 0);
+}
+
+#[test]
+fn worst_cases() {
+	assert_upper_bound(&text("&&&<<<"));
+	assert_upper_bound(&comment("\0\0\0"));
+	assert_upper_bound(&comment(">-<!-"));
+	assert_upper_bound(&html_element(
+		"p",
+		&[Attribute {
+			name: "title",
+			value: "\"\"'&",
+		}],
+		Node::Multi(&[]),
+	));
+	assert_upper_bound(&html_element("pre", &[], text("\nx")));
+	assert_upper_bound(&html_element("textarea", &[], text("</&")));
+	assert_upper_bound(&html_element("br", &[], Node::Multi(&[])));
+}
+
+#[test]
+fn tree() {
+	assert_upper_bound(&html_element(
+		"div",
+		&[Attribute {
+			name: "class",
+			value: "a b",
+		}],
+		Node::Multi(leak([
+			text("Hello & welcome!"),
+			html_element("script", &[], text("if (a < b) {}")),
+			comment("note"),
+			html_element("ul", &[], html_element("li", &[], text("<item>"))),
+		])),
+	));
+}
@@ -0,0 +1,69 @@
+//! Rendering into a fixed-capacity string, like `heapless::String`.
+
+use core::fmt::{self, Write};
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment, CountingWriter, Error};
+
+/// Behaves like `heapless::String<N>`: Strings that don't fit are rejected entirely.
+struct FixedString<const N: usize>(String);
+
+impl<const N: usize> Write for FixedString<N> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if self.0.len() + s.len() > N {
+			return Err(fmt::Error);
+		}
+		self.0.push_str(s);
+		Ok(())
+	}
+}
+
+struct Discard;
+impl Write for Discard {
+	fn write_str(&mut self, _: &str) -> fmt::Result {
+		Ok(())
+	}
+}
+
+const VDOM: Node<'static, ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "p",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Text {
+			text: "a < b",
+			dom_binding: None,
+		},
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn fits() {
+	let mut html = FixedString::<15>(String::new());
+	render_fragment(&VDOM, &mut html, 2).unwrap();
+	assert_eq!(html.0, "<p>a &lt; b</p>");
+}
+
+#[test]
+fn too_small() {
+	let mut html = FixedString::<14>(String::new());
+	html.write_str("<br>").unwrap();
+	let previous_len = html.0.len();
+
+	assert_eq!(
+		render_fragment(&VDOM, &mut html, 2),
+		Err(Error::from(fmt::Error))
+	);
+	assert!(html.0.starts_with("<br><p>"));
+
+	html.0.truncate(previous_len);
+	assert_eq!(html.0, "<br>");
+}
+
+#[test]
+fn measure_first() {
+	let mut counter = CountingWriter::new(Discard);
+	render_fragment(&VDOM, &mut counter, 2).unwrap();
+	assert_eq!(counter.count(), 15);
+}
@@ -0,0 +1,29 @@
+use core::fmt::{self, Display};
+use lignin::{Node, ThreadSafe};
+use lignin_html::render_fragment;
+
+struct Wrapped<'a>(Node<'a, ThreadSafe>, usize);
+
+impl Display for Wrapped<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("[")?;
+		render_fragment(&self.0, f, self.1)?;
+		f.write_str("]")
+	}
+}
+
+#[test]
+fn question_mark() {
+	let text = Node::Text {
+		text: "a & b",
+		dom_binding: None,
+	};
+	assert_eq!(Wrapped(text, 1).to_string(), "[a &amp; b]");
+
+	let mut output = String::new();
+	assert_eq!(
+		fmt::write(&mut output, format_args!("{}", Wrapped(text, 0))),
+		Err(fmt::Error)
+	);
+	assert_eq!(output, "[");
+}
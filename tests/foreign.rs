@@ -0,0 +1,402 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{
+	render_fragment, render_fragment_with_options, render_svg_document, RenderOptions,
+};
+
+const RECT: Node<'static, lignin::ThreadSafe> = Node::SvgElement {
+	element: &Element {
+		name: "rect",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn svg_xmlns() {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: RECT,
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		&RenderOptions::new().with_xmlns(true),
+	)
+	.unwrap();
+	assert_eq!(
+		fragment,
+		"<svg xmlns=http://www.w3.org/2000/svg><rect/></svg>"
+	);
+}
+
+#[test]
+fn svg_xmlns_present() {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[Attribute {
+					name: "xmlns",
+					value: "http://www.w3.org/2000/svg",
+				}],
+				content: RECT,
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		&RenderOptions::new().with_xmlns(true),
+	)
+	.unwrap();
+	assert_eq!(
+		fragment,
+		"<svg xmlns=http://www.w3.org/2000/svg><rect/></svg>"
+	);
+}
+
+#[test]
+fn svg_no_xmlns_by_default() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: RECT,
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		3,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<svg><rect/></svg>");
+}
+
+#[test]
+fn svg_document() {
+	let mut document = String::new();
+	render_svg_document(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[Attribute {
+					name: "viewBox",
+					value: "0 0 10 10",
+				}],
+				content: Node::Multi(&[
+					Node::SvgElement {
+						element: &Element {
+							name: "rect",
+							creation_options: ElementCreationOptions::new(),
+							attributes: &[
+								Attribute {
+									name: "width",
+									value: "10",
+								},
+								Attribute {
+									name: "data-empty",
+									value: "",
+								},
+							],
+							content: Node::Multi(&[]),
+							event_bindings: &[],
+						},
+						dom_binding: None,
+					},
+					Node::Comment {
+						comment: "a--b-",
+						dom_binding: None,
+					},
+					Node::SvgElement {
+						element: &Element {
+							name: "text",
+							creation_options: ElementCreationOptions::new(),
+							attributes: &[],
+							content: Node::Text {
+								text: "]]> & <",
+								dom_binding: None,
+							},
+							event_bindings: &[],
+						},
+						dom_binding: None,
+					},
+				]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut document,
+		4,
+	)
+	.unwrap();
+	assert_eq!(
+		document,
+		concat!(
+			r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+			r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">"#,
+			r#"<rect width="10" data-empty=""/>"#,
+			"<!--a-=b-|-->",
+			"<text>]]&gt; &amp; &lt;</text>",
+			"</svg>",
+		)
+	);
+}
+
+#[test]
+fn svg_document_html_root() {
+	let mut document = String::new();
+	render_svg_document(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut document,
+		4,
+	)
+	.unwrap_err();
+	assert_eq!(document, "");
+}
+
+#[test]
+fn empty_g() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::SvgElement {
+			element: &Element {
+				name: "g",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Multi(&[Node::Multi(&[])]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		3,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<g/>");
+}
+
+#[test]
+fn non_empty_g() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::SvgElement {
+			element: &Element {
+				name: "g",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: RECT,
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		3,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<g><rect/></g>");
+}
+
+#[test]
+fn rect_with_title() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::SvgElement {
+			element: &Element {
+				name: "rect",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::SvgElement {
+					element: &Element {
+						name: "title",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[],
+						content: Node::Text {
+							text: "x",
+							dom_binding: None,
+						},
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		3,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<rect><title>x</title></rect>");
+}
+
+const FOREIGN_OBJECT: Node<'static, lignin::ThreadSafe> = Node::SvgElement {
+	element: &Element {
+		name: "svg",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::SvgElement {
+			element: &Element {
+				name: "foreignObject",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::HtmlElement {
+					element: &Element {
+						name: "div",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[],
+						content: Node::Multi(&[
+							Node::HtmlElement {
+								element: &Element {
+									name: "br",
+									creation_options: ElementCreationOptions::new(),
+									attributes: &[],
+									content: Node::Multi(&[]),
+									event_bindings: &[],
+								},
+								dom_binding: None,
+							},
+							Node::HtmlElement {
+								element: &Element {
+									name: "script",
+									creation_options: ElementCreationOptions::new(),
+									attributes: &[],
+									content: Node::Text {
+										text: "1 < 2",
+										dom_binding: None,
+									},
+									event_bindings: &[],
+								},
+								dom_binding: None,
+							},
+						]),
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		},
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn foreign_object() {
+	let mut fragment = String::new();
+	render_fragment(&FOREIGN_OBJECT, &mut fragment, 6).unwrap();
+	assert_eq!(
+		fragment,
+		"<svg><foreignObject><div><br><script>1 < 2</script></div></foreignObject></svg>"
+	);
+}
+
+#[test]
+fn foreign_object_document() {
+	let mut document = String::new();
+	render_svg_document(&FOREIGN_OBJECT, &mut document, 6).unwrap();
+	assert_eq!(
+		document,
+		concat!(
+			r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+			r#"<svg xmlns="http://www.w3.org/2000/svg"><foreignObject>"#,
+			r#"<div xmlns="http://www.w3.org/1999/xhtml"><br/><script>1 &lt; 2</script></div>"#,
+			"</foreignObject></svg>",
+		)
+	);
+}
+
+#[test]
+fn html_element_inherits_svg() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::HtmlElement {
+					element: &Element {
+						name: "rect",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[],
+						content: Node::Multi(&[]),
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		3,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<svg><rect/></svg>");
+}
+
+#[test]
+fn mtext_is_integration_point() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::MathMlElement {
+			element: &Element {
+				name: "mtext",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::HtmlElement {
+					element: &Element {
+						name: "br",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[],
+						content: Node::Multi(&[]),
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		3,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<mtext><br></mtext>");
+}
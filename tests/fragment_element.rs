@@ -0,0 +1,90 @@
+mod common;
+
+use common::element;
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{render_fragment, render_fragment_with_options, RenderOptions};
+
+const CONTENT: Node<'static, ThreadSafe> = Node::Multi(&[
+	Node::Text {
+		text: "a",
+		dom_binding: None,
+	},
+	Node::Comment {
+		comment: "b",
+		dom_binding: None,
+	},
+]);
+
+#[test]
+fn renders_content_only() {
+	let element = element("lignin:fragment", &[], CONTENT);
+	let mut rendered = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element,
+			dom_binding: None,
+		},
+		&mut rendered,
+		3,
+	)
+	.unwrap();
+	assert_eq!(rendered, "a<!--b-->");
+}
+
+#[test]
+fn attributes_are_an_error() {
+	let element = element(
+		"lignin:fragment",
+		&[Attribute {
+			name: "class",
+			value: "x",
+		}],
+		CONTENT,
+	);
+	let vdom = Node::HtmlElement {
+		element,
+		dom_binding: None,
+	};
+	assert_eq!(
+		render_fragment(&vdom, &mut String::new(), 3)
+			.unwrap_err()
+			.to_string(),
+		"a `<lignin:fragment>` element is a fragment, which can't have attributes"
+	);
+}
+
+#[test]
+fn custom_name() {
+	let element = element("Fragment", &[], CONTENT);
+	let vdom = Node::HtmlElement {
+		element,
+		dom_binding: None,
+	};
+	let mut rendered = String::new();
+	render_fragment_with_options(
+		&vdom,
+		&mut rendered,
+		&RenderOptions::new().with_fragment_element_name(Some("Fragment")),
+	)
+	.unwrap();
+	assert_eq!(rendered, "a<!--b-->");
+}
+
+#[test]
+fn disabled() {
+	let element = element("lignin:fragment", &[], Node::Multi(&[]));
+	let vdom = Node::HtmlElement {
+		element,
+		dom_binding: None,
+	};
+	assert_eq!(
+		render_fragment_with_options(
+			&vdom,
+			&mut String::new(),
+			&RenderOptions::new().with_fragment_element_name(None),
+		)
+		.unwrap_err()
+		.to_string(),
+		"Invalid element name \"lignin:fragment\": ':' can't appear in element names"
+	);
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "alloc")]
+
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ReorderableFragment, ThreadSafe};
+use lignin_html::{
+	render_fragment_with_options, Error, FragmentRenderer, RenderOptions, StepResult,
+};
+
+const fn element<'a>(
+	name: &'a str,
+	attributes: &'a [Attribute<'a>],
+	content: Node<'a, ThreadSafe>,
+) -> Element<'a, ThreadSafe> {
+	Element {
+		name,
+		creation_options: ElementCreationOptions::new(),
+		attributes,
+		content,
+		event_bindings: &[],
+	}
+}
+
+const fn text(text: &str) -> Node<'_, ThreadSafe> {
+	Node::Text {
+		text,
+		dom_binding: None,
+	}
+}
+
+fn render_stepped<'a>(
+	vdom: &'a Node<'a, ThreadSafe>,
+	options: &'a RenderOptions<'a>,
+	nodes_per_step: usize,
+) -> Result<(String, usize), String> {
+	let mut renderer = FragmentRenderer::new(vdom, options).with_nodes_per_step(nodes_per_step);
+	let mut html = String::new();
+	let mut steps = 1;
+	while renderer
+		.step(&mut html)
+		.map_err(|error| error.to_string())?
+		== StepResult::Yielded
+	{
+		steps += 1;
+	}
+	assert_eq!(renderer.step(&mut html), Ok(StepResult::Done));
+	Ok((html, steps))
+}
+
+#[test]
+fn same_as_one_shot() {
+	let pre = element("pre", &[], text("\nx"));
+	let script = element("script", &[], text("a < b"));
+	let rect = element("rect", &[], Node::Multi(&[]));
+	let svg = element(
+		"svg",
+		&[],
+		Node::SvgElement {
+			element: &rect,
+			dom_binding: None,
+		},
+	);
+	let fragment_content = [text("f"), text("g")];
+	let fragment = element("lignin:fragment", &[], Node::Multi(&fragment_content));
+	let keyed = [
+		ReorderableFragment {
+			dom_key: 2,
+			content: Node::HtmlElement {
+				element: &pre,
+				dom_binding: None,
+			},
+		},
+		ReorderableFragment {
+			dom_key: 1,
+			content: Node::HtmlElement {
+				element: &fragment,
+				dom_binding: None,
+			},
+		},
+	];
+	let children = [
+		Node::Comment {
+			comment: "c--",
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: &script,
+			dom_binding: None,
+		},
+		Node::Keyed(&keyed),
+		Node::Memoized {
+			state_key: 0,
+			content: &Node::SvgElement {
+				element: &svg,
+				dom_binding: None,
+			},
+		},
+		text("<&>"),
+	];
+	let div = element(
+		"div",
+		&[Attribute {
+			name: "class",
+			value: "a b",
+		}],
+		Node::Multi(&children),
+	);
+	let vdom = Node::HtmlElement {
+		element: &div,
+		dom_binding: None,
+	};
+
+	for options in &[
+		RenderOptions::new(),
+		RenderOptions::new().with_sort_keyed(true),
+	] {
+		let mut expected = String::new();
+		render_fragment_with_options(&vdom, &mut expected, options).unwrap();
+
+		let mut previous_steps = usize::MAX;
+		for nodes_per_step in 1..20 {
+			let (html, steps) = render_stepped(&vdom, options, nodes_per_step).unwrap();
+			assert_eq!(html, expected, "{} nodes per step", nodes_per_step);
+			assert!(steps <= previous_steps);
+			previous_steps = steps;
+		}
+		assert!(
+			render_stepped(&vdom, options, 1).unwrap().1 > 10,
+			"Rendering wasn't split up."
+		);
+	}
+}
+
+#[test]
+fn errors() {
+	let children = [
+		text("a"),
+		Node::HtmlElement {
+			element: &element("div", &[], text("b")),
+			dom_binding: None,
+		},
+	];
+	let vdom = Node::Multi(&children);
+	let options = RenderOptions::new().with_depth_limit(1);
+
+	let mut expected = String::new();
+	let error = render_fragment_with_options(&vdom, &mut expected, &options).unwrap_err();
+
+	let mut renderer = FragmentRenderer::new(&vdom, &options).with_nodes_per_step(1);
+	let mut html = String::new();
+	assert_eq!(renderer.step(&mut html), Ok(StepResult::Yielded));
+	assert_eq!(renderer.step(&mut html), Ok(StepResult::Yielded));
+	assert_eq!(renderer.step(&mut html), Ok(StepResult::Yielded));
+	assert_eq!(renderer.step(&mut html), Err(error));
+	assert_eq!(renderer.step(&mut html), Ok(StepResult::Done));
+	assert_eq!(html, expected);
+}
+
+#[test]
+fn transparent_depth_limit() {
+	let innermost: [Node<ThreadSafe>; 1] = [Node::Multi(&[])];
+	let middle = [Node::Multi(&innermost)];
+	let vdom = Node::Multi(&middle);
+	let options = RenderOptions::new().with_transparent_depth_limit(2);
+	let expected = Err(Error::transparent_depth_limit_exceeded(&innermost[0], 2));
+	assert_eq!(
+		FragmentRenderer::new(&vdom, &options).step(&mut String::new()),
+		expected
+	);
+	assert_eq!(
+		render_fragment_with_options(&vdom, &mut String::new(), &options)
+			.map(|()| StepResult::Done),
+		expected
+	);
+}
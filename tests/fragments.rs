@@ -1,4 +1,4 @@
-use lignin::{Element, ElementCreationOptions, Node};
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
 use lignin_html::render_fragment;
 
 #[test]
@@ -23,6 +23,56 @@ fn br() {
 	assert_eq!(fragment, "<BR>");
 }
 
+#[test]
+fn br_with_empty_text() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "br",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Multi(&[
+					Node::Text {
+						text: "",
+						dom_binding: None,
+					},
+					Node::Multi(&[]),
+				]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		1,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<br>");
+}
+
+#[test]
+fn br_with_comment() {
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name: "br",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Comment {
+				comment: "",
+				dom_binding: None,
+			},
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+	let mut fragment = String::new();
+	assert!(render_fragment(&vdom, &mut fragment, 1)
+		.unwrap_err()
+		.to_string()
+		.starts_with("Non-empty void element content"));
+}
+
 #[test]
 fn div() {
 	let mut fragment = String::new();
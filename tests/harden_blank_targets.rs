@@ -0,0 +1,108 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render_a(attributes: &[Attribute], options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "a",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+const BLANK: Attribute = Attribute {
+	name: "target",
+	value: "_blank",
+};
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render_a(&[BLANK], &RenderOptions::new()),
+		"<a target=_blank></a>"
+	);
+}
+
+#[test]
+fn added() {
+	assert_eq!(
+		render_a(
+			&[BLANK],
+			&RenderOptions::new().with_harden_blank_targets(true)
+		),
+		r#"<a target=_blank rel="noopener noreferrer"></a>"#
+	);
+}
+
+#[test]
+fn merged() {
+	assert_eq!(
+		render_a(
+			&[
+				Attribute {
+					name: "rel",
+					value: "external NoOpener",
+				},
+				BLANK,
+			],
+			&RenderOptions::new().with_harden_blank_targets(true)
+		),
+		r#"<a rel="external NoOpener noreferrer" target=_blank></a>"#
+	);
+}
+
+#[test]
+fn other_targets() {
+	assert_eq!(
+		render_a(
+			&[Attribute {
+				name: "target",
+				value: "_self",
+			}],
+			&RenderOptions::new().with_harden_blank_targets(true)
+		),
+		"<a target=_self></a>"
+	);
+}
+
+#[test]
+fn merged_value_is_trimmed_and_escaped() {
+	assert_eq!(
+		render_a(
+			&[
+				BLANK,
+				Attribute {
+					name: "rel",
+					value: " a\"b&c\t",
+				},
+			],
+			&RenderOptions::new().with_harden_blank_targets(true)
+		),
+		r#"<a target=_blank rel="a&quot;b&amp;c noopener noreferrer"></a>"#
+	);
+	assert_eq!(
+		render_a(
+			&[
+				BLANK,
+				Attribute {
+					name: "rel",
+					value: " ",
+				},
+			],
+			&RenderOptions::new().with_harden_blank_targets(true)
+		),
+		r#"<a target=_blank rel="noopener noreferrer"></a>"#
+	);
+}
@@ -0,0 +1,446 @@
+//! Renders a battery of tricky [`Node`]s and checks that a real HTML parser reads back the intended structure.
+//!
+//! Adjacent [`Node::Text`]s are merged and empty ones dropped before comparing, as they can't be told apart in HTML.
+//! Comment text is not compared, since illegal comments are legalized.
+//!
+//! Inputs that fail to render are skipped, as those are the renderer's to refuse.
+
+use html5ever::{
+	local_name, namespace_url, ns, parse_fragment, tendril::TendrilSink, ParseOpts, QualName,
+};
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::render_fragment;
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Tree {
+	Element {
+		namespace: &'static str,
+		name: String,
+		attributes: Vec<(String, String)>,
+		children: Vec<Tree>,
+	},
+	Text(String),
+	Comment,
+}
+
+fn push(trees: &mut Vec<Tree>, tree: Tree) {
+	match (trees.last_mut(), tree) {
+		(_, Tree::Text(text)) if text.is_empty() => (),
+		(Some(Tree::Text(previous)), Tree::Text(text)) => previous.push_str(&text),
+		(_, tree) => trees.push(tree),
+	}
+}
+
+fn expected(vdom: &Node<ThreadSafe>, trees: &mut Vec<Tree>) {
+	let (namespace, element) = match vdom {
+		Node::HtmlElement { element, .. } => ("html", element),
+		Node::SvgElement { element, .. } => ("svg", element),
+		Node::MathMlElement { element, .. } => ("mathml", element),
+		Node::Comment { .. } => return push(trees, Tree::Comment),
+		Node::Text { text, .. } => return push(trees, Tree::Text(text.to_string())),
+		Node::Multi(nodes) => return nodes.iter().for_each(|node| expected(node, trees)),
+		Node::Keyed(pairs) => return pairs.iter().for_each(|pair| expected(&pair.content, trees)),
+		Node::Memoized { content, .. } => return expected(content, trees),
+		Node::RemnantSite(_) => unimplemented!(),
+	};
+	let mut children = Vec::new();
+	expected(&element.content, &mut children);
+	push(
+		trees,
+		Tree::Element {
+			namespace,
+			name: element.name.to_string(),
+			attributes: element
+				.attributes
+				.iter()
+				.map(|attribute| (attribute.name.to_string(), attribute.value.to_string()))
+				.collect(),
+			children,
+		},
+	)
+}
+
+fn parsed(handle: &Handle, trees: &mut Vec<Tree>) {
+	match &handle.data {
+		NodeData::Element { name, attrs, .. } => {
+			let mut children = Vec::new();
+			for child in handle.children.borrow().iter() {
+				parsed(child, &mut children)
+			}
+			push(
+				trees,
+				Tree::Element {
+					namespace: match name.ns {
+						ns!(html) => "html",
+						ns!(svg) => "svg",
+						ns!(mathml) => "mathml",
+						_ => "other",
+					},
+					name: name.local.to_string(),
+					attributes: attrs
+						.borrow()
+						.iter()
+						.map(|attribute| {
+							let name = match &attribute.name.prefix {
+								Some(prefix) => format!("{}:{}", prefix, attribute.name.local),
+								None => attribute.name.local.to_string(),
+							};
+							(name, attribute.value.to_string())
+						})
+						.collect(),
+					children,
+				},
+			)
+		}
+		NodeData::Text { contents } => push(trees, Tree::Text(contents.borrow().to_string())),
+		NodeData::Comment { .. } => push(trees, Tree::Comment),
+		NodeData::Document | NodeData::Doctype { .. } | NodeData::ProcessingInstruction { .. } => {
+			unreachable!()
+		}
+	}
+}
+
+/// Parses `html` in the context of a `body` element.
+fn parse(html: &str) -> Vec<Tree> {
+	let dom = parse_fragment(
+		RcDom::default(),
+		ParseOpts::default(),
+		QualName::new(None, ns!(html), local_name!("body")),
+		Vec::new(),
+	)
+	.one(html);
+	// The fragment is wrapped in an `html` element.
+	let document = dom.document.children.borrow();
+	let root = &document[0];
+	let mut trees = Vec::new();
+	for child in root.children.borrow().iter() {
+		parsed(child, &mut trees)
+	}
+	trees
+}
+
+fn check(vdom: &Node<ThreadSafe>) -> bool {
+	let mut html = String::new();
+	if render_fragment(vdom, &mut html, 10).is_err() {
+		return false;
+	}
+	let mut expected_trees = Vec::new();
+	expected(vdom, &mut expected_trees);
+	assert_eq!(
+		parse(&html),
+		expected_trees,
+		"{:?} rendered as {:?}",
+		vdom,
+		html
+	);
+	true
+}
+
+const TEXTS: &[&str] = &[
+	"",
+	"plain",
+	"a < b",
+	"a<b",
+	"a & b",
+	"&amp;",
+	"&lt;",
+	"&notit;",
+	"&copy",
+	"&#x41;",
+	"&#",
+	"a > b",
+	"</div>",
+	"</p>",
+	"<!--",
+	"-->",
+	"<!DOCTYPE html>",
+	"<![CDATA[",
+	"]]>",
+	"\"'`=",
+	"\u{A0}",
+	"caf\u{E9}",
+	"<script>",
+	"</script>",
+	"</SCRIPT ",
+	"</style>",
+	"</title>",
+	"</textarea>",
+	"</pre>",
+	"\n",
+	"\nleading newline",
+	"\n\ntwo leading newlines",
+	"trailing newline\n",
+	"\t tabs and spaces ",
+];
+
+const ATTRIBUTE_VALUES: &[&str] = &[
+	"",
+	"plain",
+	"a b",
+	"a=b",
+	"a>b",
+	"a<b",
+	"a/b",
+	"/",
+	"a`b",
+	"\"",
+	"'",
+	"\"'",
+	"a\"b'c",
+	"&amp;",
+	"&",
+	"&copy",
+	"&notit;",
+	"a\nb",
+	" ",
+	"\t",
+	"\n\n",
+	"\r",
+	"\r\n",
+	" leading space",
+	"caf\u{E9}",
+	// Inline CSS, as in `style`.
+	"background:url('x')",
+	"content: \"x\"",
+	"font-family: \"A B\", 'C D'; content: '\\\"'",
+	"color:red;",
+];
+
+#[test]
+fn text() {
+	for &text in TEXTS {
+		for &name in &[
+			"div", "p", "span", "title", "textarea", "script", "style", "pre", "listing",
+		] {
+			let element = Element {
+				name,
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text,
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			};
+			check(&Node::HtmlElement {
+				element: &element,
+				dom_binding: None,
+			});
+		}
+	}
+}
+
+#[test]
+fn adjacent_text() {
+	for &a in TEXTS {
+		for &b in TEXTS {
+			check(&Node::Multi(&[
+				Node::Text {
+					text: a,
+					dom_binding: None,
+				},
+				Node::Text {
+					text: b,
+					dom_binding: None,
+				},
+			]));
+		}
+	}
+}
+
+#[test]
+fn comments() {
+	for &comment in TEXTS {
+		let element = Element {
+			name: "div",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Multi(&[
+				Node::Comment {
+					comment,
+					dom_binding: None,
+				},
+				Node::Text {
+					text: "after",
+					dom_binding: None,
+				},
+			]),
+			event_bindings: &[],
+		};
+		assert!(check(&Node::HtmlElement {
+			element: &element,
+			dom_binding: None,
+		}));
+	}
+}
+
+#[test]
+fn attribute_values() {
+	for &value in ATTRIBUTE_VALUES {
+		for &name in &["div", "img"] {
+			let attributes = [
+				Attribute {
+					name: "title",
+					value,
+				},
+				Attribute {
+					name: "class",
+					value,
+				},
+				Attribute {
+					name: "style",
+					value,
+				},
+			];
+			let element = Element {
+				name,
+				creation_options: ElementCreationOptions::new(),
+				attributes: &attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			};
+			assert!(check(&Node::HtmlElement {
+				element: &element,
+				dom_binding: None,
+			}));
+		}
+
+		// Empty foreign elements are self-closing, which interacts with unquoted values.
+		let attributes = [Attribute {
+			name: "class",
+			value,
+		}];
+		let rect = Element {
+			name: "rect",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &attributes,
+			content: Node::Multi(&[]),
+			event_bindings: &[],
+		};
+		let svg = Element {
+			name: "svg",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Multi(&[
+				Node::SvgElement {
+					element: &rect,
+					dom_binding: None,
+				},
+				Node::SvgElement {
+					element: &rect,
+					dom_binding: None,
+				},
+			]),
+			event_bindings: &[],
+		};
+		assert!(check(&Node::SvgElement {
+			element: &svg,
+			dom_binding: None,
+		}));
+	}
+}
+
+#[test]
+fn foreign_text() {
+	for &text in TEXTS {
+		for &name in &["text", "title", "style", "script"] {
+			let inner = Element {
+				name,
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text,
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			};
+			let svg = Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::SvgElement {
+					element: &inner,
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			};
+			assert!(check(&Node::SvgElement {
+				element: &svg,
+				dom_binding: None,
+			}));
+		}
+	}
+}
+
+/// Pieces of script that move the tokenizer between the ***script data*** states, including the ***double escaped*** ones.
+const SCRIPT_TEXTS: &[&str] = &[
+	"",
+	"x",
+	"<",
+	"</",
+	"<!",
+	"<!-",
+	"<!--",
+	"<!-->",
+	"-",
+	"--",
+	"-->",
+	"<script",
+	"<script>",
+	"<SCRIPT ",
+	"</script",
+	"</script>",
+	"</scRipt/",
+	"scr",
+	"ipt>",
+	"<!--<script>",
+	"<!--<script>x</script>-->",
+	"<!--<script></script><script>",
+	"<scripts>",
+	"</scripts>",
+];
+
+#[test]
+fn script_data() {
+	for &a in SCRIPT_TEXTS {
+		for &b in SCRIPT_TEXTS {
+			for &name in &["script", "style"] {
+				let texts = [
+					Node::Text {
+						text: a,
+						dom_binding: None,
+					},
+					Node::Text {
+						text: b,
+						dom_binding: None,
+					},
+				];
+				let element = Element {
+					name,
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					content: Node::Multi(&texts),
+					event_bindings: &[],
+				};
+				let after = Element {
+					name: "p",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					content: Node::Multi(&[]),
+					event_bindings: &[],
+				};
+				check(&Node::Multi(&[
+					Node::HtmlElement {
+						element: &element,
+						dom_binding: None,
+					},
+					Node::HtmlElement {
+						element: &after,
+						dom_binding: None,
+					},
+				]));
+			}
+		}
+	}
+}
@@ -0,0 +1,58 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render_img(attributes: &[Attribute], options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "img",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+const SRC: Attribute = Attribute {
+	name: "src",
+	value: "x",
+};
+
+#[test]
+fn off_by_default() {
+	assert_eq!(render_img(&[SRC], &RenderOptions::new()), "<img src=x>");
+}
+
+#[test]
+fn injected() {
+	assert_eq!(
+		render_img(&[SRC], &RenderOptions::new().with_image_perf_defaults(true)),
+		"<img src=x loading=lazy decoding=async>"
+	);
+}
+
+#[test]
+fn only_missing() {
+	assert_eq!(
+		render_img(
+			&[
+				Attribute {
+					name: "LOADING",
+					value: "eager",
+				},
+				SRC,
+			],
+			&RenderOptions::new().with_image_perf_defaults(true)
+		),
+		"<img LOADING=eager src=x decoding=async>"
+	);
+}
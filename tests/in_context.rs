@@ -0,0 +1,67 @@
+use lignin::{Node, ThreadSafe};
+use lignin_html::render_fragment_in_context;
+
+const TEXT: Node<'static, ThreadSafe> = Node::Text {
+	text: "a < b & c",
+	dom_binding: None,
+};
+
+fn render(vdom: &Node<'static, ThreadSafe>, parent: &'static str) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment_in_context(vdom, &mut fragment, 1, parent)
+		.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn normal() {
+	assert_eq!(render(&TEXT, "div").unwrap(), "a &lt; b &amp; c");
+}
+
+#[test]
+fn escapable_raw_text() {
+	assert_eq!(render(&TEXT, "title").unwrap(), "a < b &amp; c");
+}
+
+#[test]
+fn raw_text() {
+	assert_eq!(render(&TEXT, "style").unwrap(), "a < b & c");
+}
+
+#[test]
+fn raw_text_closed() {
+	render(
+		&Node::Text {
+			text: "</style>",
+			dom_binding: None,
+		},
+		"style",
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn void() {
+	render(&TEXT, "br").unwrap_err();
+	assert_eq!(render(&Node::Multi(&[]), "br").unwrap(), "");
+}
+
+#[test]
+fn invalid_parent() {
+	render(&TEXT, "").unwrap_err();
+}
+
+#[test]
+fn raw_text_closed_offset() {
+	assert_eq!(
+		render(
+			&Node::Text {
+				text: "a { } </style\tb",
+				dom_binding: None,
+			},
+			"style",
+		)
+		.unwrap_err(),
+		"Element closed in raw text of `<style>` at byte 6: \"</style\\t\""
+	);
+}
@@ -0,0 +1,45 @@
+use lignin_html::write_escaped_js_attribute;
+
+fn escape(value: &str) -> String {
+	let mut escaped = String::new();
+	write_escaped_js_attribute(value, &mut escaped).unwrap();
+	escaped
+}
+
+#[test]
+fn plain() {
+	assert_eq!(escape("Hello, wörld! 🌍"), "Hello, wörld! 🌍");
+}
+
+#[test]
+fn quotes() {
+	assert_eq!(escape(r#"'"`"#), r"\x27\x22\x60");
+}
+
+#[test]
+fn backslash_and_template() {
+	assert_eq!(escape(r"\${x}"), r"\x5C\x24{x}");
+}
+
+#[test]
+fn html_significant() {
+	assert_eq!(
+		escape("&quot;</script><b>"),
+		r"\x26quot;\x3C/script\x3E\x3Cb\x3E"
+	);
+}
+
+#[test]
+fn line_terminators_and_controls() {
+	assert_eq!(
+		escape("a\nb\rc\td\u{2028}e\u{2029}f\0g\u{1B}h\u{7F}"),
+		r"a\nb\rc\td\u2028e\u2029f\x00g\x1Bh\x7F"
+	);
+}
+
+#[test]
+fn nothing_escapes() {
+	let escaped = escape("\"'`&<>\\\n\r$");
+	assert!(!escaped.contains(&['"', '\'', '`', '&', '<', '>', '\n', '\r', '$'][..]));
+	assert_eq!(escaped.matches('\\').count(), 10);
+}
@@ -0,0 +1,32 @@
+use lignin::ThreadSafe;
+use lignin_html::render_json_script;
+
+#[test]
+fn with_id() {
+	let mut fragment = String::new();
+	render_json_script::<ThreadSafe>(
+		Some("initial-state"),
+		r#"{"html":"</script><!-- & -->"}"#,
+		&mut fragment,
+	)
+	.unwrap();
+	assert_eq!(
+		fragment,
+		r#"<script type=application/json id=initial-state>{"html":"\u003C/script\u003E\u003C!-- \u0026 --\u003E"}</script>"#
+	);
+}
+
+#[test]
+fn without_id() {
+	let mut fragment = String::new();
+	render_json_script::<ThreadSafe>(None, "[1,2]", &mut fragment).unwrap();
+	assert_eq!(fragment, "<script type=application/json>[1,2]</script>");
+}
+
+#[test]
+fn invalid_id() {
+	let mut fragment = String::new();
+	render_json_script::<ThreadSafe>(Some("initial state"), "{}", &mut fragment).unwrap_err();
+	render_json_script::<ThreadSafe>(Some(""), "{}", &mut fragment).unwrap_err();
+	assert_eq!(fragment, "");
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "alloc")]
+
+use lignin::{Node, ReorderableFragment, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+const TEXT: Node<'static, ThreadSafe> = Node::Text {
+	text: "x",
+	dom_binding: None,
+};
+
+fn render(vdom: &Node<'static, ThreadSafe>, strict_keys: bool) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		vdom,
+		&mut fragment,
+		&RenderOptions::new().with_strict_keys(strict_keys),
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn unique() {
+	let vdom = Node::Keyed(&[
+		ReorderableFragment {
+			dom_key: 1,
+			content: TEXT,
+		},
+		ReorderableFragment {
+			dom_key: 2,
+			content: TEXT,
+		},
+	]);
+	assert_eq!(render(&vdom, true).unwrap(), "xx");
+}
+
+#[test]
+fn duplicate() {
+	let vdom = Node::Keyed(&[
+		ReorderableFragment {
+			dom_key: 1,
+			content: TEXT,
+		},
+		ReorderableFragment {
+			dom_key: 1,
+			content: TEXT,
+		},
+	]);
+	assert_eq!(render(&vdom, false).unwrap(), "xx");
+	assert_eq!(
+		render(&vdom, true).unwrap_err(),
+		"Duplicate key 1 in a `Keyed` node"
+	);
+}
+
+#[test]
+fn sorted() {
+	let vdom = Node::Keyed(&[
+		ReorderableFragment {
+			dom_key: 3,
+			content: Node::Text {
+				text: "c",
+				dom_binding: None,
+			},
+		},
+		ReorderableFragment {
+			dom_key: 1,
+			content: Node::Text {
+				text: "a",
+				dom_binding: None,
+			},
+		},
+		ReorderableFragment {
+			dom_key: 2,
+			content: Node::Text {
+				text: "b",
+				dom_binding: None,
+			},
+		},
+	]);
+	assert_eq!(render(&vdom, false).unwrap(), "cab");
+
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&vdom,
+		&mut fragment,
+		&RenderOptions::new().with_sort_keyed(true),
+	)
+	.unwrap();
+	assert_eq!(fragment, "abc");
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "alloc")]
+
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+use std::borrow::Cow;
+
+fn cdn<'v>(element_name: &str, attribute_name: &str, value: &'v str) -> Option<Cow<'v, str>> {
+	match (element_name, attribute_name) {
+		("img", "src") if value.starts_with('/') => {
+			Some(Cow::Owned(format!("https://cdn.example.com{}", value)))
+		}
+		(_, "data-debug") => None,
+		_ => Some(Cow::Borrowed(value)),
+	}
+}
+
+fn render<'a>(vdom: &'a Node<'a, ThreadSafe>) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		vdom,
+		&mut fragment,
+		&RenderOptions::new().with_map_attribute(cdn),
+	)
+	.unwrap();
+	fragment
+}
+
+#[test]
+fn rewrites_src() {
+	assert_eq!(
+		render(&Node::HtmlElement {
+			element: &Element {
+				name: "img",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[
+					Attribute {
+						name: "src",
+						value: "/a b.png",
+					},
+					Attribute {
+						name: "data-debug",
+						value: "1",
+					},
+					Attribute {
+						name: "alt",
+						value: "",
+					},
+				],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}),
+		r#"<img src="https://cdn.example.com/a b.png" alt>"#
+	);
+}
+
+#[test]
+fn other_elements_are_unchanged() {
+	assert_eq!(
+		render(&Node::HtmlElement {
+			element: &Element {
+				name: "script",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[Attribute {
+					name: "src",
+					value: "/a.js",
+				}],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}),
+		"<script src=/a.js></script>"
+	);
+}
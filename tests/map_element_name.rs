@@ -0,0 +1,98 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn map(name: &str) -> Option<&str> {
+	match name {
+		"Fragment" => None,
+		"Button" => Some("button"),
+		name => Some(name),
+	}
+}
+
+fn render<'a>(vdom: &'a Node<'a, ThreadSafe>) -> Result<String, String> {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		vdom,
+		&mut fragment,
+		&RenderOptions::new().with_map_element_name(map),
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn fragment_renders_children_only() {
+	assert_eq!(
+		render(&Node::HtmlElement {
+			element: &Element {
+				name: "div",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::HtmlElement {
+					element: &Element {
+						name: "Fragment",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[Attribute {
+							name: "key",
+							value: "1",
+						}],
+						content: Node::Multi(&[
+							Node::Text {
+								text: "a",
+								dom_binding: None,
+							},
+							Node::HtmlElement {
+								element: &Element {
+									name: "Button",
+									creation_options: ElementCreationOptions::new(),
+									attributes: &[Attribute {
+										name: "type",
+										value: "button",
+									}],
+									content: Node::Multi(&[]),
+									event_bindings: &[],
+								},
+								dom_binding: None,
+							},
+						]),
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		})
+		.unwrap(),
+		"<div>a<button type=button></button></div>"
+	);
+}
+
+#[test]
+fn mapped_names_are_validated() {
+	fn map_invalid(_: &str) -> Option<&str> {
+		Some("x y")
+	}
+
+	let mut fragment = String::new();
+	assert_eq!(
+		render_fragment_with_options(
+			&Node::HtmlElement {
+				element: &Element {
+					name: "div",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					content: Node::Multi(&[]),
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			}
+			.prefer_thread_safe(),
+			&mut fragment,
+			&RenderOptions::new().with_map_element_name(map_invalid),
+		)
+		.unwrap_err()
+		.to_string(),
+		"Invalid element name \"x y\": ' ' can't appear in element names"
+	);
+}
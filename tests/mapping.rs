@@ -0,0 +1,87 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{html_element, leak, text};
+use lignin::{Node, ReorderableFragment, ThreadSafe};
+use lignin_html::{render_fragment, render_fragment_with_mapping, NodePath};
+use std::ops::Range;
+
+fn render(vdom: &Node<ThreadSafe>, depth_limit: usize) -> (String, Vec<(Range<usize>, NodePath)>) {
+	let mut html = String::new();
+	let mapping = render_fragment_with_mapping(vdom, &mut html, depth_limit).unwrap();
+	(html, mapping)
+}
+
+#[test]
+fn same_output() {
+	let vdom = html_element(
+		"div",
+		&[],
+		Node::Multi(leak([
+			text("a & b"),
+			html_element("br", &[], Node::Multi(&[])),
+		])),
+	);
+	let mut expected = String::new();
+	render_fragment(&vdom, &mut expected, 2).unwrap();
+	assert_eq!(render(&vdom, 2).0, expected);
+}
+
+#[test]
+fn nested() {
+	let vdom = Node::Multi(leak([
+		html_element(
+			"ul",
+			&[],
+			Node::Keyed(leak([
+				ReorderableFragment {
+					dom_key: 5,
+					content: html_element("li", &[], text("x")),
+				},
+				ReorderableFragment {
+					dom_key: 3,
+					content: Node::Memoized {
+						state_key: 0,
+						content: leak(html_element("li", &[], text("yz"))),
+					},
+				},
+			])),
+		),
+		text("<"),
+	]));
+	let (html, mapping) = render(&vdom, 3);
+	assert_eq!(html, "<ul><li>x</li><li>yz</li></ul>&lt;");
+	assert_eq!(
+		mapping,
+		[
+			(0..30, vec![0]),
+			(4..14, vec![0, 0, 0]),
+			(8..9, vec![0, 0, 0, 0]),
+			(14..25, vec![0, 0, 1, 0]),
+			(18..20, vec![0, 0, 1, 0, 0]),
+			(30..34, vec![1]),
+		]
+	);
+	for (range, _) in &mapping {
+		assert!(range.end <= html.len());
+	}
+}
+
+#[test]
+fn raw_text_content_not_mapped() {
+	let (html, mapping) = render(&html_element("script", &[], text("1 < 2")), 2);
+	assert_eq!(html, "<script>1 < 2</script>");
+	assert_eq!(mapping, [(0..html.len(), vec![])]);
+}
+
+#[test]
+fn transparent_root() {
+	assert_eq!(render(&Node::Multi(&[]), 1), (String::new(), vec![]));
+}
+
+#[test]
+fn error() {
+	let vdom = html_element("p", &[], text("a"));
+	assert!(render_fragment_with_mapping(&vdom, &mut String::new(), 1).is_err());
+}
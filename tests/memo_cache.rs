@@ -0,0 +1,44 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::leak;
+use lignin::{Node, ThreadSafe};
+use lignin_html::{render_fragment_cached, MemoCache};
+
+fn memoized(state_key: u64, text: &'static str) -> Node<'static, ThreadSafe> {
+	Node::Memoized {
+		state_key,
+		content: leak(common::text(text)),
+	}
+}
+
+fn render(vdom: &Node<'static, ThreadSafe>, cache: &mut MemoCache) -> String {
+	let mut fragment = String::new();
+	render_fragment_cached(vdom, &mut fragment, 3, cache).unwrap();
+	fragment
+}
+
+#[test]
+fn reused() {
+	let mut cache = MemoCache::new();
+	assert_eq!(render(&memoized(1, "a & b"), &mut cache), "a &amp; b");
+	assert_eq!(cache.len(), 1);
+
+	// Same key, so the content isn't looked at.
+	assert_eq!(render(&memoized(1, "changed"), &mut cache), "a &amp; b");
+
+	assert_eq!(render(&memoized(2, "other"), &mut cache), "other");
+	assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn invalidated() {
+	let mut cache = MemoCache::new();
+	assert_eq!(render(&memoized(1, "a"), &mut cache), "a");
+	assert!(cache.remove(1));
+	assert_eq!(render(&memoized(1, "b"), &mut cache), "b");
+	cache.clear();
+	assert!(cache.is_empty());
+	assert_eq!(render(&memoized(1, "c"), &mut cache), "c");
+}
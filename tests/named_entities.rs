@@ -0,0 +1,65 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render<'a>(vdom: &'a Node<'a, ThreadSafe>, options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(vdom, &mut fragment, options).unwrap();
+	fragment
+}
+
+const TEXT: Node<'static, ThreadSafe> = Node::Text {
+	text: "a\u{A0}b \u{A9} caf\u{E9}",
+	dom_binding: None,
+};
+
+#[test]
+fn nbsp() {
+	assert_eq!(
+		render(&TEXT, &RenderOptions::new().with_named_entities(true)),
+		"a&nbsp;b &copy; caf\u{E9}"
+	);
+}
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render(&TEXT, &RenderOptions::new()),
+		"a\u{A0}b \u{A9} caf\u{E9}"
+	);
+}
+
+#[test]
+fn with_ascii_only() {
+	assert_eq!(
+		render(
+			&TEXT,
+			&RenderOptions::new()
+				.with_named_entities(true)
+				.with_ascii_only(true)
+		),
+		"a&nbsp;b &copy; caf&#xe9;"
+	);
+}
+
+#[test]
+fn attribute_value() {
+	assert_eq!(
+		render(
+			&Node::HtmlElement {
+				element: &Element {
+					name: "p",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[Attribute {
+						name: "title",
+						value: "1\u{2013}2",
+					}],
+					content: Node::Multi(&[]),
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			},
+			&RenderOptions::new().with_named_entities(true)
+		),
+		"<p title=1&ndash;2></p>"
+	);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{element, leak, render_with};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::RenderOptions;
+
+/// `<svg viewbox="0 0 1 1" PRESERVEASPECTRATIO=none><lineargradient gradientunits=x/><foo barbaz/></svg><div viewbox></div>`
+fn tree() -> Node<'static, ThreadSafe> {
+	Node::Multi(leak([
+		Node::SvgElement {
+			element: element(
+				"svg",
+				&[
+					Attribute {
+						name: "viewbox",
+						value: "0 0 1 1",
+					},
+					Attribute {
+						name: "PRESERVEASPECTRATIO",
+						value: "none",
+					},
+				],
+				Node::Multi(leak([
+					Node::SvgElement {
+						element: element(
+							"lineargradient",
+							&[Attribute {
+								name: "gradientunits",
+								value: "x",
+							}],
+							Node::Multi(&[]),
+						),
+						dom_binding: None,
+					},
+					Node::SvgElement {
+						element: element(
+							"foo",
+							&[Attribute {
+								name: "barbaz",
+								value: "",
+							}],
+							Node::Multi(&[]),
+						),
+						dom_binding: None,
+					},
+				])),
+			),
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: element(
+				"div",
+				&[Attribute {
+					name: "viewbox",
+					value: "",
+				}],
+				Node::Multi(&[]),
+			),
+			dom_binding: None,
+		},
+	]))
+}
+
+fn render(options: &RenderOptions) -> String {
+	render_with(&tree(), options).unwrap()
+}
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render(&RenderOptions::new()),
+		r#"<svg viewbox="0 0 1 1" PRESERVEASPECTRATIO=none><lineargradient gradientunits=x /><foo barbaz/></svg><div viewbox></div>"#
+	);
+}
+
+#[test]
+fn normalized() {
+	assert_eq!(
+		render(&RenderOptions::new().with_normalize_svg_case(true)),
+		r#"<svg viewBox="0 0 1 1" preserveAspectRatio=none><linearGradient gradientUnits=x /><foo barbaz/></svg><div viewbox></div>"#
+	);
+}
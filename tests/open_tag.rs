@@ -0,0 +1,153 @@
+mod common;
+
+use common::element;
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{
+	render_fragment, render_fragment_with_options, render_svg_document, RenderOptions,
+};
+
+const ATTRIBUTES: &[Attribute] = &[
+	Attribute {
+		name: "hidden",
+		value: "",
+	},
+	Attribute {
+		name: "title",
+		value: "a b",
+	},
+	Attribute {
+		name: "data-x",
+		value: "y",
+	},
+];
+
+#[test]
+fn html() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "DIV",
+				creation_options: ElementCreationOptions::new().with_is(Some("x-div")),
+				attributes: ATTRIBUTES,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		2,
+	)
+	.unwrap();
+	assert_eq!(
+		fragment,
+		"<DIV is=x-div hidden title=\"a b\" data-x=y></DIV>"
+	);
+}
+
+#[test]
+fn foreign_self_closing() {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::SvgElement {
+			element: &Element {
+				name: "svg",
+				creation_options: ElementCreationOptions::new(),
+				attributes: ATTRIBUTES,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		&RenderOptions::new().with_xmlns(true),
+	)
+	.unwrap();
+	assert_eq!(
+		fragment,
+		"<svg xmlns=http://www.w3.org/2000/svg hidden title=\"a b\" data-x=y />"
+	);
+}
+
+#[test]
+fn self_closing_solidus_spacing() {
+	fn image(attributes: &'static [Attribute<'static>]) -> String {
+		let mut fragment = String::new();
+		render_fragment(
+			&Node::SvgElement {
+				element: &Element {
+					name: "image",
+					creation_options: ElementCreationOptions::new(),
+					attributes,
+					content: Node::Multi(&[]),
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			}
+			.prefer_thread_safe(),
+			&mut fragment,
+			2,
+		)
+		.unwrap();
+		fragment
+	}
+
+	assert_eq!(image(&[]), "<image/>");
+	assert_eq!(
+		image(&[Attribute {
+			name: "preserveAspectRatio",
+			value: "",
+		}]),
+		"<image preserveAspectRatio/>"
+	);
+	assert_eq!(
+		image(&[Attribute {
+			name: "href",
+			value: "a b",
+		}]),
+		"<image href=\"a b\"/>"
+	);
+	assert_eq!(
+		image(&[Attribute {
+			name: "href",
+			value: "x",
+		}]),
+		"<image href=x />"
+	);
+}
+
+#[test]
+fn xhtml_void() {
+	fn svg_with_img(attributes: &'static [Attribute<'static>]) -> String {
+		let mut document = String::new();
+		render_svg_document(
+			&Node::SvgElement {
+				element: &Element {
+					name: "foreignObject",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					content: Node::HtmlElement {
+						element: element("img", attributes, Node::Multi(&[])),
+						dom_binding: None,
+					},
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			}
+			.prefer_thread_safe(),
+			&mut document,
+			3,
+		)
+		.unwrap();
+		document
+	}
+
+	assert!(svg_with_img(&[]).contains("<img xmlns=\"http://www.w3.org/1999/xhtml\"/>"));
+	assert!(svg_with_img(&[Attribute {
+		name: "src",
+		value: "x"
+	}])
+	.contains("<img xmlns=\"http://www.w3.org/1999/xhtml\" src=\"x\"/>"));
+}
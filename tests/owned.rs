@@ -0,0 +1,34 @@
+#![cfg(feature = "alloc")]
+
+use lignin::{Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_to_string, render_fragment_to_vec};
+
+const VDOM: Node<'static, lignin::ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "P",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Text {
+			text: "Grüße & <3",
+			dom_binding: None,
+		},
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn string() {
+	assert_eq!(
+		render_fragment_to_string(&VDOM, 2).unwrap(),
+		"<P>Grüße &amp; &lt;3</P>"
+	);
+}
+
+#[test]
+fn vec() {
+	assert_eq!(
+		String::from_utf8(render_fragment_to_vec(&VDOM, 2).unwrap()).unwrap(),
+		"<P>Grüße &amp; &lt;3</P>"
+	);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "alloc")]
+
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment, OwnedError};
+
+fn render_temporary(name: &str) -> Result<String, OwnedError> {
+	let name = name.to_owned();
+	let element = Element {
+		name: &name,
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	};
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &element,
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		1,
+	)
+	.map_err(|error| error.into_owned())?;
+	Ok(fragment)
+}
+
+#[test]
+fn outlives_tree() {
+	assert_eq!(render_temporary("br").unwrap(), "<br>");
+
+	let error = render_temporary("x y").unwrap_err();
+	assert_eq!(
+		error.to_string(),
+		"Invalid element name \"x y\": ' ' can't appear in element names"
+	);
+	assert_eq!(error.clone(), error);
+}
+
+#[test]
+fn node_description() {
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name: "p",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Text {
+				text: "a",
+				dom_binding: None,
+			},
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+	let error = render_fragment(&vdom, &mut String::new(), 1).unwrap_err();
+	assert_eq!(error.into_owned().to_string(), error.to_string());
+	assert_eq!(
+		error.into_owned().to_string(),
+		"Depth limit of 1 exceeded at a text node"
+	);
+}
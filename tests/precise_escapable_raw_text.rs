@@ -0,0 +1,99 @@
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render(name: &str, texts: &[&str], precise: bool) -> String {
+	let nodes: Vec<Node<ThreadSafe>> = texts
+		.iter()
+		.map(|&text| Node::Text {
+			text,
+			dom_binding: None,
+		})
+		.collect();
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name,
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Multi(&nodes),
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+	let mut html = String::new();
+	render_fragment_with_options(
+		&vdom,
+		&mut html,
+		&RenderOptions::new().with_precise_escapable_raw_text(precise),
+	)
+	.unwrap();
+	html
+}
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render("title", &["a</b>c"], false),
+		"<title>a&lt;/b>c</title>"
+	);
+}
+
+#[test]
+fn other_end_tags_verbatim() {
+	assert_eq!(render("title", &["a</b>c"], true), "<title>a</b>c</title>");
+	assert_eq!(
+		render("textarea", &["</title> </textareas>"], true),
+		"<textarea>\n</title> </textareas></textarea>"
+	);
+}
+
+#[test]
+fn own_end_tag_escaped() {
+	for &text in &["</title>", "</TITLE >", "</title/", "</title\n"] {
+		assert_eq!(
+			render("title", &[text], true),
+			format!("<title>&lt;{}</title>", &text[1..])
+		);
+	}
+}
+
+#[test]
+fn across_text_nodes() {
+	// Each of these could be continued into the end tag by the next node.
+	assert_eq!(
+		render("title", &["a<", "/title>"], true),
+		"<title>a&lt;/title></title>"
+	);
+	assert_eq!(
+		render("title", &["a</", "title>"], true),
+		"<title>a&lt;/title></title>"
+	);
+	assert_eq!(
+		render("title", &["a</ti", "tle>"], true),
+		"<title>a&lt;/title></title>"
+	);
+	assert_eq!(
+		render("title", &["a</title", ">"], true),
+		"<title>a&lt;/title></title>"
+	);
+	// This can't.
+	assert_eq!(
+		render("title", &["a<b", "/title>"], true),
+		"<title>a<b/title></title>"
+	);
+}
+
+#[test]
+fn ampersands() {
+	assert_eq!(
+		render("title", &["a & b"], true),
+		"<title>a &amp; b</title>"
+	);
+	assert_eq!(
+		render("title", &["&copy; 2021 &amp; &#xA9; &copy"], true),
+		"<title>&copy; 2021 &amp; &#xA9; &amp;copy</title>"
+	);
+	assert_eq!(
+		render("textarea", &["&copy;"], false),
+		"<textarea>\n&amp;copy;</textarea>"
+	);
+}
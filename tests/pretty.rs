@@ -0,0 +1,81 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_with_options, PrettyOptions, RenderOptions};
+
+fn render_div(attributes: &[Attribute], options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "div",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+const LONG: &[Attribute] = &[
+	Attribute {
+		name: "id",
+		value: "a-fairly-long-identifier",
+	},
+	Attribute {
+		name: "class",
+		value: "several classes for styling",
+	},
+	Attribute {
+		name: "title",
+		value: "A descriptive title",
+	},
+	Attribute {
+		name: "data-something",
+		value: "more-data-here",
+	},
+	Attribute {
+		name: "hidden",
+		value: "",
+	},
+];
+
+#[test]
+fn wrapped() {
+	assert_eq!(
+		render_div(
+			LONG,
+			&RenderOptions::new().with_pretty(Some(PrettyOptions::new().with_max_line_width(80)))
+		),
+		concat!(
+			"<div",
+			"\n\tid=a-fairly-long-identifier",
+			"\n\tclass=\"several classes for styling\"",
+			"\n\ttitle=\"A descriptive title\"",
+			"\n\tdata-something=more-data-here",
+			"\n\thidden>",
+			"</div>",
+		)
+	);
+}
+
+#[test]
+fn short_enough() {
+	assert_eq!(
+		render_div(
+			&LONG[..1],
+			&RenderOptions::new().with_pretty(Some(PrettyOptions::new().with_max_line_width(37)))
+		),
+		"<div id=a-fairly-long-identifier></div>"
+	);
+}
+
+#[test]
+fn not_pretty_by_default() {
+	assert!(!render_div(LONG, &RenderOptions::new()).contains('\n'));
+}
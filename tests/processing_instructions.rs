@@ -0,0 +1,84 @@
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_svg_document_with_prolog, write_processing_instruction};
+
+fn write(target_name: &str, data: &str) -> Result<String, String> {
+	let mut xml = String::new();
+	write_processing_instruction::<ThreadSafe>(&mut xml, target_name, data)
+		.map_err(|error| error.to_string())?;
+	Ok(xml)
+}
+
+#[test]
+fn well_formed() {
+	assert_eq!(
+		write("xml-stylesheet", r#"href="style.css" type="text/css""#).unwrap(),
+		r#"<?xml-stylesheet href="style.css" type="text/css"?>"#
+	);
+	assert_eq!(write("pi", "").unwrap(), "<?pi?>");
+	assert_eq!(write("x.y-1", "a?b>c").unwrap(), "<?x.y-1 a?b>c?>");
+	assert_eq!(write("caf\u{E9}", "\t\n").unwrap(), "<?caf\u{E9} \t\n?>");
+}
+
+#[test]
+fn data_ends_instruction() {
+	assert_eq!(
+		write("pi", "a ?> b").unwrap_err(),
+		"Invalid processing instruction data \"a ?> b\""
+	);
+}
+
+#[test]
+fn invalid_data_characters() {
+	write("pi", "\0").unwrap_err();
+	write("pi", "\u{1B}").unwrap_err();
+	write("pi", "\u{FFFF}").unwrap_err();
+}
+
+#[test]
+fn invalid_targets() {
+	for &target_name in &[
+		"", "xml", "XmL", "1pi", "-pi", "a b", "a:b", "a?", "p\u{D7}",
+	] {
+		assert_eq!(
+			write(target_name, "").unwrap_err(),
+			format!("Invalid processing instruction target {:?}", target_name)
+		);
+	}
+	// Only exactly `xml` is reserved.
+	write("xml-stylesheet", "").unwrap();
+	write("xmlfoo", "").unwrap();
+}
+
+const SVG: Node<'static, ThreadSafe> = Node::SvgElement {
+	element: &Element {
+		name: "svg",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn prolog() {
+	let mut svg = String::new();
+	render_svg_document_with_prolog(
+		&SVG,
+		&mut svg,
+		1,
+		&[("xml-stylesheet", "href=\"a.css\""), ("pi", "")],
+	)
+	.unwrap();
+	assert_eq!(
+		svg,
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?><?xml-stylesheet href=\"a.css\"?><?pi?><svg xmlns=\"http://www.w3.org/2000/svg\"/>"
+	);
+}
+
+#[test]
+fn invalid_prolog_writes_nothing() {
+	let mut svg = String::new();
+	render_svg_document_with_prolog(&SVG, &mut svg, 1, &[("pi", ""), ("pi", "?>")]).unwrap_err();
+	assert_eq!(svg, "");
+}
@@ -0,0 +1,96 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render_input(attributes: &[Attribute], options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "input",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+const EMPTY: &[Attribute] = &[
+	Attribute {
+		name: "required",
+		value: "",
+	},
+	Attribute {
+		name: "value",
+		value: "",
+	},
+];
+
+/// Empty values are written in presence form, which parses to the same empty value.
+#[test]
+fn bare_by_default() {
+	assert_eq!(
+		RenderOptions::new().quote_empty_attributes(),
+		&[] as &[&str]
+	);
+	assert_eq!(
+		render_input(EMPTY, &RenderOptions::new()),
+		"<input required value>"
+	);
+}
+
+#[test]
+fn value_quoted() {
+	assert_eq!(
+		render_input(
+			EMPTY,
+			&RenderOptions::new().with_quote_empty_attributes(&["VALUE"])
+		),
+		r#"<input required value="">"#
+	);
+}
+
+#[test]
+fn non_empty_unaffected() {
+	assert_eq!(
+		render_input(
+			&[Attribute {
+				name: "value",
+				value: "x",
+			}],
+			&RenderOptions::new().with_quote_empty_attributes(&["value"])
+		),
+		"<input value=x>"
+	);
+}
+
+#[test]
+fn prefix() {
+	assert_eq!(
+		render_input(
+			&[
+				Attribute {
+					name: "data-a",
+					value: "",
+				},
+				Attribute {
+					name: "data-b",
+					value: "",
+				},
+				Attribute {
+					name: "disabled",
+					value: "",
+				},
+			],
+			&RenderOptions::new().with_quote_empty_attributes(&["data-*"])
+		),
+		r#"<input data-a="" data-b="" disabled>"#
+	);
+}
@@ -0,0 +1,112 @@
+use lignin::{Element, ElementCreationOptions, Node};
+use lignin_html::render_fragment;
+
+#[test]
+fn multi_byte_character_after_lt_solidus() {
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "script",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text: "</scrip\u{E9}x>",
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		2,
+	)
+	.unwrap();
+	assert_eq!(fragment, "<script></scrip\u{E9}x></script>");
+}
+
+fn render_script(texts: &[&'static str]) -> Result<String, String> {
+	let texts: Vec<_> = texts
+		.iter()
+		.map(|&text| Node::Text {
+			text,
+			dom_binding: None,
+		})
+		.collect();
+	let element = Element {
+		name: "script",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&texts),
+		event_bindings: &[],
+	};
+	let mut fragment = String::new();
+	render_fragment(
+		&Node::HtmlElement {
+			element: &element,
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		2,
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(fragment)
+}
+
+#[test]
+fn double_escaped_and_closed() {
+	assert_eq!(
+		render_script(&["<!--<script>x</script>-->"]).unwrap(),
+		"<script><!--<script>x</script>--></script>"
+	);
+	assert_eq!(
+		render_script(&["<!--<script></script>"]).unwrap(),
+		"<script><!--<script></script></script>"
+	);
+	assert_eq!(
+		render_script(&["<!--<script>", "</script>-->"]).unwrap(),
+		"<script><!--<script></script>--></script>"
+	);
+}
+
+#[test]
+fn double_escaped_unclosed() {
+	for &text in &[
+		"<!--<script>",
+		"<!--<script>x",
+		"<!--<SCRIPT/",
+		"<!--<script>-",
+		"<!--<script></script",
+	] {
+		assert_eq!(
+			render_script(&[text]).unwrap_err(),
+			"Raw text of `<script>` ends within `<!--<script>`, where `</script>` doesn't close the element",
+			"{:?}",
+			text
+		);
+	}
+}
+
+#[test]
+fn double_escape_ended() {
+	// `-->` leaves even the double escaped state.
+	render_script(&["<!--<script>-->"]).unwrap();
+	// Not `script`.
+	render_script(&["<!--<scripts>"]).unwrap();
+	// No `<!--` before it.
+	render_script(&["<script>"]).unwrap();
+}
+
+#[test]
+fn closed_across_text_nodes() {
+	assert_eq!(
+		render_script(&["</scr", "ipt>"]).unwrap_err(),
+		"Element closed in raw text of `<script>` at byte 0: \"ipt>\""
+	);
+	assert_eq!(
+		render_script(&["<!--", "</script>"]).unwrap_err(),
+		"Element closed in raw text of `<script>` at byte 0: \"</script>\""
+	);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::{html_element, leak, text};
+use lignin::{Node, ReorderableFragment, ThreadSafe};
+use lignin_html::render_fragment;
+
+const PARENTS: &[&str] = &["script", "style", "title", "textarea"];
+
+/// Each wrapper [`Node`] kind around `a` and `b`, and all of them nested.
+fn wrapped(
+	a: Node<'static, ThreadSafe>,
+	b: Node<'static, ThreadSafe>,
+) -> Vec<Node<'static, ThreadSafe>> {
+	let keyed = Node::Keyed(leak([
+		ReorderableFragment {
+			dom_key: 1,
+			content: a,
+		},
+		ReorderableFragment {
+			dom_key: 2,
+			content: b,
+		},
+	]));
+	let multi = Node::Multi(leak([a, b]));
+	vec![
+		multi,
+		keyed,
+		Node::Memoized {
+			state_key: 0,
+			content: leak(multi),
+		},
+		Node::Multi(leak([
+			Node::Memoized {
+				state_key: 1,
+				content: leak(a),
+			},
+			Node::Keyed(leak([ReorderableFragment {
+				dom_key: 1,
+				content: Node::Multi(leak([b])),
+			}])),
+		])),
+	]
+}
+
+fn render(vdom: &Node<'static, ThreadSafe>) -> Result<String, String> {
+	let mut html = String::new();
+	render_fragment(vdom, &mut html, 10).map_err(|error| error.to_string())?;
+	Ok(html)
+}
+
+#[test]
+fn text_is_concatenated() {
+	for &parent in PARENTS {
+		let newline = if parent == "textarea" { "\n" } else { "" };
+		for content in wrapped(text("a"), text("b")) {
+			assert_eq!(
+				render(&html_element(parent, &[], content)),
+				Ok(format!("<{0}>{1}ab</{0}>", parent, newline)),
+				"{:?}",
+				content
+			);
+		}
+	}
+}
+
+#[test]
+fn nested_elements_are_rejected() {
+	for &parent in PARENTS {
+		for content in wrapped(text("a"), html_element("b", &[], text("x"))) {
+			let error = render(&html_element(parent, &[], content)).unwrap_err();
+			assert!(
+				error.starts_with("a `<b>` element can't appear in the ")
+					&& error.contains(&format!("`<{}>`", parent)),
+				"{}",
+				error
+			);
+		}
+	}
+}
+
+#[test]
+fn nested_comments_are_rejected() {
+	let comment = Node::Comment {
+		comment: "x",
+		dom_binding: None,
+	};
+	for &parent in PARENTS {
+		for content in wrapped(comment, text("a")) {
+			let error = render(&html_element(parent, &[], content)).unwrap_err();
+			assert!(
+				error.starts_with("a comment can't appear in the "),
+				"{}",
+				error
+			);
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn sort_keyed() {
+	use lignin_html::{render_fragment_with_options, RenderOptions};
+
+	for &parent in PARENTS {
+		let vdom = html_element(
+			parent,
+			&[],
+			Node::Keyed(leak([
+				ReorderableFragment {
+					dom_key: 2,
+					content: text("b"),
+				},
+				ReorderableFragment {
+					dom_key: 1,
+					content: text("a"),
+				},
+			])),
+		);
+		let mut html = String::new();
+		render_fragment_with_options(
+			&vdom,
+			&mut html,
+			&RenderOptions::new().with_sort_keyed(true),
+		)
+		.unwrap();
+		assert!(html.ends_with(&format!("ab</{}>", parent)), "{}", html);
+	}
+}
@@ -0,0 +1,73 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render_input(attributes: &[Attribute], options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "input",
+				creation_options: ElementCreationOptions::new(),
+				attributes,
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+const PASSWORD: &[Attribute] = &[
+	Attribute {
+		name: "type",
+		value: "password",
+	},
+	Attribute {
+		name: "VALUE",
+		value: "hunter2",
+	},
+];
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render_input(PASSWORD, &RenderOptions::new()),
+		"<input type=password VALUE=hunter2>"
+	);
+}
+
+#[test]
+fn value() {
+	assert_eq!(
+		render_input(
+			PASSWORD,
+			&RenderOptions::new().with_redact_attributes(&["value"])
+		),
+		"<input type=password VALUE=[redacted]>"
+	);
+}
+
+#[test]
+fn prefix() {
+	assert_eq!(
+		render_input(
+			&[
+				Attribute {
+					name: "data-secret-token",
+					value: "abc",
+				},
+				Attribute {
+					name: "data-secret",
+					value: "def",
+				},
+			],
+			&RenderOptions::new().with_redact_attributes(&["DATA-SECRET-*"])
+		),
+		"<input data-secret-token=[redacted] data-secret=def>"
+	);
+}
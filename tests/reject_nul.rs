@@ -0,0 +1,55 @@
+mod common;
+
+use common::{html_element, render_with as render, text};
+use lignin::{Attribute, Node};
+use lignin_html::{CommentPolicy, RenderOptions};
+
+const REJECT: RenderOptions = RenderOptions::new().with_reject_nul(true);
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render(&text("a\0b"), &RenderOptions::new()).unwrap(),
+		"a\0b"
+	);
+}
+
+#[test]
+fn text_node() {
+	assert_eq!(
+		render(&text("a\0b"), &REJECT).unwrap_err(),
+		"\"a\\0b\" contains NUL, which would be written as-is"
+	);
+	assert_eq!(render(&text("ab"), &REJECT).unwrap(), "ab");
+}
+
+#[test]
+fn raw_text() {
+	for &name in &["script", "style", "title", "textarea"] {
+		assert!(render(&html_element(name, &[], text("\0")), &REJECT).is_err());
+	}
+}
+
+#[test]
+fn attribute_value() {
+	let attributes = [Attribute {
+		name: "title",
+		value: "\0",
+	}];
+	assert!(render(&html_element("p", &attributes, Node::Multi(&[])), &REJECT).is_err());
+}
+
+#[test]
+fn comments() {
+	let comment = Node::Comment {
+		comment: "\0",
+		dom_binding: None,
+	};
+	// Already replaced with U+FFFD.
+	assert_eq!(render(&comment, &REJECT).unwrap(), "<!--\u{FFFD}-->");
+	assert!(render(
+		&comment,
+		&REJECT.with_comment_policy(CommentPolicy::Verbatim)
+	)
+	.is_err());
+}
@@ -0,0 +1,106 @@
+use lignin::{Attribute, ThreadSafe};
+use lignin_html::render_attributes;
+
+fn render(attributes: &[Attribute]) -> Result<String, String> {
+	let mut rendered = String::new();
+	render_attributes::<ThreadSafe>(attributes, &mut rendered)
+		.map_err(|error| error.to_string())?;
+	Ok(rendered)
+}
+
+#[test]
+fn empty() {
+	assert_eq!(render(&[]).unwrap(), "");
+}
+
+#[test]
+fn quoting() {
+	assert_eq!(
+		render(&[
+			Attribute {
+				name: "hidden",
+				value: "",
+			},
+			Attribute {
+				name: "id",
+				value: "x",
+			},
+			Attribute {
+				name: "title",
+				value: "it's \"here\" & there",
+			},
+			Attribute {
+				name: "alt",
+				value: "say \"hi\"",
+			},
+		])
+		.unwrap(),
+		" hidden id=x title=\"it's &quot;here&quot; &amp; there\" alt='say \"hi\"'"
+	);
+}
+
+#[test]
+fn invalid() {
+	assert_eq!(
+		render(&[Attribute {
+			name: "is",
+			value: "x-y",
+		}])
+		.unwrap_err(),
+		"Reserved attribute name \"is\"; specify through `Element::creation_options` instead"
+	);
+	assert_eq!(
+		render(&[Attribute {
+			name: "a b",
+			value: "",
+		}])
+		.unwrap_err(),
+		"Invalid attribute name \"a b\""
+	);
+}
+
+#[test]
+fn noncharacters() {
+	let noncharacters = (0xFDD0..=0xFDEF)
+		.chain((0..=0x10).flat_map(|plane| {
+			let last = (plane << 16) | 0xFFFF;
+			vec![last - 1, last]
+		}))
+		.map(|c| char::from_u32(c).unwrap());
+	for c in noncharacters {
+		let name = format!("a{}", c);
+		assert_eq!(
+			render(&[Attribute {
+				name: &name,
+				value: "",
+			}])
+			.unwrap_err(),
+			format!("Invalid attribute name {:?}", name),
+			"U+{:X}",
+			c as u32
+		);
+	}
+}
+
+#[test]
+fn noncharacter_neighbours() {
+	for &c in &[
+		'\u{FDCF}',
+		'\u{FDF0}',
+		'\u{FFFD}',
+		'\u{10000}',
+		'\u{1FFFD}',
+		'\u{20000}',
+		'\u{10FFFD}',
+	] {
+		let name = format!("a{}", c);
+		assert_eq!(
+			render(&[Attribute {
+				name: &name,
+				value: "",
+			}])
+			.unwrap(),
+			format!(" {}", name)
+		);
+	}
+}
@@ -0,0 +1,89 @@
+use core::fmt::Write;
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment, Error, RenderHtml};
+
+/// Either a VDOM or app-specific data.
+enum Item<'a> {
+	Vdom(Node<'a, ThreadSafe>),
+	Price { cents: u32 },
+}
+
+impl<'a> RenderHtml<'a, ThreadSafe> for Item<'a> {
+	fn render_html(
+		&'a self,
+		target: &mut dyn Write,
+		depth_limit: usize,
+	) -> Result<(), Error<'a, ThreadSafe>> {
+		match self {
+			Item::Vdom(vdom) => vdom.render_html(target, depth_limit),
+			Item::Price { cents } => Ok(write!(
+				target,
+				"<data value={}>${}.{:02}</data>",
+				cents,
+				cents / 100,
+				cents % 100
+			)?),
+		}
+	}
+}
+
+/// Composes other [`RenderHtml`]s.
+struct List<'a>(&'a [&'a dyn RenderHtml<'a, ThreadSafe>]);
+
+impl<'a> RenderHtml<'a, ThreadSafe> for List<'a> {
+	fn render_html(
+		&'a self,
+		target: &mut dyn Write,
+		depth_limit: usize,
+	) -> Result<(), Error<'a, ThreadSafe>> {
+		for item in self.0 {
+			target.write_str("<li>")?;
+			item.render_html(target, depth_limit - 1)?;
+			target.write_str("</li>")?;
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn node_matches_render_fragment() {
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name: "b",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Text {
+				text: "<3",
+				dom_binding: None,
+			},
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+
+	let mut expected = String::new();
+	render_fragment(&vdom, &mut expected, 2).unwrap();
+	let mut html = String::new();
+	vdom.render_html(&mut html, 2).unwrap();
+	assert_eq!(html, expected);
+
+	assert!(vdom.render_html(&mut String::new(), 1).is_err());
+}
+
+#[test]
+fn custom() {
+	let text = Item::Vdom(Node::Text {
+		text: "a & b",
+		dom_binding: None,
+	});
+	let price = Item::Price { cents: 1234 };
+	let items: [&dyn RenderHtml<ThreadSafe>; 2] = [&text, &price];
+	let list = List(&items);
+
+	let mut html = String::new();
+	list.render_html(&mut html, 2).unwrap();
+	assert_eq!(
+		html,
+		"<li>a &amp; b</li><li><data value=1234>$12.34</data></li>"
+	);
+}
@@ -0,0 +1,53 @@
+use lignin::{Element, ElementCreationOptions, Node, ReorderableFragment, ThreadSafe};
+use lignin_html::{render_fragment, render_keyed_by_key};
+
+const KEYED: &[ReorderableFragment<ThreadSafe>] = &[
+	ReorderableFragment {
+		dom_key: 1,
+		content: Node::Text {
+			text: "one",
+			dom_binding: None,
+		},
+	},
+	ReorderableFragment {
+		dom_key: 2,
+		content: Node::HtmlElement {
+			element: &Element {
+				name: "b",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text: "two & more",
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		},
+	},
+];
+
+#[test]
+fn present() {
+	let mut html = String::new();
+	assert!(render_keyed_by_key(KEYED, 2, &mut html, 2).unwrap());
+	assert_eq!(html, "<b>two &amp; more</b>");
+
+	// The same as within the whole `Node::Keyed`.
+	let mut whole = String::new();
+	render_fragment(&Node::Keyed(KEYED), &mut whole, 2).unwrap();
+	assert_eq!(whole, format!("one{}", html));
+}
+
+#[test]
+fn absent() {
+	let mut html = String::new();
+	assert!(!render_keyed_by_key(KEYED, 3, &mut html, 2).unwrap());
+	assert_eq!(html, "");
+}
+
+#[test]
+fn depth_limit() {
+	assert!(render_keyed_by_key(KEYED, 2, &mut String::new(), 1).is_err());
+	assert!(render_keyed_by_key(KEYED, 1, &mut String::new(), 1).unwrap());
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::text;
+use lignin_html::render_pair;
+
+#[test]
+fn same() {
+	let (old, new, difference) = render_pair(&text("a"), &text("a"), 1).unwrap();
+	assert_eq!((old.as_str(), new.as_str(), difference), ("a", "a", None));
+}
+
+#[test]
+fn different() {
+	let (old, new, difference) = render_pair(&text("a<b"), &text("a&b"), 1).unwrap();
+	assert_eq!(
+		(old.as_str(), new.as_str(), difference),
+		("a&lt;b", "a&amp;b", Some(2))
+	);
+}
+
+#[test]
+fn prefix() {
+	let (_, _, difference) = render_pair(&text("ab"), &text("abc"), 1).unwrap();
+	assert_eq!(difference, Some(2));
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{leak, render_with};
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::RenderOptions;
+
+static DIV: Node<'static, ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "DIV",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Text {
+			text: "x",
+			dom_binding: None,
+		},
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+fn render(
+	vdom: &'static Node<'static, ThreadSafe>,
+	repeat_limit: Option<usize>,
+) -> Result<String, String> {
+	render_with(vdom, &RenderOptions::new().with_repeat_limit(repeat_limit))
+}
+
+#[test]
+fn repeated() {
+	let vdom = leak(Node::Multi(
+		(0..3)
+			.map(|_| Node::Memoized {
+				state_key: 0,
+				content: &DIV,
+			})
+			.collect::<Vec<_>>()
+			.leak(),
+	));
+
+	assert_eq!(
+		render(vdom, None).unwrap(),
+		"<DIV>x</DIV><DIV>x</DIV><DIV>x</DIV>"
+	);
+	assert_eq!(
+		render(vdom, Some(3)).unwrap(),
+		"<DIV>x</DIV><DIV>x</DIV><DIV>x</DIV>"
+	);
+	assert_eq!(
+		render(vdom, Some(2)).unwrap_err(),
+		"a `<DIV>` element was rendered more than 2 times"
+	);
+}
+
+#[test]
+fn copies_count_as_the_same_node() {
+	static COPIES: Node<'static, ThreadSafe> = Node::Multi(&[DIV; 5]);
+
+	assert_eq!(
+		render(&COPIES, Some(5)).unwrap(),
+		"<DIV>x</DIV><DIV>x</DIV><DIV>x</DIV><DIV>x</DIV><DIV>x</DIV>"
+	);
+	assert_eq!(
+		render(&COPIES, Some(1)).unwrap_err(),
+		"a `<DIV>` element was rendered more than 1 times"
+	);
+}
+
+#[test]
+fn copies_of_wrappers_around_text_count() {
+	static TEXT: Node<'static, ThreadSafe> = Node::Text {
+		text: "x",
+		dom_binding: None,
+	};
+	static TEXTS: [Node<'static, ThreadSafe>; 1] = [TEXT];
+	static MULTI: Node<'static, ThreadSafe> =
+		Node::Multi(&[Node::Multi(&TEXTS), Node::Multi(&TEXTS)]);
+	static MEMOIZED: Node<'static, ThreadSafe> = Node::Multi(&[
+		Node::Memoized {
+			state_key: 0,
+			content: &TEXT,
+		},
+		Node::Memoized {
+			state_key: 0,
+			content: &TEXT,
+		},
+	]);
+
+	assert_eq!(render(&MULTI, Some(2)).unwrap(), "xx");
+	assert_eq!(
+		render(&MULTI, Some(1)).unwrap_err(),
+		"a `Multi` node was rendered more than 1 times"
+	);
+	assert_eq!(render(&MEMOIZED, Some(2)).unwrap(), "xx");
+	assert_eq!(
+		render(&MEMOIZED, Some(1)).unwrap_err(),
+		"a `Memoized` node was rendered more than 1 times"
+	);
+}
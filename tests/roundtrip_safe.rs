@@ -0,0 +1,94 @@
+mod common;
+
+use common::{html_element, leak, render_with, text};
+use lignin::{Node, ReorderableFragment, ThreadSafe};
+use lignin_html::RenderOptions;
+
+fn render(vdom: &Node<ThreadSafe>, roundtrip_safe: bool) -> String {
+	render_with(
+		vdom,
+		&RenderOptions::new().with_roundtrip_safe(roundtrip_safe),
+	)
+	.unwrap()
+}
+
+#[test]
+fn off_by_default() {
+	assert_eq!(render(&Node::Multi(&[text("a"), text("b")]), false), "ab");
+}
+
+#[test]
+fn adjacent_text() {
+	assert_eq!(
+		render(&Node::Multi(&[text("a"), text("b"), text("c")]), true),
+		"a<!---->b<!---->c"
+	);
+}
+
+#[test]
+fn across_groups() {
+	let vdom = Node::Multi(leak([
+		Node::Multi(leak([text("a")])),
+		Node::Keyed(leak([ReorderableFragment {
+			dom_key: 0,
+			content: text("b"),
+		}])),
+		html_element("lignin:fragment", &[], text("c")),
+	]));
+	assert_eq!(render(&vdom, true), "a<!---->b<!---->c");
+}
+
+#[test]
+fn only_where_needed() {
+	let vdom = Node::Multi(leak([
+		text("a"),
+		text(""),
+		html_element("b", &[], text("c")),
+		text("d"),
+		Node::Comment {
+			comment: "e",
+			dom_binding: None,
+		},
+		text("f"),
+		html_element("title", &[], Node::Multi(leak([text("g"), text("h")]))),
+		text("i"),
+	]));
+	assert_eq!(render(&vdom, true), "a<b>c</b>d<!--e-->f<title>gh</title>i");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn manifest() {
+	use lignin_html::render_fragment_roundtrip_safe;
+
+	let vdom = Node::Multi(leak([
+		text("a"),
+		text("b"),
+		Node::Comment {
+			comment: "c-->",
+			dom_binding: None,
+		},
+		Node::Comment {
+			comment: "d",
+			dom_binding: None,
+		},
+		Node::Comment {
+			comment: "<!--e",
+			dom_binding: None,
+		},
+		text("f"),
+		text("g"),
+	]));
+	let mut html = String::new();
+	let manifest = render_fragment_roundtrip_safe(&vdom, &mut html, 1).unwrap();
+	assert_eq!(html, "a<!---->b<!--c==>--><!--d--><!--<!==e-->f<!---->g");
+	assert_eq!(manifest.boundaries, [1..8, 41..48]);
+	assert_eq!(manifest.legalized_comments, [9..20, 28..40]);
+
+	// Removing the boundaries gives the plain rendering.
+	let mut stripped = html.clone();
+	for boundary in manifest.boundaries.iter().rev() {
+		stripped.replace_range(boundary.clone(), "");
+	}
+	assert_eq!(stripped, render(&vdom, false));
+}
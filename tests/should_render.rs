@@ -0,0 +1,124 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadBound, ThreadSafe};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn flag_enabled(element: &Element<ThreadBound>) -> bool {
+	!element
+		.attributes
+		.iter()
+		.any(|attribute| attribute.name == "data-flag" && attribute.value == "beta")
+}
+
+/// Sees more than the name and attributes.
+fn not_empty_widget(element: &Element<ThreadBound>) -> bool {
+	!(element.creation_options.is() == Some("x-widget") && element.content.dom_empty())
+}
+
+fn render<'a>(vdom: &'a Node<'a, ThreadSafe>, options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(vdom, &mut fragment, options).unwrap();
+	fragment
+}
+
+#[test]
+fn skips_subtree() {
+	let vdom: Node<ThreadSafe> = Node::HtmlElement {
+		element: &Element {
+			name: "div",
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			content: Node::Multi(&[
+				Node::HtmlElement {
+					element: &Element {
+						name: "section",
+						creation_options: ElementCreationOptions::new(),
+						attributes: &[Attribute {
+							name: "data-flag",
+							value: "beta",
+						}],
+						content: Node::HtmlElement {
+							element: &Element {
+								name: "p",
+								creation_options: ElementCreationOptions::new(),
+								attributes: &[],
+								content: Node::Text {
+									text: "New!",
+									dom_binding: None,
+								},
+								event_bindings: &[],
+							},
+							dom_binding: None,
+						},
+						event_bindings: &[],
+					},
+					dom_binding: None,
+				},
+				Node::Text {
+					text: "Old.",
+					dom_binding: None,
+				},
+			]),
+			event_bindings: &[],
+		},
+		dom_binding: None,
+	};
+
+	assert_eq!(
+		render(&vdom, &RenderOptions::new()),
+		"<div><section data-flag=beta><p>New!</p></section>Old.</div>"
+	);
+	assert_eq!(
+		render(
+			&vdom,
+			&RenderOptions::new().with_should_render(flag_enabled)
+		),
+		"<div>Old.</div>"
+	);
+}
+
+#[test]
+fn sees_creation_options_and_content() {
+	let vdom: Node<ThreadSafe> = Node::Multi(&[
+		Node::HtmlElement {
+			element: &Element {
+				name: "div",
+				creation_options: ElementCreationOptions::new().with_is(Some("x-widget")),
+				attributes: &[],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: &Element {
+				name: "div",
+				creation_options: ElementCreationOptions::new().with_is(Some("x-widget")),
+				attributes: &[],
+				content: Node::Text {
+					text: "a",
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		},
+	]);
+
+	assert_eq!(
+		render(
+			&vdom,
+			&RenderOptions::new().with_should_render(not_empty_widget)
+		),
+		"<div is=x-widget>a</div>"
+	);
+}
+
+#[test]
+fn default_renders_everything() {
+	assert!(RenderOptions::new().should_render()(&Element {
+		name: "div",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[]),
+		event_bindings: &[],
+	}));
+}
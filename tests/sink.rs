@@ -0,0 +1,111 @@
+use lignin::{Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_fragment_to_sink, Error, HtmlSink, RenderOptions, SinkError};
+
+const VDOM: Node<'static, ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "p",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Text {
+			text: "a < b",
+			dom_binding: None,
+		},
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Full {
+	attempted: usize,
+}
+
+/// Accepts at most `capacity` bytes.
+struct Limited {
+	written: String,
+	capacity: usize,
+}
+
+impl HtmlSink for Limited {
+	type Error = Full;
+
+	fn write_html(&mut self, s: &str) -> Result<(), Full> {
+		if self.written.len() + s.len() > self.capacity {
+			return Err(Full {
+				attempted: self.written.len() + s.len(),
+			});
+		}
+		self.written.push_str(s);
+		Ok(())
+	}
+}
+
+#[test]
+fn fmt_write() {
+	let mut html = String::new();
+	render_fragment_to_sink(&VDOM, &mut html, &RenderOptions::new()).unwrap();
+	assert_eq!(html, "<p>a &lt; b</p>");
+}
+
+#[test]
+fn custom_sink() {
+	let mut sink = Limited {
+		written: String::new(),
+		capacity: 100,
+	};
+	render_fragment_to_sink(&VDOM, &mut sink, &RenderOptions::new()).unwrap();
+	assert_eq!(sink.written, "<p>a &lt; b</p>");
+}
+
+#[test]
+fn sink_error() {
+	let mut sink = Limited {
+		written: String::new(),
+		capacity: 4,
+	};
+	assert_eq!(
+		render_fragment_to_sink(&VDOM, &mut sink, &RenderOptions::new()),
+		Err(SinkError::Sink(Full { attempted: 5 }))
+	);
+}
+
+#[test]
+fn render_error() {
+	let mut sink = Limited {
+		written: String::new(),
+		capacity: 100,
+	};
+	assert_eq!(
+		render_fragment_to_sink(&VDOM, &mut sink, &RenderOptions::new().with_depth_limit(1)),
+		Err(SinkError::Render(Error::depth_limit_exceeded(
+			match &VDOM {
+				Node::HtmlElement { element, .. } => &element.content,
+				_ => unreachable!(),
+			},
+			1
+		)))
+	);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_error() {
+	use lignin_html::IoSink;
+	use std::io::{self, ErrorKind, Write};
+
+	struct Broken;
+	impl Write for Broken {
+		fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+			Err(io::Error::new(ErrorKind::BrokenPipe, "closed"))
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	match render_fragment_to_sink(&VDOM, &mut IoSink(Broken), &RenderOptions::new()) {
+		Err(SinkError::Sink(error)) => assert_eq!(error.kind(), ErrorKind::BrokenPipe),
+		other => panic!("Expected an I/O error, got {:?}", other),
+	}
+}
@@ -0,0 +1,118 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment_with_options, RenderOptions};
+
+fn render_a(href: &str, options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "a",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[Attribute {
+					name: "href",
+					value: href,
+				}],
+				content: Node::Multi(&[]),
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+#[test]
+fn off_by_default() {
+	assert_eq!(
+		render_a("a&amp;b", &RenderOptions::new()),
+		"<a href=a&amp;amp;b></a>"
+	);
+}
+
+#[test]
+fn attribute_reference_kept() {
+	let options = RenderOptions::new().with_smart_ampersands(true);
+	assert_eq!(render_a("a&amp;b", &options), "<a href=a&amp;b></a>");
+	assert_eq!(
+		render_a("&#47;&#x2F;", &options),
+		"<a href=&#47;&#x2F;></a>"
+	);
+}
+
+#[test]
+fn attribute_ampersand_escaped() {
+	let options = RenderOptions::new().with_smart_ampersands(true);
+	assert_eq!(render_a("a&b", &options), "<a href=a&amp;b></a>");
+	assert_eq!(
+		render_a("?a=1&b=2", &options),
+		r#"<a href="?a=1&amp;b=2"></a>"#
+	);
+	assert_eq!(render_a("&amp", &options), "<a href=&amp;amp></a>");
+	assert_eq!(
+		render_a("&unknown;", &options),
+		"<a href=&amp;unknown;></a>"
+	);
+	assert_eq!(
+		render_a("&#;&#x;", &options),
+		"<a href=&amp;#;&amp;#x;></a>"
+	);
+}
+
+fn render_text(text: &str, options: &RenderOptions) -> String {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::Text {
+			text,
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		options,
+	)
+	.unwrap();
+	fragment
+}
+
+#[test]
+fn text() {
+	let options = RenderOptions::new().with_smart_ampersands(true);
+	assert_eq!(
+		render_text("5 &lt; 7 &amp; 8", &options),
+		"5 &lt; 7 &amp; 8"
+	);
+	assert_eq!(render_text("a & b", &options), "a &amp; b");
+	assert_eq!(render_text("&copy", &options), "&amp;copy");
+	assert_eq!(
+		render_text("5 &lt; 7", &RenderOptions::new()),
+		"5 &amp;lt; 7"
+	);
+}
+
+#[test]
+fn escapable_raw_text() {
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		&Node::HtmlElement {
+			element: &Element {
+				name: "title",
+				creation_options: ElementCreationOptions::new(),
+				attributes: &[],
+				content: Node::Text {
+					text: "Q&amp;A & more",
+					dom_binding: None,
+				},
+				event_bindings: &[],
+			},
+			dom_binding: None,
+		}
+		.prefer_thread_safe(),
+		&mut fragment,
+		&RenderOptions::new().with_smart_ampersands(true),
+	)
+	.unwrap();
+	assert_eq!(fragment, "<title>Q&amp;A &amp; more</title>");
+}
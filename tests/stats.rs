@@ -0,0 +1,47 @@
+use lignin::{Element, ElementCreationOptions, Node};
+use lignin_html::{render_fragment, render_fragment_with_stats};
+
+const VDOM: Node<'static, lignin::ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "DIV",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[],
+		content: Node::Multi(&[
+			Node::Text {
+				text: "a < b",
+				dom_binding: None,
+			},
+			Node::Comment {
+				comment: "c",
+				dom_binding: None,
+			},
+		]),
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn stats() {
+	let mut fragment = String::new();
+	let stats = render_fragment_with_stats(&VDOM, &mut fragment, 2).unwrap();
+	assert_eq!(fragment, "<DIV>a &lt; b<!--c--></DIV>");
+	assert_eq!(stats.max_depth, 2);
+	assert_eq!(stats.node_count, 4);
+	assert_eq!(stats.bytes_written, fragment.len());
+	assert_eq!(stats.html_elements, 1);
+	assert_eq!(stats.svg_elements, 0);
+	assert_eq!(stats.multi, 1);
+	assert_eq!(stats.text_nodes, 1);
+	assert_eq!(stats.comments, 1);
+	assert_eq!(stats.memoized, 0);
+	assert_eq!(stats.keyed, 0);
+}
+
+#[test]
+fn max_depth_is_tight() {
+	let mut fragment = String::new();
+	let stats = render_fragment_with_stats(&VDOM, &mut fragment, usize::MAX).unwrap();
+	render_fragment(&VDOM, &mut String::new(), stats.max_depth).unwrap();
+	render_fragment(&VDOM, &mut String::new(), stats.max_depth - 1).unwrap_err();
+}
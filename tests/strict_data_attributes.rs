@@ -0,0 +1,68 @@
+mod common;
+
+use common::{html_element, leak};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{render_fragment_with_options, Error, RenderOptions};
+
+fn render<'a>(attribute_name: &'a str, strict: bool) -> Result<String, Error<'a, ThreadSafe>> {
+	let vdom = leak(html_element(
+		"div",
+		leak([Attribute {
+			name: attribute_name,
+			value: "x",
+		}]),
+		Node::Multi(&[]),
+	));
+	let mut fragment = String::new();
+	render_fragment_with_options(
+		vdom,
+		&mut fragment,
+		&RenderOptions::new().with_strict_data_attributes(strict),
+	)?;
+	Ok(fragment)
+}
+
+#[test]
+fn off_by_default() {
+	assert!(!RenderOptions::new().strict_data_attributes());
+}
+
+#[test]
+fn lowercase_always_accepted() {
+	for &strict in &[false, true] {
+		assert_eq!(
+			render("data-foo", strict).unwrap(),
+			"<div data-foo=x></div>"
+		);
+		assert_eq!(
+			render("data-foo-bar", strict).unwrap(),
+			"<div data-foo-bar=x></div>"
+		);
+	}
+}
+
+#[test]
+fn uppercase_rejected_only_in_strict_mode() {
+	assert_eq!(
+		render("data-fooBar", false).unwrap(),
+		"<div data-fooBar=x></div>"
+	);
+	for &name in &["data-fooBar", "DATA-foo"] {
+		let error = render(name, true).unwrap_err();
+		assert_eq!(error, Error::uppercase_data_attribute_name(name));
+		assert_eq!(
+			error.to_string(),
+			format!(
+				"Invalid attribute name {:?}: `data-*` attribute names can't contain ASCII uppercase letters, \
+				 since parsers lowercase them (`data-foo-bar` is `dataset.fooBar`)",
+				name
+			)
+		);
+	}
+}
+
+#[test]
+fn other_attributes_unaffected() {
+	assert_eq!(render("onClick", true).unwrap(), "<div onClick=x></div>");
+	assert_eq!(render("dataFoo", true).unwrap(), "<div dataFoo=x></div>");
+}
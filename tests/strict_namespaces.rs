@@ -0,0 +1,50 @@
+mod common;
+
+use common::{html_element, render_with, svg_element};
+use lignin::{Node, ThreadSafe};
+use lignin_html::RenderOptions;
+
+fn element(name: &'static str, svg: bool) -> Node<'static, ThreadSafe> {
+	if svg {
+		svg_element(name, &[], Node::Multi(&[]))
+	} else {
+		html_element(name, &[], Node::Multi(&[]))
+	}
+}
+
+fn render(vdom: &Node<'static, ThreadSafe>, strict_namespaces: bool) -> Result<String, String> {
+	render_with(
+		vdom,
+		&RenderOptions::new().with_strict_namespaces(strict_namespaces),
+	)
+}
+
+#[test]
+fn html_circle() {
+	let vdom = element("circle", false);
+	assert_eq!(
+		render(&vdom, true).unwrap_err(),
+		"Element in wrong namespace: a `<circle>` element belongs to another namespace"
+	);
+	assert_eq!(render(&vdom, false).unwrap(), "<circle></circle>");
+}
+
+#[test]
+fn svg_div() {
+	render(&element("DIV", true), true).unwrap_err();
+}
+
+#[test]
+fn shared() {
+	assert_eq!(render(&element("a", false), true).unwrap(), "<a></a>");
+	assert_eq!(render(&element("a", true), true).unwrap(), "<a/>");
+	assert_eq!(render(&element("title", true), true).unwrap(), "<title/>");
+}
+
+#[test]
+fn unknown() {
+	assert_eq!(
+		render(&element("my-element", false), true).unwrap(),
+		"<my-element></my-element>"
+	);
+}
@@ -0,0 +1,32 @@
+use core::fmt::{self, Write};
+use lignin_html::Tee;
+
+struct Failing;
+impl Write for Failing {
+	fn write_str(&mut self, _: &str) -> fmt::Result {
+		Err(fmt::Error)
+	}
+}
+
+#[test]
+fn both() {
+	let mut a = String::new();
+	let mut b = String::new();
+	let mut tee = Tee(&mut a, &mut b);
+	tee.write_str("ab").unwrap();
+	tee.write_char('c').unwrap();
+	write!(tee, "{}", 1).unwrap();
+	assert_eq!(a, "abc1");
+	assert_eq!(b, "abc1");
+}
+
+#[test]
+fn errors() {
+	let mut a = String::new();
+	Tee(&mut a, Failing).write_str("a").unwrap_err();
+	assert_eq!(a, "a");
+
+	let mut b = String::new();
+	Tee(Failing, &mut b).write_str("b").unwrap_err();
+	assert_eq!(b, "");
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "testing")]
+
+use lignin_html::testing::{assert_html_eq, normalize_html};
+
+#[test]
+fn attribute_order_and_quoting() {
+	assert_html_eq(
+		"<DIV title='a \"b\"' id=x hidden></DIV>",
+		"<DIV hidden id=\"x\" title=\"a &quot;b&quot;\"></DIV>",
+	);
+}
+
+#[test]
+fn whitespace() {
+	assert_html_eq(
+		"<ul>\n\t<li>one  two</li>\n\t<li\n>three</li>\n</ul>",
+		"<ul><li>one two</li><li>three</li></ul>",
+	);
+}
+
+#[test]
+fn self_closing() {
+	assert_eq!(normalize_html("<use x=#y />"), "<use x=\"#y\" />");
+	assert_eq!(normalize_html("<use x=a/b>"), "<use x=\"a/b\">");
+}
+
+#[test]
+fn comments_and_doctype_are_kept() {
+	assert_eq!(
+		normalize_html("<!DOCTYPE html><!--  a  <b c=d>  -->"),
+		"<!DOCTYPE html><!--  a  <b c=d>  -->"
+	);
+}
+
+#[test]
+#[should_panic(expected = "HTML differs after normalization")]
+fn different_text() {
+	assert_html_eq("<p>a</p>", "<p>b</p>");
+}
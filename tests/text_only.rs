@@ -0,0 +1,147 @@
+use lignin::{Attribute, Element, ElementCreationOptions, Node, ThreadSafe};
+use lignin_html::{render_text_only, render_text_only_with_options, TextOnlyOptions};
+
+mod common;
+use common::{comment, html_element, text};
+
+fn render(vdom: &Node<ThreadSafe>, depth_limit: usize) -> Result<String, String> {
+	let mut text = String::new();
+	render_text_only(vdom, &mut text, depth_limit).map_err(|error| error.to_string())?;
+	Ok(text)
+}
+
+fn render_with(vdom: &Node<ThreadSafe>, options: TextOnlyOptions) -> String {
+	let mut text = String::new();
+	render_text_only_with_options(vdom, &mut text, 100, options).unwrap();
+	text
+}
+
+const VDOM: Node<'static, ThreadSafe> = Node::HtmlElement {
+	element: &Element {
+		name: "p",
+		creation_options: ElementCreationOptions::new(),
+		attributes: &[Attribute {
+			name: "title",
+			value: "not text",
+		}],
+		content: Node::Multi(&[
+			Node::Text {
+				text: "1 < 2 ",
+				dom_binding: None,
+			},
+			Node::Comment {
+				comment: "skipped",
+				dom_binding: None,
+			},
+			Node::HtmlElement {
+				element: &Element {
+					name: "b",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					content: Node::Text {
+						text: "&amp;",
+						dom_binding: None,
+					},
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			},
+			Node::HtmlElement {
+				element: &Element {
+					name: "script",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					content: Node::Text {
+						text: " x()",
+						dom_binding: None,
+					},
+					event_bindings: &[],
+				},
+				dom_binding: None,
+			},
+		]),
+		event_bindings: &[],
+	},
+	dom_binding: None,
+};
+
+#[test]
+fn text_only() {
+	assert_eq!(render(&VDOM, 3).unwrap(), "1 < 2 &amp; x()");
+}
+
+#[test]
+fn depth_limit() {
+	assert_eq!(
+		render(&VDOM, 2).unwrap_err(),
+		"Depth limit of 2 exceeded at a text node"
+	);
+}
+
+#[test]
+fn collapse_whitespace() {
+	let vdom = html_element(
+		"span",
+		&[],
+		Node::Multi(common::leak([
+			text(" \t1  2\n"),
+			comment("skipped"),
+			text(" 3\r\n"),
+			html_element("pre", &[], text(" 4\n\n5 ")),
+			text(" "),
+		])),
+	);
+	assert_eq!(
+		render_with(&vdom, TextOnlyOptions::new().with_collapse_whitespace(true)),
+		"1 2 3  4\n\n5 "
+	);
+	assert_eq!(
+		render_with(&vdom, TextOnlyOptions::new()),
+		" \t1  2\n 3\r\n 4\n\n5  "
+	);
+}
+
+#[test]
+fn block_boundaries() {
+	let vdom = html_element(
+		"div",
+		&[],
+		Node::Multi(common::leak([
+			html_element("h1", &[], text("Title")),
+			html_element(
+				"p",
+				&[],
+				Node::Multi(common::leak([
+					text("a "),
+					html_element("b", &[], text("b")),
+				])),
+			),
+			html_element(
+				"ul",
+				&[],
+				Node::Multi(common::leak([
+					html_element("li", &[], text(" 1 ")),
+					html_element("li", &[], text("2")),
+				])),
+			),
+			text("tail"),
+		])),
+	);
+	assert_eq!(
+		render_with(&vdom, TextOnlyOptions::new().with_block_boundaries(true)),
+		"Title\na b\n 1 \n2\ntail"
+	);
+	assert_eq!(
+		render_with(
+			&vdom,
+			TextOnlyOptions::new()
+				.with_block_boundaries(true)
+				.with_collapse_whitespace(true)
+		),
+		"Title\na b\n1\n2\ntail"
+	);
+	assert_eq!(
+		render_with(&vdom, TextOnlyOptions::new()),
+		"Titlea b 1 2tail"
+	);
+}
@@ -0,0 +1,82 @@
+use lignin::ThreadSafe;
+use lignin_html::write_token_list_attribute;
+
+fn render(name: &str, tokens: &[&str], deduplicate: bool) -> Result<String, String> {
+	let mut rendered = String::new();
+	write_token_list_attribute::<ThreadSafe, _>(
+		name,
+		tokens.iter().copied(),
+		deduplicate,
+		&mut rendered,
+	)
+	.map_err(|error| error.to_string())?;
+	Ok(rendered)
+}
+
+#[test]
+fn joined() {
+	assert_eq!(
+		render("class", &["btn", "btn-primary"], false).unwrap(),
+		" class=\"btn btn-primary\""
+	);
+}
+
+#[test]
+fn single() {
+	assert_eq!(
+		render("rel", &["noopener"], false).unwrap(),
+		" rel=\"noopener\""
+	);
+}
+
+#[test]
+fn escaped() {
+	assert_eq!(
+		render("class", &["a&b", "\"c\"", "<d>"], false).unwrap(),
+		" class=\"a&amp;b &quot;c&quot; <d>\""
+	);
+}
+
+#[test]
+fn empty_list_omits_attribute() {
+	assert_eq!(render("class", &[], false).unwrap(), "");
+	assert_eq!(render("class", &["", ""], false).unwrap(), "");
+}
+
+#[test]
+fn empty_tokens_skipped() {
+	assert_eq!(
+		render("class", &["", "a", "", "b"], false).unwrap(),
+		" class=\"a b\""
+	);
+}
+
+#[test]
+fn deduplicate() {
+	assert_eq!(
+		render("class", &["a", "b", "a", "c", "b"], false).unwrap(),
+		" class=\"a b a c b\""
+	);
+	assert_eq!(
+		render("class", &["a", "b", "a", "c", "b"], true).unwrap(),
+		" class=\"a b c\""
+	);
+}
+
+#[test]
+fn invalid_name() {
+	assert_eq!(
+		render("a b", &["c"], false).unwrap_err(),
+		render_attribute_error("a b")
+	);
+	assert!(render("is", &["c"], false).is_err());
+}
+
+fn render_attribute_error(name: &str) -> String {
+	lignin_html::render_attributes::<ThreadSafe>(
+		&[lignin::Attribute { name, value: "" }],
+		&mut String::new(),
+	)
+	.unwrap_err()
+	.to_string()
+}
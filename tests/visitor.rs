@@ -0,0 +1,69 @@
+mod common;
+
+use common::element;
+use lignin::Node;
+use lignin_html::{render_fragment_with_visitor, Error};
+
+#[test]
+fn counts_elements() {
+	let b = element("b", &[], Node::Multi(&[]));
+	let children = [
+		Node::HtmlElement {
+			element: b,
+			dom_binding: None,
+		},
+		Node::Text {
+			text: "x",
+			dom_binding: None,
+		},
+		Node::HtmlElement {
+			element: b,
+			dom_binding: None,
+		},
+	];
+	let p = element("p", &[], Node::Multi(&children));
+	let vdom = Node::HtmlElement {
+		element: p,
+		dom_binding: None,
+	};
+
+	let mut elements = 0;
+	let mut nodes = 0;
+	let mut html = String::new();
+	render_fragment_with_visitor(&vdom, &mut html, 4, &mut |node| {
+		nodes += 1;
+		if let Node::HtmlElement { .. } = node {
+			elements += 1;
+		}
+		Ok(())
+	})
+	.unwrap();
+	assert_eq!(html, "<p><b></b>x<b></b></p>");
+	assert_eq!(elements, 3);
+	// Including the `Multi`s inside the elements.
+	assert_eq!(nodes, 7);
+}
+
+#[test]
+fn rejects_element() {
+	let iframe = element("iframe", &[], Node::Multi(&[]));
+	let children = [Node::HtmlElement {
+		element: iframe,
+		dom_binding: None,
+	}];
+	let div = element("div", &[], Node::Multi(&children));
+	let vdom = Node::HtmlElement {
+		element: div,
+		dom_binding: None,
+	};
+
+	let mut html = String::new();
+	let error = render_fragment_with_visitor(&vdom, &mut html, 4, &mut |node| match node {
+		Node::HtmlElement { element, .. } if element.name == "iframe" => Err(Error::rejected(node)),
+		_ => Ok(()),
+	})
+	.unwrap_err();
+	assert_eq!(error, Error::rejected(&children[0]));
+	assert_eq!(error.to_string(), "a `<iframe>` element was rejected");
+	assert_eq!(html, "<div>");
+}
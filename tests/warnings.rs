@@ -0,0 +1,67 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{comment, html_element, leak};
+use lignin::{Attribute, Node, ThreadSafe};
+use lignin_html::{render_fragment, render_fragment_with_warnings, Warning};
+
+fn render(vdom: &Node<ThreadSafe>) -> (String, Vec<Warning>) {
+	let mut html = String::new();
+	let warnings = render_fragment_with_warnings(vdom, &mut html, 10).unwrap();
+	(html, warnings)
+}
+
+#[test]
+fn none() {
+	let vdom = html_element(
+		"html",
+		&[Attribute {
+			name: "lang",
+			value: "en",
+		}],
+		Node::Multi(leak([
+			comment("-> fine <!-"),
+			html_element(
+				"img",
+				&[Attribute {
+					name: "alt",
+					value: "",
+				}],
+				Node::Multi(&[]),
+			),
+		])),
+	);
+	let mut expected = String::new();
+	render_fragment(&vdom, &mut expected, 10).unwrap();
+	assert_eq!(render(&vdom), (expected, vec![]));
+}
+
+#[test]
+fn all() {
+	let vdom = html_element(
+		"html",
+		&[],
+		Node::Multi(leak([
+			comment("a-->b"),
+			html_element("img", &[], Node::Multi(&[])),
+			comment("c--!>d<!--e"),
+		])),
+	);
+	let (html, warnings) = render(&vdom);
+	assert_eq!(html, "<html><!--a==>b--><img><!--c==!>d<!==e--></html>");
+	assert_eq!(
+		warnings,
+		[
+			Warning::MissingLangAttribute(vec![]),
+			Warning::CommentLegalized(vec![0, 0]),
+			Warning::MissingAltAttribute(vec![0, 1]),
+			Warning::CommentLegalized(vec![0, 2]),
+		]
+	);
+	assert_eq!(warnings[2].path(), &[0, 1]);
+	assert_eq!(
+		warnings[2].to_string(),
+		"The `img` element at [0, 1] has no `alt` attribute"
+	);
+}
@@ -0,0 +1,38 @@
+use lignin_html::{whitespace_sensitivity, WhitespaceSensitivity};
+
+#[test]
+fn pre() {
+	assert_eq!(whitespace_sensitivity("pre"), WhitespaceSensitivity::Pre);
+	assert_eq!(
+		whitespace_sensitivity("TextArea"),
+		WhitespaceSensitivity::Pre
+	);
+	assert_eq!(whitespace_sensitivity("script"), WhitespaceSensitivity::Pre);
+}
+
+#[test]
+fn span() {
+	assert_eq!(
+		whitespace_sensitivity("span"),
+		WhitespaceSensitivity::Inline
+	);
+	assert_eq!(whitespace_sensitivity("a"), WhitespaceSensitivity::Inline);
+}
+
+#[test]
+fn div() {
+	assert_eq!(whitespace_sensitivity("div"), WhitespaceSensitivity::Block);
+	assert_eq!(whitespace_sensitivity("P"), WhitespaceSensitivity::Block);
+}
+
+#[test]
+fn unknown_is_inline() {
+	assert_eq!(
+		whitespace_sensitivity("x-widget"),
+		WhitespaceSensitivity::Inline
+	);
+	assert_eq!(
+		whitespace_sensitivity("pre-"),
+		WhitespaceSensitivity::Inline
+	);
+}